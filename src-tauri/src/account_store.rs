@@ -1,6 +1,7 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use std::sync::{Mutex, MutexGuard};
+use std::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager};
@@ -8,16 +9,21 @@ use uuid::Uuid;
 
 use crate::error::{BackendError, Result};
 use crate::models::{
-    is_valid_provider_id, is_valid_strategy_id, normalize_optional_string, normalize_string,
-    AccountRecord, CreateAccountInput, EncryptedCredentials, UpdateAccountInput,
+    is_valid_icon_override, is_valid_provider_id, is_valid_strategy_id, is_valid_tag,
+    normalize_optional_string, normalize_string, AccountErrorInfo, AccountRecord,
+    AccountWithCredentialStatus, CreateAccountInput, EncryptedCredentials, UpdateAccountInput,
+    MAX_NOTES_CHARS,
 };
 use crate::providers::{
-    find_provider_contract, validate_auth_strategy_for_provider, validate_provider_settings,
+    find_provider_contract, find_provider_runtime, validate_auth_strategy_for_provider,
+    validate_provider_settings, ProbeSuccess,
 };
-use crate::utils::now_rfc3339;
+use crate::utils::{rfc3339_to_unix_ms, write_file_atomic, ClockProvider, SystemClock};
 
 const STORE_FILE_NAME: &str = "accounts.json";
-const STORE_SCHEMA_VERSION: u32 = 1;
+const STORE_SCHEMA_VERSION: u32 = 2;
+// Gap between consecutive `sort_order` values so moves can land in between.
+const SORT_ORDER_STEP: i64 = 100;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -41,9 +47,12 @@ struct AccountStoreState {
 }
 
 #[derive(Debug)]
-pub struct AccountStore {
+pub struct AccountStore<C: ClockProvider = SystemClock> {
     path: PathBuf,
-    state: Mutex<AccountStoreState>,
+    state: RwLock<AccountStoreState>,
+    // Last successful probe result per account; in-memory only.
+    output_cache: Mutex<HashMap<String, ProbeSuccess>>,
+    clock: C,
 }
 
 impl AccountStore {
@@ -58,6 +67,12 @@ impl AccountStore {
     }
 
     fn load_from_path(path: PathBuf) -> Result<Self> {
+        Self::load_with_clock(path, SystemClock)
+    }
+}
+
+impl<C: ClockProvider> AccountStore<C> {
+    fn load_with_clock(path: PathBuf, clock: C) -> Result<Self> {
         let state = match fs::read_to_string(&path) {
             Ok(contents) => {
                 if contents.trim().is_empty() {
@@ -72,79 +87,195 @@ impl AccountStore {
 
         Ok(Self {
             path,
-            state: Mutex::new(state),
+            state: RwLock::new(state),
+            output_cache: Mutex::new(HashMap::new()),
+            clock,
         })
     }
 
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
     pub fn list_accounts(&self) -> Result<Vec<AccountRecord>> {
-        let state = self.lock_state()?;
-        let mut accounts = state.accounts.clone();
-        accounts.sort_by(|a, b| {
-            a.created_at
-                .cmp(&b.created_at)
-                .then_with(|| a.id.cmp(&b.id))
-        });
+        let state = self.read_state()?;
+        let mut accounts = state
+            .accounts
+            .iter()
+            .filter(|account| account.deleted_at.is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+        accounts.sort_by(compare_account_order);
+        Ok(accounts)
+    }
+
+    /// Gives the listed accounts ascending `sort_order` values in the given
+    /// order. Live accounts that are not listed keep their relative order
+    /// after them.
+    pub fn reorder_accounts(&self, account_ids: &[String]) -> Result<()> {
+        let mut state = self.write_state()?;
+        let current = ordered_live_account_ids(&state);
+        let mut order: Vec<String> = Vec::with_capacity(current.len());
+        for account_id in account_ids {
+            let account_id = account_id.trim();
+            if !current.iter().any(|id| id == account_id) {
+                return Err(BackendError::AccountNotFound);
+            }
+            if order.iter().any(|id| id == account_id) {
+                return Err(BackendError::Validation(format!(
+                    "account '{account_id}' is listed more than once"
+                )));
+            }
+            order.push(account_id.to_string());
+        }
+        for account_id in current {
+            if !order.contains(&account_id) {
+                order.push(account_id);
+            }
+        }
+        assign_sort_orders(&mut state, &order);
+        self.save_locked(&state)
+    }
+
+    /// Moves `account_id` directly before `target_id`, taking the midpoint of
+    /// the neighbouring `sort_order` values and renumbering everything when no
+    /// gap is left.
+    pub fn move_account_before(&self, account_id: &str, target_id: &str) -> Result<()> {
+        let (account_id, target_id) = (account_id.trim(), target_id.trim());
+        let mut state = self.write_state()?;
+        let mut order = ordered_live_account_ids(&state);
+        if !order.iter().any(|id| id == account_id) || !order.iter().any(|id| id == target_id) {
+            return Err(BackendError::AccountNotFound);
+        }
+        if account_id == target_id {
+            return Ok(());
+        }
+
+        order.retain(|id| id != account_id);
+        let target_index = order
+            .iter()
+            .position(|id| id == target_id)
+            .ok_or(BackendError::AccountNotFound)?;
+        let sort_order_of = |id: &str| {
+            state
+                .accounts
+                .iter()
+                .find(|account| account.id == id)
+                .and_then(|account| account.sort_order)
+        };
+        let next = sort_order_of(target_id);
+        let previous = target_index
+            .checked_sub(1)
+            .map(|index| sort_order_of(&order[index]));
+        let midpoint = match (previous, next) {
+            (None, Some(next)) => Some(next - SORT_ORDER_STEP),
+            (Some(Some(previous)), Some(next)) if next - previous > 1 => {
+                Some(previous + (next - previous) / 2)
+            }
+            _ => None,
+        };
+
+        match midpoint {
+            Some(sort_order) => {
+                if let Some(account) = state
+                    .accounts
+                    .iter_mut()
+                    .find(|account| account.id == account_id)
+                {
+                    account.sort_order = Some(sort_order);
+                }
+            }
+            None => {
+                order.insert(target_index, account_id.to_string());
+                assign_sort_orders(&mut state, &order);
+            }
+        }
+        self.save_locked(&state)
+    }
+
+    /// `list_accounts` with each account's credential presence, read from the
+    /// same snapshot so no per-account lookups are needed.
+    pub fn list_accounts_with_credential_status(&self) -> Result<Vec<AccountWithCredentialStatus>> {
+        Ok(self
+            .list_accounts()?
+            .into_iter()
+            .map(AccountWithCredentialStatus::from)
+            .collect())
+    }
+
+    /// Live accounts for one provider, in `list_accounts` order.
+    pub fn find_accounts_by_provider(&self, provider_id: &str) -> Result<Vec<AccountRecord>> {
+        let state = self.read_state()?;
+        let mut accounts = state
+            .accounts
+            .iter()
+            .filter(|account| account.provider_id == provider_id && account.deleted_at.is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+        accounts.sort_by(compare_account_order);
         Ok(accounts)
     }
 
+    /// Live accounts carrying `tag` (case-insensitive), in `list_accounts` order.
+    pub fn filter_accounts_by_tag(&self, tag: &str) -> Result<Vec<AccountRecord>> {
+        let tag = tag.trim().to_ascii_lowercase();
+        Ok(self
+            .list_accounts()?
+            .into_iter()
+            .filter(|account| {
+                account
+                    .tags
+                    .as_ref()
+                    .is_some_and(|tags| tags.contains(&tag))
+            })
+            .collect())
+    }
+
+    pub fn count_accounts_by_provider(&self, provider_id: &str) -> Result<usize> {
+        let state = self.read_state()?;
+        Ok(state
+            .accounts
+            .iter()
+            .filter(|account| account.provider_id == provider_id && account.deleted_at.is_none())
+            .count())
+    }
+
+    /// Live accounts only; deleted ones are reached through
+    /// `list_deleted_accounts`, `restore_account` and `purge_account`.
     pub fn get_account(&self, account_id: &str) -> Result<Option<AccountRecord>> {
         let account_id = account_id.trim();
         if account_id.is_empty() {
             return Ok(None);
         }
-        let state = self.lock_state()?;
+        let state = self.read_state()?;
         Ok(state
             .accounts
             .iter()
-            .find(|account| account.id == account_id)
+            .find(|account| account.id == account_id && account.deleted_at.is_none())
             .cloned())
     }
 
-    pub fn create_account(&self, input: CreateAccountInput) -> Result<AccountRecord> {
-        let provider_id = normalize_string(&input.provider_id)
-            .map(|value| value.to_ascii_lowercase())
-            .ok_or_else(|| BackendError::Validation("providerId is required".to_string()))?;
-        if !is_valid_provider_id(&provider_id) {
-            return Err(BackendError::Validation(
-                "providerId must match ^[a-z0-9][a-z0-9._-]{1,63}$".to_string(),
-            ));
-        }
-        let provider = find_provider_contract(&provider_id)
-            .ok_or_else(|| BackendError::Validation("providerId is not registered".to_string()))?;
+    pub fn get_account_with_credential_status(
+        &self,
+        account_id: &str,
+    ) -> Result<Option<AccountWithCredentialStatus>> {
+        Ok(self
+            .get_account(account_id)?
+            .map(AccountWithCredentialStatus::from))
+    }
 
-        let auth_strategy_id = match normalize_optional_string(input.auth_strategy_id) {
-            Some(strategy_id) => {
-                if !is_valid_strategy_id(&strategy_id) {
-                    return Err(BackendError::Validation(
-                        "authStrategyId must match ^[a-zA-Z][a-zA-Z0-9._-]{1,63}$".to_string(),
-                    ));
-                }
-                validate_auth_strategy_for_provider(provider, Some(&strategy_id))
-                    .map_err(BackendError::Validation)?;
-                Some(strategy_id)
-            }
-            None => None,
-        };
+    pub fn get_account_last_error(&self, account_id: &str) -> Result<Option<AccountErrorInfo>> {
+        Ok(self
+            .get_account(account_id)?
+            .as_ref()
+            .and_then(AccountErrorInfo::from_account))
+    }
 
-        let label = normalize_optional_string(input.label).unwrap_or_else(|| provider_id.clone());
-        let settings = input.settings.unwrap_or_else(|| serde_json::json!({}));
-        validate_provider_settings(provider, &settings).map_err(BackendError::Validation)?;
-
-        let now = now_rfc3339();
-        let account = AccountRecord {
-            id: Uuid::new_v4().to_string(),
-            provider_id,
-            auth_strategy_id,
-            label,
-            settings,
-            credentials: None,
-            created_at: now.clone(),
-            updated_at: now,
-            last_fetch_at: None,
-            last_error: None,
-        };
+    pub fn create_account(&self, input: CreateAccountInput) -> Result<AccountRecord> {
+        let account = new_account_record(input, &self.clock.now_rfc3339())?;
 
-        let mut state = self.lock_state()?;
+        let mut state = self.write_state()?;
+        ensure_account_capacity(&state, &account.provider_id)?;
         state.accounts.push(account.clone());
         self.save_locked(&state)?;
         Ok(account)
@@ -162,11 +293,11 @@ impl AccountStore {
             ));
         }
 
-        let mut state = self.lock_state()?;
+        let mut state = self.write_state()?;
         let account_index = state
             .accounts
             .iter()
-            .position(|account| account.id == account_id)
+            .position(|account| account.id == account_id && account.deleted_at.is_none())
             .ok_or(BackendError::AccountNotFound)?;
         let mut account = state.accounts[account_index].clone();
         let provider = find_provider_contract(&account.provider_id).ok_or_else(|| {
@@ -203,21 +334,98 @@ impl AccountStore {
 
         if input.clear_last_error {
             account.last_error = None;
+            account.last_error_at = None;
         }
 
-        account.updated_at = now_rfc3339();
+        if let Some(disabled) = input.disabled {
+            account.disabled = disabled.then_some(true);
+        }
+
+        if let Some(tags) = input.tags {
+            account.tags = normalize_tags(tags)?;
+        }
+
+        if let Some(notes) = input.notes {
+            account.notes = normalize_notes(notes)?;
+        }
+
+        account.updated_at = self.clock.now_rfc3339();
         state.accounts[account_index] = account.clone();
         self.save_locked(&state)?;
         Ok(account)
     }
 
+    /// Soft-deletes an account; it stays recoverable via `restore_account`
+    /// until purged.
     pub fn delete_account(&self, account_id: &str) -> Result<Option<AccountRecord>> {
         let account_id = account_id.trim();
         if account_id.is_empty() {
             return Ok(None);
         }
 
-        let mut state = self.lock_state()?;
+        let mut state = self.write_state()?;
+        let Some(account) = state
+            .accounts
+            .iter_mut()
+            .find(|account| account.id == account_id && account.deleted_at.is_none())
+        else {
+            return Ok(None);
+        };
+        let now = self.clock.now_rfc3339();
+        account.deleted_at = Some(now.clone());
+        account.updated_at = now;
+        let deleted = account.clone();
+        self.save_locked(&state)?;
+        self.invalidate_cached_output(account_id)?;
+        Ok(Some(deleted))
+    }
+
+    pub fn list_deleted_accounts(&self) -> Result<Vec<AccountRecord>> {
+        let state = self.read_state()?;
+        let mut accounts = state
+            .accounts
+            .iter()
+            .filter(|account| account.deleted_at.is_some())
+            .cloned()
+            .collect::<Vec<_>>();
+        accounts.sort_by(|a, b| {
+            b.deleted_at
+                .cmp(&a.deleted_at)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        Ok(accounts)
+    }
+
+    pub fn restore_account(&self, account_id: &str) -> Result<AccountRecord> {
+        let account_id = account_id.trim();
+        let mut state = self.write_state()?;
+        let provider_id = state
+            .accounts
+            .iter()
+            .find(|account| account.id == account_id && account.deleted_at.is_some())
+            .map(|account| account.provider_id.clone())
+            .ok_or(BackendError::AccountNotFound)?;
+        ensure_account_capacity(&state, &provider_id)?;
+        let account = state
+            .accounts
+            .iter_mut()
+            .find(|account| account.id == account_id && account.deleted_at.is_some())
+            .ok_or(BackendError::AccountNotFound)?;
+        account.deleted_at = None;
+        account.updated_at = self.clock.now_rfc3339();
+        let restored = account.clone();
+        self.save_locked(&state)?;
+        Ok(restored)
+    }
+
+    /// Permanently removes an account, deleted or not.
+    pub fn purge_account(&self, account_id: &str) -> Result<Option<AccountRecord>> {
+        let account_id = account_id.trim();
+        if account_id.is_empty() {
+            return Ok(None);
+        }
+
+        let mut state = self.write_state()?;
         let index = state
             .accounts
             .iter()
@@ -225,10 +433,117 @@ impl AccountStore {
         let removed = index.map(|index| state.accounts.remove(index));
         if removed.is_some() {
             self.save_locked(&state)?;
+            self.invalidate_cached_output(account_id)?;
         }
         Ok(removed)
     }
 
+    pub fn purge_accounts_older_than(&self, days: u32) -> Result<Vec<AccountRecord>> {
+        let cutoff_ms = self.clock.now_unix_ms() - i64::from(days) * 24 * 60 * 60 * 1000;
+        let mut state = self.write_state()?;
+        let (purged, kept): (Vec<_>, Vec<_>) =
+            state.accounts.iter().cloned().partition(|account| {
+                account
+                    .deleted_at
+                    .as_deref()
+                    .and_then(rfc3339_to_unix_ms)
+                    .is_some_and(|deleted_ms| deleted_ms <= cutoff_ms)
+            });
+        if purged.is_empty() {
+            return Ok(purged);
+        }
+        // A failed save leaves the in-memory accounts matching the file.
+        let next = AccountStoreState { accounts: kept };
+        self.save_locked(&next)?;
+        *state = next;
+        Ok(purged)
+    }
+
+    /// Serializes all accounts in the store file format with credentials stripped.
+    pub fn export_accounts(&self) -> Result<String> {
+        let state = self.read_state()?;
+        let payload = AccountStoreFile {
+            schema_version: STORE_SCHEMA_VERSION,
+            accounts: state
+                .accounts
+                .iter()
+                .filter(|account| account.deleted_at.is_none())
+                .cloned()
+                .map(|mut account| {
+                    account.credentials = None;
+                    account
+                })
+                .collect(),
+        };
+        Ok(serde_json::to_string_pretty(&payload)?)
+    }
+
+    /// Imports accounts from an `export_accounts` payload under fresh ids,
+    /// validating each record like `create_account`. Records matching a live
+    /// account by id or by provider and label are skipped. With `overwrite`
+    /// the live accounts are soft-deleted first, keeping their credentials so
+    /// they can be restored.
+    pub fn import_accounts(&self, contents: &str, overwrite: bool) -> Result<Vec<AccountRecord>> {
+        let imported = parse_store_contents(contents)?;
+        let now = self.clock.now_rfc3339();
+
+        let mut state = self.write_state()?;
+        let mut next = AccountStoreState {
+            accounts: state.accounts.clone(),
+        };
+        if overwrite {
+            for account in next
+                .accounts
+                .iter_mut()
+                .filter(|account| account.deleted_at.is_none())
+            {
+                account.deleted_at = Some(now.clone());
+                account.updated_at = now.clone();
+            }
+        }
+
+        let mut accounts = Vec::with_capacity(imported.accounts.len());
+        for record in imported.accounts {
+            let original_id = record.id.clone();
+            let account = imported_account_record(record, &now)?;
+            let is_duplicate = next.accounts.iter().any(|existing| {
+                existing.deleted_at.is_none()
+                    && (existing.id == original_id
+                        || (existing.provider_id == account.provider_id
+                            && existing.label == account.label))
+            });
+            if is_duplicate {
+                continue;
+            }
+            ensure_account_capacity(&next, &account.provider_id)?;
+            next.accounts.push(account.clone());
+            accounts.push(account);
+        }
+
+        if overwrite || !accounts.is_empty() {
+            self.save_locked(&next)?;
+            *state = next;
+        }
+        Ok(accounts)
+    }
+
+    pub fn get_cached_output(&self, account_id: &str) -> Result<Option<ProbeSuccess>> {
+        let cache = self.lock_output_cache()?;
+        Ok(cache.get(account_id).cloned())
+    }
+
+    pub fn set_cached_output(&self, account_id: &str, output: ProbeSuccess) -> Result<()> {
+        let mut cache = self.lock_output_cache()?;
+        cache.insert(account_id.to_string(), output);
+        Ok(())
+    }
+
+    fn invalidate_cached_output(&self, account_id: &str) -> Result<()> {
+        let mut cache = self.lock_output_cache()?;
+        cache.remove(account_id);
+        Ok(())
+    }
+
     pub fn record_probe_success(&self, account_id: &str) -> Result<()> {
         let account_id = account_id.trim();
         if account_id.is_empty() {
@@ -237,16 +552,18 @@ impl AccountStore {
             ));
         }
 
-        let mut state = self.lock_state()?;
+        let mut state = self.write_state()?;
         let account = state
             .accounts
             .iter_mut()
-            .find(|account| account.id == account_id)
+            .find(|account| account.id == account_id && account.deleted_at.is_none())
             .ok_or(BackendError::AccountNotFound)?;
 
-        let now = now_rfc3339();
+        let now = self.clock.now_rfc3339();
         account.last_fetch_at = Some(now.clone());
         account.last_error = None;
+        account.last_error_at = None;
+        account.rate_limited_until = None;
         account.updated_at = now;
         self.save_locked(&state)?;
         Ok(())
@@ -260,15 +577,85 @@ impl AccountStore {
             ));
         }
 
-        let mut state = self.lock_state()?;
+        let mut state = self.write_state()?;
         let account = state
             .accounts
             .iter_mut()
-            .find(|account| account.id == account_id)
+            .find(|account| account.id == account_id && account.deleted_at.is_none())
             .ok_or(BackendError::AccountNotFound)?;
 
+        let now = self.clock.now_rfc3339();
         account.last_error = Some(message.to_string());
-        account.updated_at = now_rfc3339();
+        account.last_error_at = Some(now.clone());
+        account.updated_at = now;
+        self.save_locked(&state)?;
+        Ok(())
+    }
+
+    pub fn set_rate_limited_until(&self, account_id: &str, until: Option<String>) -> Result<()> {
+        let account_id = account_id.trim();
+        if account_id.is_empty() {
+            return Err(BackendError::Validation(
+                "accountId is required".to_string(),
+            ));
+        }
+
+        let mut state = self.write_state()?;
+        let account = state
+            .accounts
+            .iter_mut()
+            .find(|account| account.id == account_id && account.deleted_at.is_none())
+            .ok_or(BackendError::AccountNotFound)?;
+
+        account.rate_limited_until = until;
+        account.updated_at = self.clock.now_rfc3339();
+        self.save_locked(&state)?;
+        Ok(())
+    }
+
+    pub fn set_icon_override(&self, account_id: &str, icon: Option<String>) -> Result<()> {
+        let account_id = account_id.trim();
+        if account_id.is_empty() {
+            return Err(BackendError::Validation(
+                "accountId is required".to_string(),
+            ));
+        }
+        let icon = normalize_icon_override(icon)?;
+
+        let mut state = self.write_state()?;
+        let account = state
+            .accounts
+            .iter_mut()
+            .find(|account| account.id == account_id && account.deleted_at.is_none())
+            .ok_or(BackendError::AccountNotFound)?;
+
+        account.icon_override = icon;
+        account.updated_at = self.clock.now_rfc3339();
+        self.save_locked(&state)?;
+        Ok(())
+    }
+
+    pub fn set_credentials_expires_at(
+        &self,
+        account_id: &str,
+        expires_at: Option<String>,
+    ) -> Result<()> {
+        let account_id = account_id.trim();
+        if account_id.is_empty() {
+            return Err(BackendError::Validation(
+                "accountId is required".to_string(),
+            ));
+        }
+
+        let mut state = self.write_state()?;
+        let account = state
+            .accounts
+            .iter_mut()
+            .find(|account| account.id == account_id && account.deleted_at.is_none())
+            .ok_or(BackendError::AccountNotFound)?;
+
+        account.credentials_expires_at = expires_at;
+        account.updated_at = self.clock.now_rfc3339();
         self.save_locked(&state)?;
         Ok(())
     }
@@ -285,17 +672,43 @@ impl AccountStore {
             ));
         }
 
-        let mut state = self.lock_state()?;
+        let mut state = self.write_state()?;
         let account = state
             .accounts
             .iter_mut()
-            .find(|account| account.id == account_id)
+            .find(|account| account.id == account_id && account.deleted_at.is_none())
             .ok_or(BackendError::AccountNotFound)?;
         account.credentials = Some(encrypted);
         self.save_locked(&state)?;
         Ok(())
     }
 
+    /// Writes re-encrypted blobs in a single save. Each update carries the nonce
+    /// of the blob it replaces; blobs changed since then are left as they are.
+    pub fn replace_credentials_blobs(
+        &self,
+        updates: Vec<(String, String, EncryptedCredentials)>,
+    ) -> Result<usize> {
+        let mut state = self.write_state()?;
+        let mut replaced = 0;
+        for (account_id, previous_nonce, encrypted) in updates {
+            let Some(account) = state
+                .accounts
+                .iter_mut()
+                .find(|account| account.id == account_id)
+            else {
+                continue;
+            };
+            if account.credentials.as_ref().map(|current| &current.nonce) != Some(&previous_nonce) {
+                continue;
+            }
+            account.credentials = Some(encrypted);
+            replaced += 1;
+        }
+        self.save_locked(&state)?;
+        Ok(replaced)
+    }
+
     pub fn get_credentials_blob(&self, account_id: &str) -> Result<Option<EncryptedCredentials>> {
         let account_id = account_id.trim();
         if account_id.is_empty() {
@@ -304,7 +717,7 @@ impl AccountStore {
             ));
         }
 
-        let state = self.lock_state()?;
+        let state = self.read_state()?;
         let account = state
             .accounts
             .iter()
@@ -326,7 +739,7 @@ impl AccountStore {
             ));
         }
 
-        let mut state = self.lock_state()?;
+        let mut state = self.write_state()?;
         let account = state
             .accounts
             .iter_mut()
@@ -337,10 +750,22 @@ impl AccountStore {
         Ok(())
     }
 
-    fn lock_state(&self) -> Result<MutexGuard<'_, AccountStoreState>> {
-        self.state
+    fn lock_output_cache(&self) -> Result<MutexGuard<'_, HashMap<String, ProbeSuccess>>> {
+        self.output_cache
             .lock()
-            .map_err(|_| BackendError::Store("account store mutex poisoned".to_string()))
+            .map_err(|_| BackendError::Store("account output cache mutex poisoned".to_string()))
+    }
+
+    fn read_state(&self) -> Result<RwLockReadGuard<'_, AccountStoreState>> {
+        self.state
+            .read()
+            .map_err(|_| BackendError::Store("account store lock poisoned".to_string()))
+    }
+
+    fn write_state(&self) -> Result<RwLockWriteGuard<'_, AccountStoreState>> {
+        self.state
+            .write()
+            .map_err(|_| BackendError::Store("account store lock poisoned".to_string()))
     }
 
     fn save_locked(&self, state: &AccountStoreState) -> Result<()> {
@@ -349,28 +774,268 @@ impl AccountStore {
             accounts: state.accounts.clone(),
         };
         let serialized = serde_json::to_string_pretty(&payload)?;
-        fs::write(&self.path, serialized)?;
-        Ok(())
+        write_file_atomic(&self.path, serialized.as_bytes())
+    }
+}
+
+/// Manual `sort_order` first (unset last), then most recently updated, then id.
+fn compare_account_order(a: &AccountRecord, b: &AccountRecord) -> std::cmp::Ordering {
+    let by_sort_order = match (a.sort_order, b.sort_order) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    };
+    by_sort_order
+        .then_with(|| b.updated_at.cmp(&a.updated_at))
+        .then_with(|| a.id.cmp(&b.id))
+}
+
+fn ordered_live_account_ids(state: &AccountStoreState) -> Vec<String> {
+    let mut accounts = state
+        .accounts
+        .iter()
+        .filter(|account| account.deleted_at.is_none())
+        .collect::<Vec<_>>();
+    accounts.sort_by(|a, b| compare_account_order(a, b));
+    accounts
+        .into_iter()
+        .map(|account| account.id.clone())
+        .collect()
+}
+
+fn assign_sort_orders(state: &mut AccountStoreState, order: &[String]) {
+    for account in state.accounts.iter_mut() {
+        if let Some(index) = order.iter().position(|id| *id == account.id) {
+            account.sort_order = Some((index as i64 + 1) * SORT_ORDER_STEP);
+        }
+    }
+}
+
+/// Validates `input` the way `create_account` does and builds the record
+/// under a fresh id.
+fn new_account_record(input: CreateAccountInput, now: &str) -> Result<AccountRecord> {
+    let provider_id = normalize_string(&input.provider_id)
+        .map(|value| value.to_ascii_lowercase())
+        .ok_or_else(|| BackendError::Validation("providerId is required".to_string()))?;
+    if !is_valid_provider_id(&provider_id) {
+        return Err(BackendError::Validation(
+            "providerId must match ^[a-z0-9][a-z0-9._-]{1,63}$".to_string(),
+        ));
+    }
+    let provider = find_provider_contract(&provider_id)
+        .ok_or_else(|| BackendError::Validation("providerId is not registered".to_string()))?;
+    // Aliases resolve to the canonical id, which is what gets stored.
+    let provider_id = provider.id.to_string();
+
+    let auth_strategy_id = match normalize_optional_string(input.auth_strategy_id) {
+        Some(strategy_id) => {
+            if !is_valid_strategy_id(&strategy_id) {
+                return Err(BackendError::Validation(
+                    "authStrategyId must match ^[a-zA-Z][a-zA-Z0-9._-]{1,63}$".to_string(),
+                ));
+            }
+            validate_auth_strategy_for_provider(provider, Some(&strategy_id))
+                .map_err(BackendError::Validation)?;
+            Some(strategy_id)
+        }
+        None => None,
+    };
+
+    let label = normalize_optional_string(input.label).unwrap_or_else(|| provider_id.clone());
+    let settings = input.settings.unwrap_or_else(|| serde_json::json!({}));
+    validate_provider_settings(provider, &settings).map_err(BackendError::Validation)?;
+
+    Ok(AccountRecord {
+        id: Uuid::new_v4().to_string(),
+        provider_id,
+        auth_strategy_id,
+        label,
+        settings,
+        credentials: None,
+        created_at: now.to_string(),
+        updated_at: now.to_string(),
+        last_fetch_at: None,
+        last_error: None,
+        last_error_at: None,
+        deleted_at: None,
+        disabled: None,
+        rate_limited_until: None,
+        credentials_expires_at: None,
+        tags: None,
+        notes: None,
+        sort_order: None,
+        icon_override: None,
+    })
+}
+
+/// Rebuilds an imported record through `new_account_record` and the tag,
+/// notes and icon validation, keeping only user-editable fields.
+fn imported_account_record(record: AccountRecord, now: &str) -> Result<AccountRecord> {
+    let mut account = new_account_record(
+        CreateAccountInput {
+            provider_id: record.provider_id,
+            auth_strategy_id: record.auth_strategy_id,
+            label: Some(record.label),
+            settings: Some(record.settings),
+        },
+        now,
+    )?;
+    account.tags = normalize_tags(record.tags.unwrap_or_default())?;
+    account.notes = match record.notes {
+        Some(notes) => normalize_notes(notes)?,
+        None => None,
+    };
+    account.icon_override = normalize_icon_override(record.icon_override)?;
+    account.disabled = record.disabled.filter(|disabled| *disabled);
+    account.sort_order = record.sort_order;
+    if !record.created_at.trim().is_empty() {
+        account.created_at = record.created_at;
+    }
+    Ok(account)
+}
+
+fn normalize_icon_override(icon: Option<String>) -> Result<Option<String>> {
+    let icon = normalize_optional_string(icon);
+    if icon
+        .as_deref()
+        .is_some_and(|icon| !is_valid_icon_override(icon))
+    {
+        return Err(BackendError::Validation(
+            "icon must be a path starting with / or a data:image/svg+xml URI".to_string(),
+        ));
+    }
+    Ok(icon)
+}
+
+/// Lowercases, validates and de-duplicates tags, keeping first-seen order. An
+/// empty list clears the tags.
+fn normalize_tags(tags: Vec<String>) -> Result<Option<Vec<String>>> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_ascii_lowercase();
+        if !is_valid_tag(&tag) {
+            return Err(BackendError::Validation(format!(
+                "tag '{tag}' must match ^[a-z0-9][a-z0-9-]{{0,31}}$"
+            )));
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    Ok((!normalized.is_empty()).then_some(normalized))
+}
+
+fn normalize_notes(notes: String) -> Result<Option<String>> {
+    let notes = normalize_string(&notes);
+    if notes
+        .as_ref()
+        .is_some_and(|notes| notes.chars().count() > MAX_NOTES_CHARS)
+    {
+        return Err(BackendError::Validation(format!(
+            "notes must be at most {MAX_NOTES_CHARS} characters"
+        )));
+    }
+    Ok(notes)
+}
+
+/// Rejects another live account for providers that cap how many they allow.
+fn ensure_account_capacity(state: &AccountStoreState, provider_id: &str) -> Result<()> {
+    let Some(runtime) = find_provider_runtime(provider_id) else {
+        return Ok(());
+    };
+    let existing = state
+        .accounts
+        .iter()
+        .filter(|account| account.provider_id == provider_id && account.deleted_at.is_none())
+        .count();
+    if !runtime.supports_multiple_accounts() && existing > 0 {
+        return Err(BackendError::Validation(
+            "This provider supports only one account".to_string(),
+        ));
     }
+    if let Some(max_accounts) = runtime.max_accounts() {
+        if existing >= max_accounts {
+            return Err(BackendError::Validation(format!(
+                "This provider supports at most {max_accounts} accounts"
+            )));
+        }
+    }
+    Ok(())
 }
 
+type AccountsMigration = fn(Vec<AccountRecord>) -> Vec<AccountRecord>;
+
+/// Ordered schema migrations keyed by the version they upgrade from. Only the
+/// steps below `STORE_SCHEMA_VERSION` run; bump the constant to activate the next one.
+const MIGRATIONS: [(u32, AccountsMigration); 1] = [(1, migrate_v1_to_v2)];
+
 fn parse_store_contents(contents: &str) -> Result<AccountStoreState> {
     let store_file = serde_json::from_str::<AccountStoreFile>(contents)?;
-    if store_file.schema_version != STORE_SCHEMA_VERSION {
+    let mut state = migrate(store_file.schema_version, store_file)?;
+    migrate_missing_created_at(&mut state.accounts);
+    Ok(state)
+}
+
+fn migrate(from: u32, file: AccountStoreFile) -> Result<AccountStoreState> {
+    if from == 0 || from > STORE_SCHEMA_VERSION {
         return Err(BackendError::Store(format!(
             "unsupported account store schema version: {}",
-            store_file.schema_version
+            from
         )));
     }
 
-    Ok(AccountStoreState {
-        accounts: store_file.accounts,
-    })
+    let mut accounts = file.accounts;
+    for version in from..STORE_SCHEMA_VERSION {
+        let (_, migration) = MIGRATIONS
+            .iter()
+            .find(|(source, _)| *source == version)
+            .ok_or_else(|| {
+                BackendError::Store(format!(
+                    "no account store migration from schema version {}",
+                    version
+                ))
+            })?;
+        log::info!(
+            "[accounts] migrating store schema v{} -> v{}",
+            version,
+            version + 1
+        );
+        accounts = migration(accounts);
+    }
+
+    Ok(AccountStoreState { accounts })
+}
+
+/// Flags credential blobs written before the metadata HMAC so that an empty
+/// `hmac` is only ever accepted for them.
+fn migrate_v1_to_v2(mut accounts: Vec<AccountRecord>) -> Vec<AccountRecord> {
+    for encrypted in accounts
+        .iter_mut()
+        .filter_map(|account| account.credentials.as_mut())
+    {
+        encrypted.legacy = encrypted.hmac.is_empty();
+    }
+    accounts
+}
+
+fn migrate_missing_created_at(accounts: &mut [AccountRecord]) {
+    for account in accounts.iter_mut() {
+        if account.created_at.trim().is_empty() {
+            log::debug!(
+                "[accounts] migrated empty created_at account={} updated_at={}",
+                account.id,
+                account.updated_at
+            );
+            account.created_at = account.updated_at.clone();
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::MockClock;
 
     fn make_temp_store_path() -> PathBuf {
         let dir =
@@ -389,12 +1054,12 @@ mod tests {
 
         let store = AccountStore::load_from_path(path.clone()).expect("store should load");
         let account = store
-            .create_account(CreateAccountInput {
-                provider_id: "codex".to_string(),
-                auth_strategy_id: Some("oauth".to_string()),
-                label: Some("Codex Personal".to_string()),
-                settings: Some(serde_json::json!({"region": "us"})),
-            })
+            .create_account(
+                CreateAccountInput::for_provider("codex")
+                    .with_auth_strategy("oauth")
+                    .with_label("Codex Personal")
+                    .with_settings(serde_json::json!({"region": "us"})),
+            )
             .expect("account should be created");
         assert_eq!(account.provider_id, "codex");
 
@@ -418,23 +1083,13 @@ mod tests {
 
         let store = AccountStore::load_from_path(path).expect("store should load");
         let account = store
-            .create_account(CreateAccountInput {
-                provider_id: "codex".to_string(),
-                auth_strategy_id: Some("oauth".to_string()),
-                label: None,
-                settings: None,
-            })
+            .create_account(CreateAccountInput::for_provider("codex").with_auth_strategy("oauth"))
             .expect("account should be created");
 
         let updated = store
             .update_account(
                 &account.id,
-                UpdateAccountInput {
-                    auth_strategy_id: Some("".to_string()),
-                    label: None,
-                    settings: None,
-                    clear_last_error: false,
-                },
+                UpdateAccountInput::default().with_auth_strategy(""),
             )
             .expect("account should be updated");
 
@@ -453,22 +1108,18 @@ mod tests {
 
         let store = AccountStore::load_from_path(path).expect("store should load");
         let account = store
-            .create_account(CreateAccountInput {
-                provider_id: "zai".to_string(),
-                auth_strategy_id: Some("apiKey".to_string()),
-                label: Some("Z.ai Work".to_string()),
-                settings: None,
-            })
+            .create_account(
+                CreateAccountInput::for_provider("zai")
+                    .with_auth_strategy("apiKey")
+                    .with_label("Z.ai Work"),
+            )
             .expect("account should be created");
 
         let result = store.update_account(
             &account.id,
-            UpdateAccountInput {
-                auth_strategy_id: Some("oauth".to_string()),
-                label: Some("Should Not Persist".to_string()),
-                settings: None,
-                clear_last_error: false,
-            },
+            UpdateAccountInput::default()
+                .with_auth_strategy("oauth")
+                .with_label("Should Not Persist"),
         );
 
         let err = result.expect_err("unsupported auth strategy should fail");
@@ -495,12 +1146,8 @@ mod tests {
             .to_path_buf();
 
         let store = AccountStore::load_from_path(path).expect("store should load");
-        let result = store.create_account(CreateAccountInput {
-            provider_id: "zai".to_string(),
-            auth_strategy_id: Some("oauth".to_string()),
-            label: None,
-            settings: None,
-        });
+        let result = store
+            .create_account(CreateAccountInput::for_provider("zai").with_auth_strategy("oauth"));
 
         let err = result.expect_err("unsupported auth strategy should fail");
         assert!(err
@@ -520,12 +1167,12 @@ mod tests {
 
         let store = AccountStore::load_from_path(path.clone()).expect("store should load");
         let account = store
-            .create_account(CreateAccountInput {
-                provider_id: "codex".to_string(),
-                auth_strategy_id: Some("oauth".to_string()),
-                label: Some("Codex Personal".to_string()),
-                settings: Some(serde_json::json!({})),
-            })
+            .create_account(
+                CreateAccountInput::for_provider("codex")
+                    .with_auth_strategy("oauth")
+                    .with_label("Codex Personal")
+                    .with_settings(serde_json::json!({})),
+            )
             .expect("account should be created");
 
         let encrypted = EncryptedCredentials {
@@ -533,6 +1180,8 @@ mod tests {
             key_version: 1,
             nonce: "nonce".to_string(),
             ciphertext: "ciphertext".to_string(),
+            hmac: String::new(),
+            legacy: false,
         };
 
         store
@@ -555,4 +1204,781 @@ mod tests {
 
         fs::remove_dir_all(parent).expect("temp dir should be removed");
     }
+
+    #[test]
+    fn parse_store_contents_backfills_empty_created_at() {
+        let contents = serde_json::json!({
+            "schemaVersion": STORE_SCHEMA_VERSION,
+            "accounts": [{
+                "id": "acc-1",
+                "providerId": "codex",
+                "label": "Codex",
+                "createdAt": "",
+                "updatedAt": "2025-01-01T00:00:00Z"
+            }]
+        })
+        .to_string();
+
+        let state = parse_store_contents(&contents).expect("store should parse");
+
+        assert_eq!(state.accounts[0].created_at, "2025-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn failed_save_keeps_prior_store_file() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let store = AccountStore::load_from_path(path.clone()).expect("store should load");
+        let account = store
+            .create_account(
+                CreateAccountInput::for_provider("codex")
+                    .with_auth_strategy("oauth")
+                    .with_label("Codex Personal")
+                    .with_settings(serde_json::json!({})),
+            )
+            .expect("account should be created");
+        let saved = fs::read_to_string(&path).expect("store file should be readable");
+
+        // A store aimed at a directory cannot persist; the real file is untouched.
+        let blocked_path = parent.join("blocked.json");
+        fs::create_dir(&blocked_path).expect("blocking dir should be created");
+        fs::write(blocked_path.join("keep"), "").expect("blocking file should be written");
+        let blocked = AccountStore {
+            path: blocked_path,
+            state: RwLock::new(AccountStoreState::default()),
+            output_cache: Mutex::new(HashMap::new()),
+            clock: SystemClock,
+        };
+        assert!(blocked
+            .create_account(CreateAccountInput::for_provider("codex").with_label("Lost"))
+            .is_err());
+
+        assert_eq!(
+            fs::read_to_string(&path).expect("store file should be readable"),
+            saved
+        );
+        let mut entries = fs::read_dir(&parent)
+            .expect("temp dir should be readable")
+            .map(|entry| entry.expect("entry should be readable").file_name())
+            .collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(entries, vec!["accounts.json", "blocked.json"]);
+
+        let reloaded = AccountStore::load_from_path(path).expect("store should reload");
+        assert!(reloaded
+            .get_account(&account.id)
+            .expect("get account should work")
+            .is_some());
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn export_then_import_strips_credentials_and_reassigns_ids() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let store = AccountStore::load_from_path(path).expect("store should load");
+        let account = store
+            .create_account(
+                CreateAccountInput::for_provider("codex")
+                    .with_auth_strategy("oauth")
+                    .with_label("Codex Personal")
+                    .with_settings(serde_json::json!({})),
+            )
+            .expect("account should be created");
+        store
+            .set_credentials_blob(
+                &account.id,
+                EncryptedCredentials {
+                    alg: "xchacha20poly1305".to_string(),
+                    key_version: 1,
+                    nonce: "nonce".to_string(),
+                    ciphertext: "ciphertext".to_string(),
+                    hmac: String::new(),
+                    legacy: false,
+                },
+            )
+            .expect("credentials should be set");
+
+        let exported = store.export_accounts().expect("export should work");
+        assert!(!exported.contains("ciphertext"));
+
+        assert!(store
+            .import_accounts(&exported, false)
+            .expect("import should work")
+            .is_empty());
+        assert_eq!(store.list_accounts().expect("list should work").len(), 1);
+
+        let imported = store
+            .import_accounts(&exported, true)
+            .expect("import should work");
+        assert_eq!(imported.len(), 1);
+        assert_ne!(imported[0].id, account.id);
+        assert!(imported[0].credentials.is_none());
+        assert_eq!(store.list_accounts().expect("list should work").len(), 1);
+
+        // The replaced account stays in the trash with its credentials.
+        let deleted = store
+            .list_deleted_accounts()
+            .expect("deleted accounts should list");
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].id, account.id);
+        assert!(store
+            .has_credentials_blob(&account.id)
+            .expect("credential lookup should work"));
+
+        let invalid = exported.replace(
+            "\"label\": \"Codex Personal\"",
+            "\"label\": \"Other\", \"tags\": [\"Not A Tag!\"]",
+        );
+        assert!(matches!(
+            store.import_accounts(&invalid, false),
+            Err(BackendError::Validation(_))
+        ));
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn concurrent_reads_do_not_block_each_other() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let store = AccountStore::load_from_path(path).expect("store should load");
+        let account = store
+            .create_account(
+                CreateAccountInput::for_provider("codex")
+                    .with_auth_strategy("oauth")
+                    .with_label("Codex Personal")
+                    .with_settings(serde_json::json!({})),
+            )
+            .expect("account should be created");
+
+        let held = store.read_state().expect("first read should succeed");
+        let listed = store.list_accounts().expect("second read should not block");
+        let fetched = store
+            .get_account(&account.id)
+            .expect("third read should not block");
+        drop(held);
+
+        assert_eq!(listed.len(), 1);
+        assert!(fetched.is_some());
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn parse_store_contents_rejects_unknown_schema_version() {
+        let contents = serde_json::json!({
+            "schemaVersion": 0,
+            "accounts": []
+        })
+        .to_string();
+
+        let error = parse_store_contents(&contents).expect_err("v0 store should fail");
+        assert!(error
+            .to_string()
+            .contains("unsupported account store schema version: 0"));
+    }
+
+    #[test]
+    fn parse_store_contents_ignores_unknown_keys() {
+        let contents = serde_json::json!({
+            "schemaVersion": 1,
+            "futureField": true,
+            "accounts": [{
+                "id": "acc-1",
+                "providerId": "codex",
+                "label": "Codex",
+                "createdAt": "2025-01-01T00:00:00Z",
+                "updatedAt": "2025-01-01T00:00:00Z",
+                "futureAccountField": "value"
+            }]
+        })
+        .to_string();
+
+        let state = parse_store_contents(&contents).expect("v1 store should parse");
+        assert_eq!(state.accounts.len(), 1);
+    }
+
+    #[test]
+    fn v1_migration_flags_only_blobs_without_hmac() {
+        let blob = |hmac: &str| {
+            serde_json::json!({
+                "alg": "xchacha20poly1305",
+                "keyVersion": 1,
+                "nonce": "nonce",
+                "ciphertext": "ciphertext",
+                "hmac": hmac
+            })
+        };
+        let account = |id: &str, credentials: serde_json::Value| {
+            serde_json::json!({
+                "id": id,
+                "providerId": "codex",
+                "label": id,
+                "credentials": credentials,
+                "createdAt": "2025-01-01T00:00:00Z",
+                "updatedAt": "2025-01-01T00:00:00Z"
+            })
+        };
+        let contents = |schema_version: u32| {
+            serde_json::json!({
+                "schemaVersion": schema_version,
+                "accounts": [account("old", blob("")), account("new", blob("mac"))]
+            })
+            .to_string()
+        };
+
+        let state = parse_store_contents(&contents(1)).expect("v1 store should parse");
+        let legacy = |state: &AccountStoreState, id: &str| {
+            state
+                .accounts
+                .iter()
+                .find(|account| account.id == id)
+                .and_then(|account| account.credentials.as_ref())
+                .expect("credentials should be present")
+                .legacy
+        };
+        assert!(legacy(&state, "old"));
+        assert!(!legacy(&state, "new"));
+
+        let state = parse_store_contents(&contents(2)).expect("v2 store should parse");
+        assert!(!legacy(&state, "old"));
+    }
+
+    #[test]
+    fn find_and_count_accounts_by_provider() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let store = AccountStore::load_from_path(path).expect("store should load");
+        let ids =
+            [("codex", "zeta"), ("zai", "Work"), ("codex", "Alpha")].map(|(provider_id, label)| {
+                store
+                    .create_account(CreateAccountInput::for_provider(provider_id).with_label(label))
+                    .expect("account should be created")
+                    .id
+            });
+        store
+            .reorder_accounts(&[ids[0].clone(), ids[2].clone(), ids[1].clone()])
+            .expect("reorder should work");
+
+        let codex = store
+            .find_accounts_by_provider("codex")
+            .expect("find should work");
+        let labels = codex
+            .iter()
+            .map(|account| account.label.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(labels, vec!["zeta", "Alpha"]);
+        assert_eq!(
+            store
+                .count_accounts_by_provider("zai")
+                .expect("count should work"),
+            1
+        );
+        assert_eq!(
+            store
+                .count_accounts_by_provider("claude")
+                .expect("count should work"),
+            0
+        );
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn list_accounts_reports_credential_status() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let store = AccountStore::load_from_path(path).expect("store should load");
+        let create = |label: &str| {
+            store
+                .create_account(
+                    CreateAccountInput::for_provider("codex")
+                        .with_auth_strategy("oauth")
+                        .with_label(label)
+                        .with_settings(serde_json::json!({})),
+                )
+                .expect("account should be created")
+        };
+        let with_credentials = create("Codex Work");
+        let without_credentials = create("Codex Personal");
+        store
+            .set_credentials_blob(
+                &with_credentials.id,
+                EncryptedCredentials {
+                    alg: "xchacha20poly1305".to_string(),
+                    key_version: 1,
+                    nonce: "nonce".to_string(),
+                    ciphertext: "ciphertext".to_string(),
+                    hmac: String::new(),
+                    legacy: false,
+                },
+            )
+            .expect("credentials should be set");
+
+        let statuses = store
+            .list_accounts_with_credential_status()
+            .expect("list should work")
+            .into_iter()
+            .map(|status| (status.account.id, status.has_credentials))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses.get(&with_credentials.id), Some(&true));
+        assert_eq!(statuses.get(&without_credentials.id), Some(&false));
+
+        let single = store
+            .get_account_with_credential_status(&without_credentials.id)
+            .expect("get should work")
+            .expect("account should exist");
+        assert!(!single.has_credentials);
+        assert!(store
+            .get_account_with_credential_status("missing")
+            .expect("get should work")
+            .is_none());
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn deleted_account_rejects_mutations() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let store = AccountStore::load_from_path(path).expect("store should load");
+        let account = store
+            .create_account(CreateAccountInput::for_provider("codex").with_label("Codex Personal"))
+            .expect("account should be created");
+        let deleted = store
+            .delete_account(&account.id)
+            .expect("delete should work")
+            .expect("account should be soft-deleted");
+
+        let id = account.id.as_str();
+        let results = [
+            store
+                .update_account(id, UpdateAccountInput::default().with_disabled(true))
+                .map(|_| ()),
+            store.record_probe_success(id),
+            store.record_probe_error(id, "boom"),
+            store.set_rate_limited_until(id, Some("2030-01-01T00:00:00Z".to_string())),
+            store.set_icon_override(id, None),
+            store.set_credentials_expires_at(id, None),
+            store.set_credentials_blob(
+                id,
+                EncryptedCredentials {
+                    alg: "xchacha20poly1305".to_string(),
+                    key_version: 1,
+                    nonce: "nonce".to_string(),
+                    ciphertext: "ciphertext".to_string(),
+                    hmac: String::new(),
+                    legacy: false,
+                },
+            ),
+        ];
+        for result in results {
+            assert!(matches!(result, Err(BackendError::AccountNotFound)));
+        }
+
+        let unchanged = store
+            .list_deleted_accounts()
+            .expect("list deleted should work")
+            .pop()
+            .expect("deleted account should remain");
+        assert_eq!(unchanged.updated_at, deleted.updated_at);
+        assert!(unchanged.disabled.is_none());
+        assert!(unchanged.last_error.is_none());
+        assert!(unchanged.credentials.is_none());
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn deleted_account_is_hidden_until_restored() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let store = AccountStore::load_from_path(path).expect("store should load");
+        let account = store
+            .create_account(
+                CreateAccountInput::for_provider("codex")
+                    .with_auth_strategy("oauth")
+                    .with_label("Codex Personal")
+                    .with_settings(serde_json::json!({})),
+            )
+            .expect("account should be created");
+
+        store
+            .delete_account(&account.id)
+            .expect("delete should work")
+            .expect("account should be soft-deleted");
+        assert!(store.list_accounts().expect("list should work").is_empty());
+        assert!(store
+            .get_account(&account.id)
+            .expect("get should work")
+            .is_none());
+        assert_eq!(
+            store
+                .list_deleted_accounts()
+                .expect("list deleted should work")
+                .len(),
+            1
+        );
+
+        let restored = store
+            .restore_account(&account.id)
+            .expect("restore should work");
+        assert!(restored.deleted_at.is_none());
+        assert_eq!(store.list_accounts().expect("list should work").len(), 1);
+
+        store
+            .delete_account(&account.id)
+            .expect("delete should work");
+        let purged = store
+            .purge_accounts_older_than(0)
+            .expect("purge should work");
+        assert_eq!(purged.len(), 1);
+        assert!(store
+            .list_deleted_accounts()
+            .expect("list deleted should work")
+            .is_empty());
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn manual_sort_order_wins_over_update_time() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let clock = MockClock::new(0);
+        let store = AccountStore::load_with_clock(path, clock.clone()).expect("store should load");
+        let ids = ["a", "b", "c", "d"]
+            .into_iter()
+            .map(|label| {
+                clock.advance_ms(1000);
+                store
+                    .create_account(
+                        CreateAccountInput::for_provider("codex")
+                            .with_auth_strategy("oauth")
+                            .with_label(label),
+                    )
+                    .expect("account should be created")
+                    .id
+            })
+            .collect::<Vec<_>>();
+        let labels = |store: &AccountStore<MockClock>| {
+            store
+                .list_accounts()
+                .expect("list should work")
+                .into_iter()
+                .map(|account| account.label)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(&store), vec!["d", "c", "b", "a"]);
+
+        store
+            .reorder_accounts(&[ids[2].clone(), ids[0].clone()])
+            .expect("reorder should work");
+        assert_eq!(labels(&store), vec!["c", "a", "d", "b"]);
+
+        store
+            .move_account_before(&ids[3], &ids[0])
+            .expect("move should work");
+        assert_eq!(labels(&store), vec!["c", "d", "a", "b"]);
+        let moved = store
+            .get_account(&ids[3])
+            .expect("get should work")
+            .expect("account should exist");
+        assert_eq!(moved.sort_order, Some(150));
+
+        store
+            .move_account_before(&ids[1], &ids[2])
+            .expect("move to front should work");
+        assert_eq!(labels(&store), vec!["b", "c", "d", "a"]);
+
+        assert!(matches!(
+            store.reorder_accounts(&[ids[0].clone(), ids[0].clone()]),
+            Err(BackendError::Validation(_))
+        ));
+        assert!(matches!(
+            store.move_account_before("missing", &ids[0]),
+            Err(BackendError::AccountNotFound)
+        ));
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn icon_override_accepts_paths_and_svg_data_uris_only() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let store = AccountStore::load_from_path(path).expect("store should load");
+        let account = store
+            .create_account(CreateAccountInput::for_provider("codex").with_auth_strategy("oauth"))
+            .expect("account should be created");
+
+        for icon in [
+            "/icons/work.svg",
+            "data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=",
+            "data:image/svg+xml,%3Csvg%3E%3C/svg%3E",
+        ] {
+            store
+                .set_icon_override(&account.id, Some(icon.to_string()))
+                .expect("icon should be accepted");
+        }
+        for icon in [
+            "data:image/png;base64,AAAA",
+            "data:image/svg+xmlx,<svg/>",
+            "data:text/html,<svg/>",
+            "https://example.com/icon.svg",
+            "//example.com/icon.svg",
+        ] {
+            assert!(matches!(
+                store.set_icon_override(&account.id, Some(icon.to_string())),
+                Err(BackendError::Validation(_))
+            ));
+        }
+
+        store
+            .set_icon_override(&account.id, None)
+            .expect("icon should be cleared");
+        let account = store
+            .get_account(&account.id)
+            .expect("get should work")
+            .expect("account should exist");
+        assert_eq!(account.icon_override, None);
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn tags_are_normalized_and_filterable() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let store = AccountStore::load_from_path(path).expect("store should load");
+        let account = store
+            .create_account(CreateAccountInput::for_provider("codex").with_auth_strategy("oauth"))
+            .expect("account should be created");
+        let update = |tags: Option<Vec<&str>>, notes: Option<String>| UpdateAccountInput {
+            tags: tags.map(|tags| tags.into_iter().map(str::to_string).collect()),
+            notes,
+            ..UpdateAccountInput::default()
+        };
+
+        let updated = store
+            .update_account(
+                &account.id,
+                update(Some(vec![" Work ", "team-a", "work"]), None),
+            )
+            .expect("tags should be stored");
+        assert_eq!(
+            updated.tags,
+            Some(vec!["work".to_string(), "team-a".to_string()])
+        );
+        assert_eq!(
+            store
+                .filter_accounts_by_tag("WORK")
+                .expect("filter should work")
+                .len(),
+            1
+        );
+        assert!(store
+            .filter_accounts_by_tag("personal")
+            .expect("filter should work")
+            .is_empty());
+
+        assert!(matches!(
+            store.update_account(&account.id, update(Some(vec!["-bad"]), None)),
+            Err(BackendError::Validation(_))
+        ));
+        assert!(matches!(
+            store.update_account(&account.id, update(None, Some("x".repeat(1001)))),
+            Err(BackendError::Validation(_))
+        ));
+
+        let cleared = store
+            .update_account(&account.id, update(Some(vec![]), Some("  ".to_string())))
+            .expect("empty values should clear");
+        assert_eq!(cleared.tags, None);
+        assert_eq!(cleared.notes, None);
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn single_account_provider_rejects_second_account() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let store = AccountStore::load_from_path(path).expect("store should load");
+        let input = || CreateAccountInput::for_provider("opencode").with_auth_strategy("cookie");
+        let first = store
+            .create_account(input())
+            .expect("first account should be created");
+
+        match store.create_account(input()) {
+            Err(BackendError::Validation(message)) => {
+                assert_eq!(message, "This provider supports only one account")
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert_eq!(
+            store
+                .count_accounts_by_provider("opencode")
+                .expect("count should work"),
+            1
+        );
+
+        store.delete_account(&first.id).expect("delete should work");
+        let second = store
+            .create_account(input())
+            .expect("account should be created after the first is deleted");
+        assert!(matches!(
+            store.restore_account(&first.id),
+            Err(BackendError::Validation(_))
+        ));
+
+        let second_label = store
+            .export_accounts()
+            .expect("export should work")
+            .replace(&second.id, "acc-2")
+            .replace("\"label\": \"opencode\"", "\"label\": \"Second\"");
+        assert!(matches!(
+            store.import_accounts(&second_label, false),
+            Err(BackendError::Validation(_))
+        ));
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn purge_uses_store_clock_for_deletion_age() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let clock =
+            MockClock::new(rfc3339_to_unix_ms("2026-01-01T00:00:00Z").expect("valid timestamp"));
+        let store = AccountStore::load_with_clock(path, clock.clone()).expect("store should load");
+        let account = store
+            .create_account(CreateAccountInput::for_provider("codex").with_auth_strategy("oauth"))
+            .expect("account should be created");
+        assert_eq!(account.created_at, "2026-01-01T00:00:00Z");
+
+        store
+            .delete_account(&account.id)
+            .expect("delete should work");
+        clock.advance_ms(12 * 60 * 60 * 1000);
+        assert!(store
+            .purge_accounts_older_than(1)
+            .expect("purge should work")
+            .is_empty());
+
+        clock.advance_ms(12 * 60 * 60 * 1000);
+        assert_eq!(
+            store
+                .purge_accounts_older_than(1)
+                .expect("purge should work")
+                .len(),
+            1
+        );
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn probe_errors_record_when_they_occurred() {
+        let path = make_temp_store_path();
+        let parent = path
+            .parent()
+            .expect("temp store path should have a parent")
+            .to_path_buf();
+
+        let clock =
+            MockClock::new(rfc3339_to_unix_ms("2026-01-01T00:00:00Z").expect("valid timestamp"));
+        let store = AccountStore::load_with_clock(path, clock.clone()).expect("store should load");
+        let account = store
+            .create_account(CreateAccountInput::for_provider("codex").with_auth_strategy("oauth"))
+            .expect("account should be created");
+        assert_eq!(
+            store
+                .get_account_last_error(&account.id)
+                .expect("lookup should work"),
+            None
+        );
+
+        clock.advance_ms(60 * 1000);
+        store
+            .record_probe_error(&account.id, "HTTP 500")
+            .expect("error should be recorded");
+        assert_eq!(
+            store
+                .get_account_last_error(&account.id)
+                .expect("lookup should work"),
+            Some(AccountErrorInfo {
+                message: "HTTP 500".to_string(),
+                occurred_at: "2026-01-01T00:01:00Z".to_string(),
+            })
+        );
+
+        store
+            .record_probe_success(&account.id)
+            .expect("success should be recorded");
+        let account = store
+            .get_account(&account.id)
+            .expect("lookup should work")
+            .expect("account should exist");
+        assert_eq!(account.last_error_at, None);
+        assert_eq!(
+            store
+                .get_account_last_error(&account.id)
+                .expect("lookup should work"),
+            None
+        );
+
+        fs::remove_dir_all(parent).expect("temp dir should be removed");
+    }
 }
@@ -1,21 +1,49 @@
 use std::collections::HashMap;
+use std::fmt;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
+use hmac::{Hmac, Mac};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::Serialize;
+use sha2::Sha256;
+use tauri::{AppHandle, Listener, Manager};
+use tauri_plugin_deep_link::DeepLinkExt;
 use tiny_http::{Header, ListenAddr, Response, Server};
 use tokio::sync::oneshot;
+use tokio_util::sync::CancellationToken;
 use url::Url;
+use uuid::Uuid;
 
 use crate::error::{BackendError, Result};
+use crate::utils::{elapsed_ms_since, now_instant, ClockProvider, SystemClock};
 
 const CALLBACK_TIMEOUT_SECS: u64 = 180;
+const STALE_FLOW_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const DEEP_LINK_SCHEME: &str = "openburn";
+pub const DEEP_LINK_REDIRECT_URI: &str = "openburn://auth/callback";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How the authorization server hands the browser back to the app.
+#[derive(Debug, Clone, Copy)]
+pub enum CallbackMode {
+    LocalServer {
+        port: Option<u16>,
+    },
+    /// `openburn://auth/callback`; no provider registers it as a redirect yet.
+    #[allow(dead_code)]
+    DeepLink,
+}
 
 #[derive(Debug)]
 pub struct OAuthCallback {
     pub code: String,
     pub state: String,
+    pub correlation_id: String,
 }
 
 #[derive(Debug)]
@@ -26,6 +54,8 @@ pub struct PendingOAuth {
     pub device_code: Option<String>,
     pub device_interval: Option<u64>,
     pub device_expires_at: Option<i64>,
+    /// Set from the state's clock by `AuthState::insert`.
+    pub created_at_ms: i64,
     pub cancel_flag: Arc<AtomicBool>,
     receiver: Mutex<Option<oneshot::Receiver<Result<OAuthCallback>>>>,
 }
@@ -45,6 +75,7 @@ impl PendingOAuth {
             device_code: None,
             device_interval: None,
             device_expires_at: None,
+            created_at_ms: 0,
             cancel_flag,
             receiver: Mutex::new(Some(receiver)),
         }
@@ -63,6 +94,7 @@ impl PendingOAuth {
             device_code: Some(device_code),
             device_interval: Some(device_interval),
             device_expires_at: Some(device_expires_at),
+            created_at_ms: 0,
             cancel_flag: Arc::new(AtomicBool::new(false)),
             receiver: Mutex::new(None),
         }
@@ -74,17 +106,74 @@ impl PendingOAuth {
     }
 }
 
-#[derive(Debug, Default)]
-pub struct AuthState {
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OAuthFlowSummary {
+    pub request_id: String,
+    pub account_id: String,
+    pub created_at_ms: i64,
+}
+
+pub struct AuthState<C: ClockProvider = SystemClock> {
     flows: Mutex<HashMap<String, Arc<PendingOAuth>>>,
+    // Signs OAuth `state` values; regenerated every launch.
+    hmac_key: [u8; 32],
+    clock: C,
+}
+
+impl Default for AuthState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: ClockProvider> fmt::Debug for AuthState<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthState")
+            .field("flows", &self.flows)
+            .finish_non_exhaustive()
+    }
 }
 
 impl AuthState {
     pub fn new() -> Self {
-        Self::default()
+        Self::with_clock(SystemClock)
+    }
+}
+
+impl<C: ClockProvider> AuthState<C> {
+    pub fn with_clock(clock: C) -> Self {
+        let mut hmac_key = [0u8; 32];
+        OsRng.fill_bytes(&mut hmac_key);
+        Self {
+            flows: Mutex::new(HashMap::new()),
+            hmac_key,
+            clock,
+        }
+    }
+
+    pub fn now_unix_ms(&self) -> i64 {
+        self.clock.now_unix_ms()
+    }
+
+    /// A fresh `{uuid}.{hmac_hex}` state value for an authorization request.
+    pub fn sign_state(&self) -> String {
+        sign_state_with(&self.hmac_key, &Uuid::new_v4().to_string())
     }
 
-    pub fn insert(&self, request_id: String, flow: PendingOAuth) {
+    pub fn verify_state(&self, state: &str) -> bool {
+        verify_state_with(&self.hmac_key, state)
+    }
+
+    /// Owned verifier for the callback listener thread, which outlives any
+    /// borrow of the managed state.
+    pub fn state_verifier(&self) -> impl Fn(&str) -> bool + Send + Sync + 'static {
+        let key = self.hmac_key;
+        move |state| verify_state_with(&key, state)
+    }
+
+    pub fn insert(&self, request_id: String, mut flow: PendingOAuth) {
+        flow.created_at_ms = self.clock.now_unix_ms();
         let mut flows = self.flows.lock().expect("auth state mutex poisoned");
         flows.insert(request_id, Arc::new(flow));
     }
@@ -99,6 +188,16 @@ impl AuthState {
         flows.remove(request_id)
     }
 
+    /// Request ids of the flows currently pending for `account_id`.
+    pub fn request_ids_for_account(&self, account_id: &str) -> Vec<String> {
+        let flows = self.flows.lock().expect("auth state mutex poisoned");
+        flows
+            .iter()
+            .filter(|(_, flow)| flow.account_id == account_id)
+            .map(|(request_id, _)| request_id.clone())
+            .collect()
+    }
+
     pub fn cancel(&self, request_id: &str) -> bool {
         let mut flows = self.flows.lock().expect("auth state mutex poisoned");
         if let Some(flow) = flows.remove(request_id) {
@@ -108,12 +207,256 @@ impl AuthState {
             false
         }
     }
+
+    /// Cancels and removes every pending flow, returning the removed flows.
+    pub fn cancel_all(&self) -> Vec<Arc<PendingOAuth>> {
+        let mut flows = self.flows.lock().expect("auth state mutex poisoned");
+        flows
+            .drain()
+            .map(|(_, flow)| {
+                flow.cancel_flag.store(true, Ordering::SeqCst);
+                flow
+            })
+            .collect()
+    }
+
+    /// Pending flows, oldest first.
+    pub fn summaries(&self) -> Vec<OAuthFlowSummary> {
+        let flows = self.flows.lock().expect("auth state mutex poisoned");
+        let mut summaries: Vec<OAuthFlowSummary> = flows
+            .iter()
+            .map(|(request_id, flow)| OAuthFlowSummary {
+                request_id: request_id.clone(),
+                account_id: flow.account_id.clone(),
+                created_at_ms: flow.created_at_ms,
+            })
+            .collect();
+        summaries.sort_by(|a, b| {
+            a.created_at_ms
+                .cmp(&b.created_at_ms)
+                .then_with(|| a.request_id.cmp(&b.request_id))
+        });
+        summaries
+    }
+
+    pub fn len(&self) -> usize {
+        self.flows.lock().expect("auth state mutex poisoned").len()
+    }
+
+    /// Cancels and removes flows whose device code expired or that were
+    /// started more than `max_age_ms` ago. Returns the evicted request ids.
+    pub fn evict_stale(&self, max_age_ms: i64) -> Vec<String> {
+        let now_ms = self.clock.now_unix_ms();
+        let mut flows = self.flows.lock().expect("auth state mutex poisoned");
+        let stale: Vec<String> = flows
+            .iter()
+            .filter(|(_, flow)| {
+                flow.device_expires_at
+                    .is_some_and(|expires_at| expires_at <= now_ms)
+                    || now_ms.saturating_sub(flow.created_at_ms) >= max_age_ms
+            })
+            .map(|(request_id, _)| request_id.clone())
+            .collect();
+        for request_id in &stale {
+            if let Some(flow) = flows.remove(request_id) {
+                flow.cancel_flag.store(true, Ordering::SeqCst);
+            }
+        }
+        stale
+    }
+}
+
+fn state_mac(key: &[u8; 32], nonce: &str) -> HmacSha256 {
+    let mut mac = <HmacSha256 as Mac>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(nonce.as_bytes());
+    mac
+}
+
+fn sign_state_with(key: &[u8; 32], nonce: &str) -> String {
+    let signature = state_mac(key, nonce).finalize().into_bytes();
+    let hex: String = signature.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("{nonce}.{hex}")
+}
+
+fn verify_state_with(key: &[u8; 32], state: &str) -> bool {
+    let Some((nonce, hex)) = state.split_once('.') else {
+        return false;
+    };
+    if Uuid::parse_str(nonce).is_err() || hex.len() % 2 != 0 {
+        return false;
+    }
+    let signature: Option<Vec<u8>> = (0..hex.len())
+        .step_by(2)
+        .map(|index| {
+            hex.get(index..index + 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+        })
+        .collect();
+    match signature {
+        Some(signature) => state_mac(key, nonce).verify_slice(&signature).is_ok(),
+        None => false,
+    }
+}
+
+/// Evicts abandoned OAuth flows every `STALE_FLOW_CHECK_INTERVAL` so flows
+/// that were never finished or cancelled do not pile up.
+pub fn spawn_stale_flow_cleanup(app: AppHandle, max_age_ms: i64, cancel: CancellationToken) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(STALE_FLOW_CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = interval.tick() => {
+                    let Some(auth_state) = app.try_state::<AuthState>() else {
+                        continue;
+                    };
+                    let evicted = auth_state.evict_stale(max_age_ms);
+                    if !evicted.is_empty() {
+                        log::info!(
+                            "[oauth] evicted stale flows count={} remaining={}",
+                            evicted.len(),
+                            auth_state.len()
+                        );
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Reads `code` and `state` from a callback URL and checks the state.
+fn callback_from_url(
+    url: &Url,
+    expected_state: &str,
+    verify_state: &impl Fn(&str) -> bool,
+    correlation_id: String,
+) -> Result<OAuthCallback> {
+    let mut code: Option<String> = None;
+    let mut state: Option<String> = None;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "code" => code = Some(value.to_string()),
+            "state" => state = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    let code =
+        code.ok_or_else(|| BackendError::Provider("OAuth callback missing code".to_string()))?;
+    let state = state.unwrap_or_default();
+    if !verify_state(&state) {
+        return Err(BackendError::Provider(
+            "OAuth state signature mismatch".to_string(),
+        ));
+    }
+    if state != expected_state {
+        return Err(BackendError::Provider(
+            "OAuth callback state mismatch".to_string(),
+        ));
+    }
+    Ok(OAuthCallback {
+        code,
+        state,
+        correlation_id,
+    })
+}
+
+fn is_deep_link_callback(url: &Url) -> bool {
+    url.scheme() == DEEP_LINK_SCHEME && url.host_str() == Some("auth") && url.path() == "/callback"
+}
+
+/// Picks the `openburn://auth/callback` link out of an opened-URL event and
+/// checks it exactly like a localhost callback. `None` when no link matches.
+fn deep_link_callback(
+    urls: Vec<Url>,
+    expected_state: &str,
+    verify_state: &impl Fn(&str) -> bool,
+    correlation_id: String,
+) -> Option<Result<OAuthCallback>> {
+    let url = urls.into_iter().find(is_deep_link_callback)?;
+    Some(callback_from_url(
+        &url,
+        expected_state,
+        verify_state,
+        correlation_id,
+    ))
+}
+
+/// Waits for a single `openburn://auth/callback` deep link. The listener is
+/// removed once a callback arrives, the flow is cancelled, or it times out.
+pub fn listen_for_deep_link_callback(
+    app: &AppHandle,
+    expected_state: String,
+    cancel_flag: Arc<AtomicBool>,
+    verify_state: impl Fn(&str) -> bool + Send + Sync + 'static,
+) -> oneshot::Receiver<Result<OAuthCallback>> {
+    let (sender, receiver) = oneshot::channel();
+    let sender = Arc::new(Mutex::new(Some(sender)));
+
+    let handler_sender = sender.clone();
+    let handler_cancel_flag = cancel_flag.clone();
+    let event_id = app.deep_link().on_open_url(move |event| {
+        if handler_cancel_flag.load(Ordering::SeqCst) {
+            return;
+        }
+        let correlation_id = Uuid::new_v4().to_string();
+        let Some(result) = deep_link_callback(
+            event.urls(),
+            &expected_state,
+            &verify_state,
+            correlation_id.clone(),
+        ) else {
+            return;
+        };
+        log::info!(
+            "[oauth] deep link callback received correlation={} ok={}",
+            correlation_id,
+            result.is_ok()
+        );
+        let sender = handler_sender
+            .lock()
+            .expect("deep link sender mutex poisoned")
+            .take();
+        if let Some(sender) = sender {
+            let _ = sender.send(result);
+        }
+    });
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let started_at = now_instant();
+        loop {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            let mut sender = sender.lock().expect("deep link sender mutex poisoned");
+            let Some(pending) = sender.as_ref() else {
+                break;
+            };
+            if pending.is_closed() || cancel_flag.load(Ordering::SeqCst) {
+                if let Some(sender) = sender.take() {
+                    let _ = sender.send(Err(BackendError::Provider("OAuth cancelled".to_string())));
+                }
+                break;
+            }
+            if elapsed_ms_since(started_at) >= CALLBACK_TIMEOUT_SECS * 1000 {
+                if let Some(sender) = sender.take() {
+                    let _ = sender.send(Err(BackendError::Provider(
+                        "OAuth callback timed out".to_string(),
+                    )));
+                }
+                break;
+            }
+        }
+        app.unlisten(event_id);
+    });
+
+    receiver
 }
 
 pub fn start_local_callback_listener_with_options(
     expected_state: String,
     callback_path: &str,
     port: Option<u16>,
+    verify_state: impl Fn(&str) -> bool + Send + 'static,
 ) -> Result<(
     u16,
     oneshot::Receiver<Result<OAuthCallback>>,
@@ -148,7 +491,7 @@ pub fn start_local_callback_listener_with_options(
     thread::spawn(move || {
         let header =
             Header::from_bytes(&b"Content-Type"[..], &b"text/html; charset=utf-8"[..]).unwrap();
-        let started_at = Instant::now();
+        let started_at = now_instant();
         let poll_interval = Duration::from_millis(200);
 
         loop {
@@ -157,7 +500,7 @@ pub fn start_local_callback_listener_with_options(
                 return;
             }
 
-            if started_at.elapsed() >= Duration::from_secs(CALLBACK_TIMEOUT_SECS) {
+            if elapsed_ms_since(started_at) >= CALLBACK_TIMEOUT_SECS * 1000 {
                 let _ = sender.send(Err(BackendError::Provider(
                     "OAuth callback timed out".to_string(),
                 )));
@@ -175,6 +518,7 @@ pub fn start_local_callback_listener_with_options(
                 }
             };
 
+            let correlation_id = Uuid::new_v4().to_string();
             let url = format!("http://localhost{}", request.url());
             let parsed = Url::parse(&url).map_err(|err| {
                 BackendError::Provider(format!("OAuth callback URL invalid: {err}"))
@@ -187,36 +531,12 @@ pub fn start_local_callback_listener_with_options(
                             "OAuth callback path mismatch".to_string(),
                         ))
                     } else {
-                        let mut code: Option<String> = None;
-                        let mut state: Option<String> = None;
-                        for (key, value) in parsed.query_pairs() {
-                            match key.as_ref() {
-                                "code" => code = Some(value.to_string()),
-                                "state" => state = Some(value.to_string()),
-                                _ => {}
-                            }
-                        }
-
-                        let code = match code {
-                            Some(code) => Ok(code),
-                            None => Err(BackendError::Provider(
-                                "OAuth callback missing code".to_string(),
-                            )),
-                        };
-
-                        match code {
-                            Ok(code) => {
-                                let state = state.unwrap_or_default();
-                                if !state.is_empty() && state != expected_state {
-                                    Err(BackendError::Provider(
-                                        "OAuth callback state mismatch".to_string(),
-                                    ))
-                                } else {
-                                    Ok(OAuthCallback { code, state })
-                                }
-                            }
-                            Err(err) => Err(err),
-                        }
+                        callback_from_url(
+                            &parsed,
+                            &expected_state,
+                            &verify_state,
+                            correlation_id.clone(),
+                        )
                     }
                 }
                 Err(err) => Err(err),
@@ -235,6 +555,11 @@ pub fn start_local_callback_listener_with_options(
             }
             .with_header(header);
 
+            log::info!(
+                "[oauth] callback received correlation={} ok={}",
+                correlation_id,
+                result.is_ok()
+            );
             let _ = request.respond(response);
             let _ = sender.send(result);
             return;
@@ -243,3 +568,127 @@ pub fn start_local_callback_listener_with_options(
 
     Ok((port, receiver, cancel_flag))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::MockClock;
+
+    #[test]
+    fn deep_link_callback_is_parsed_and_verified() {
+        let state = AuthState::new();
+        let signed = state.sign_state();
+        let url = Url::parse(&format!("{DEEP_LINK_REDIRECT_URI}?code=abc&state={signed}"))
+            .expect("deep link should parse");
+        assert!(is_deep_link_callback(&url));
+
+        let callback = callback_from_url(&url, &signed, &state.state_verifier(), "c".to_string())
+            .expect("callback should verify");
+        assert_eq!(callback.code, "abc");
+
+        let other = Url::parse("openburn://settings/callback?code=abc").expect("url should parse");
+        assert!(!is_deep_link_callback(&other));
+        let forged =
+            Url::parse("openburn://auth/callback?code=abc&state=forged").expect("url should parse");
+        assert!(
+            callback_from_url(&forged, &signed, &state.state_verifier(), "c".to_string()).is_err()
+        );
+
+        // A validly signed state from another flow is still rejected.
+        let other_flow = state.sign_state();
+        let replayed = Url::parse(&format!(
+            "{DEEP_LINK_REDIRECT_URI}?code=abc&state={other_flow}"
+        ))
+        .expect("deep link should parse");
+        assert!(deep_link_callback(
+            vec![other.clone(), replayed],
+            &signed,
+            &state.state_verifier(),
+            "c".to_string()
+        )
+        .expect("callback link should be picked")
+        .is_err());
+        assert!(deep_link_callback(
+            vec![other, url],
+            &signed,
+            &state.state_verifier(),
+            "c".to_string()
+        )
+        .expect("callback link should be picked")
+        .is_ok());
+        assert!(deep_link_callback(
+            Vec::new(),
+            &signed,
+            &state.state_verifier(),
+            "c".to_string()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn signed_state_verifies_only_with_its_key() {
+        let state = AuthState::new();
+        let signed = state.sign_state();
+        assert!(state.verify_state(&signed));
+        assert!((state.state_verifier())(&signed));
+
+        let (nonce, _) = signed.split_once('.').expect("state should be signed");
+        assert!(!state.verify_state(nonce));
+        assert!(!state.verify_state(&format!("{nonce}.{}", "00".repeat(32))));
+        assert!(!state.verify_state(""));
+        assert!(!AuthState::new().verify_state(&signed));
+    }
+
+    #[test]
+    fn cancel_all_cancels_every_flow() {
+        let clock = MockClock::new(1);
+        let state = AuthState::with_clock(clock.clone());
+        let first = PendingOAuth::new_device_flow("a".to_string(), "x".to_string(), 5, i64::MAX);
+        let first_cancel = first.cancel_flag.clone();
+        state.insert("second".to_string(), first);
+        clock.advance_ms(1);
+        let second = PendingOAuth::new_device_flow("b".to_string(), "y".to_string(), 5, i64::MAX);
+        state.insert("first".to_string(), second);
+
+        let summaries = state.summaries();
+        assert_eq!(
+            summaries
+                .iter()
+                .map(|summary| (summary.request_id.as_str(), summary.account_id.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("second", "a"), ("first", "b")]
+        );
+
+        assert_eq!(state.cancel_all().len(), 2);
+        assert_eq!(state.len(), 0);
+        assert!(first_cancel.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn stale_flows_are_cancelled_and_evicted() {
+        let clock = MockClock::new(1_000_000);
+        let state = AuthState::with_clock(clock.clone());
+
+        let old = PendingOAuth::new_device_flow("a".to_string(), "code".to_string(), 5, i64::MAX);
+        let old_cancel = old.cancel_flag.clone();
+        state.insert("old".to_string(), old);
+        clock.advance_ms(200_000);
+        let now_ms = state.now_unix_ms();
+
+        let expired =
+            PendingOAuth::new_device_flow("b".to_string(), "code".to_string(), 5, now_ms - 1);
+        state.insert("expired".to_string(), expired);
+
+        let fresh = PendingOAuth::new_device_flow("c".to_string(), "code".to_string(), 5, i64::MAX);
+        let fresh_cancel = fresh.cancel_flag.clone();
+        state.insert("fresh".to_string(), fresh);
+
+        let mut evicted = state.evict_stale(180_000);
+        evicted.sort();
+        assert_eq!(evicted, vec!["expired".to_string(), "old".to_string()]);
+        assert_eq!(state.len(), 1);
+        assert!(state.get("fresh").is_some());
+        assert!(old_cancel.load(Ordering::SeqCst));
+        assert!(!fresh_cancel.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,158 @@
+//! Launch-on-login through `tauri-plugin-autostart`. The OS login item is the
+//! source of truth; `launchOnLogin` in `SettingsStore` only mirrors it so the
+//! UI can render without an OS query. Building with the `mock-autostart`
+//! feature replaces the login item with an in-memory flag.
+
+use tauri::AppHandle;
+
+use crate::error::Result;
+use crate::settings_store::SettingsStore;
+
+const LAUNCH_ON_LOGIN_SETTING: &str = "launchOnLogin";
+const START_MINIMIZED_SETTING: &str = "startMinimized";
+
+pub trait AutostartBackend {
+    fn is_enabled(&self) -> Result<bool>;
+    fn set_enabled(&self, enabled: bool) -> Result<()>;
+}
+
+#[cfg(not(feature = "mock-autostart"))]
+pub struct OsAutostart<'a>(pub &'a AppHandle);
+
+#[cfg(not(feature = "mock-autostart"))]
+impl AutostartBackend for OsAutostart<'_> {
+    fn is_enabled(&self) -> Result<bool> {
+        use tauri_plugin_autostart::ManagerExt;
+
+        use crate::error::BackendError;
+
+        self.0
+            .autolaunch()
+            .is_enabled()
+            .map_err(|err| BackendError::Store(format!("autostart: {err}")))
+    }
+
+    fn set_enabled(&self, enabled: bool) -> Result<()> {
+        use tauri_plugin_autostart::ManagerExt;
+
+        use crate::error::BackendError;
+
+        let autolaunch = self.0.autolaunch();
+        let result = if enabled {
+            autolaunch.enable()
+        } else {
+            autolaunch.disable()
+        };
+        result.map_err(|err| BackendError::Store(format!("autostart: {err}")))
+    }
+}
+
+/// In-memory login item used by tests and `mock-autostart` builds.
+#[cfg(any(test, feature = "mock-autostart"))]
+#[derive(Debug, Default)]
+pub struct MockAutostart {
+    enabled: std::sync::atomic::AtomicBool,
+}
+
+#[cfg(any(test, feature = "mock-autostart"))]
+impl AutostartBackend for MockAutostart {
+    fn is_enabled(&self) -> Result<bool> {
+        Ok(self.enabled.load(std::sync::atomic::Ordering::SeqCst))
+    }
+
+    fn set_enabled(&self, enabled: bool) -> Result<()> {
+        self.enabled
+            .store(enabled, std::sync::atomic::Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "mock-autostart"))]
+pub fn with_backend<T>(app: &AppHandle, f: impl FnOnce(&dyn AutostartBackend) -> T) -> T {
+    f(&OsAutostart(app))
+}
+
+#[cfg(feature = "mock-autostart")]
+pub fn with_backend<T>(app: &AppHandle, f: impl FnOnce(&dyn AutostartBackend) -> T) -> T {
+    use tauri::Manager;
+
+    f(app.state::<MockAutostart>().inner())
+}
+
+/// Reads the login item state from the backend and mirrors it into settings.
+pub fn sync_launch_on_login(
+    backend: &dyn AutostartBackend,
+    settings: &SettingsStore,
+) -> Result<bool> {
+    let enabled = backend.is_enabled()?;
+    settings.set_setting(LAUNCH_ON_LOGIN_SETTING, serde_json::json!(enabled))?;
+    Ok(enabled)
+}
+
+/// Enables or disables the login item and returns the state the OS reports
+/// afterwards. Enabling also sets `startMinimized` so login launches stay in
+/// the tray.
+pub fn set_launch_on_login(
+    backend: &dyn AutostartBackend,
+    settings: &SettingsStore,
+    enabled: bool,
+) -> Result<bool> {
+    backend.set_enabled(enabled)?;
+    if enabled {
+        settings.set_setting(START_MINIMIZED_SETTING, serde_json::json!(true))?;
+    }
+    sync_launch_on_login(backend, settings)
+}
+
+/// Whether startup leaves the panel hidden in the tray. Defaults to `true`,
+/// which is how the app has always started.
+pub fn start_minimized(settings: &SettingsStore) -> bool {
+    settings
+        .get_setting(START_MINIMIZED_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|value| value.as_bool())
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use uuid::Uuid;
+
+    #[test]
+    fn launch_on_login_mirrors_backend_state_into_settings() {
+        let dir = std::env::temp_dir().join(format!("openburn-autostart-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("temp dir should be created");
+        let settings = SettingsStore::load_from_path(dir.join("app-settings.json"))
+            .expect("store should load");
+        let backend = MockAutostart::default();
+
+        settings
+            .set_setting(START_MINIMIZED_SETTING, serde_json::json!(false))
+            .expect("set should succeed");
+        assert!(!sync_launch_on_login(&backend, &settings).expect("sync should succeed"));
+        assert_eq!(
+            settings
+                .get_setting(LAUNCH_ON_LOGIN_SETTING)
+                .expect("get should succeed"),
+            Some(serde_json::json!(false))
+        );
+
+        assert!(set_launch_on_login(&backend, &settings, true).expect("enable should succeed"));
+        assert!(backend.is_enabled().expect("backend should answer"));
+        assert!(start_minimized(&settings));
+        assert_eq!(
+            settings
+                .get_setting(LAUNCH_ON_LOGIN_SETTING)
+                .expect("get should succeed"),
+            Some(serde_json::json!(true))
+        );
+
+        assert!(!set_launch_on_login(&backend, &settings, false).expect("disable should succeed"));
+        assert!(start_minimized(&settings));
+
+        fs::remove_dir_all(dir).expect("temp dir should be removed");
+    }
+}
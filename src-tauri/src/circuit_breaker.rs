@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::error::{BackendError, Result};
+use crate::utils::now_instant;
+
+pub const DEFAULT_BACKOFF: Duration = Duration::from_secs(5 * 60);
+const FAILURE_THRESHOLD: u32 = 5;
+
+#[derive(Debug, Default)]
+struct CircuitEntry {
+    consecutive_failures: u32,
+    last_error: Option<String>,
+    opened_at: Option<Instant>,
+}
+
+/// Consecutive probe failures per `(provider_id, account_id)`. After
+/// `FAILURE_THRESHOLD` failures the circuit opens and probes are skipped until
+/// `backoff` elapses, at which point a single half-open attempt is allowed.
+#[derive(Debug)]
+pub struct CircuitBreakerState {
+    backoff: Duration,
+    entries: Mutex<HashMap<(String, String), CircuitEntry>>,
+}
+
+impl CircuitBreakerState {
+    pub fn new(backoff: Duration) -> Self {
+        Self {
+            backoff,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the last recorded error while the circuit is open, or `None`
+    /// when the account may be probed (closed or half-open).
+    pub fn open_error(&self, provider_id: &str, account_id: &str) -> Result<Option<String>> {
+        self.open_error_at(provider_id, account_id, now_instant())
+    }
+
+    fn open_error_at(
+        &self,
+        provider_id: &str,
+        account_id: &str,
+        now: Instant,
+    ) -> Result<Option<String>> {
+        let entries = self.lock_entries()?;
+        let Some(entry) = entries.get(&key(provider_id, account_id)) else {
+            return Ok(None);
+        };
+        let Some(opened_at) = entry.opened_at else {
+            return Ok(None);
+        };
+        if now.saturating_duration_since(opened_at) >= self.backoff {
+            return Ok(None);
+        }
+        Ok(entry.last_error.clone())
+    }
+
+    pub fn record_success(&self, provider_id: &str, account_id: &str) -> Result<()> {
+        let mut entries = self.lock_entries()?;
+        entries.remove(&key(provider_id, account_id));
+        Ok(())
+    }
+
+    pub fn record_failure(&self, provider_id: &str, account_id: &str, message: &str) -> Result<()> {
+        self.record_failure_at(provider_id, account_id, message, now_instant())
+    }
+
+    fn record_failure_at(
+        &self,
+        provider_id: &str,
+        account_id: &str,
+        message: &str,
+        now: Instant,
+    ) -> Result<()> {
+        let mut entries = self.lock_entries()?;
+        let entry = entries.entry(key(provider_id, account_id)).or_default();
+        entry.consecutive_failures = entry.consecutive_failures.saturating_add(1);
+        entry.last_error = Some(message.to_string());
+        if entry.consecutive_failures >= FAILURE_THRESHOLD {
+            if entry.opened_at.is_none() {
+                log::warn!(
+                    "[probe] circuit opened provider={} account={} failures={}",
+                    provider_id,
+                    account_id,
+                    entry.consecutive_failures
+                );
+            }
+            // A failed half-open attempt re-opens the circuit for another backoff.
+            entry.opened_at = Some(now);
+        }
+        Ok(())
+    }
+
+    /// Closes the circuit for one account, or for every account of the provider.
+    pub fn reset(&self, provider_id: &str, account_id: Option<&str>) -> Result<()> {
+        let mut entries = self.lock_entries()?;
+        match account_id {
+            Some(account_id) => {
+                entries.remove(&key(provider_id, account_id));
+            }
+            None => entries.retain(|(provider, _), _| provider != provider_id),
+        }
+        Ok(())
+    }
+
+    fn lock_entries(&self) -> Result<MutexGuard<'_, HashMap<(String, String), CircuitEntry>>> {
+        self.entries
+            .lock()
+            .map_err(|_| BackendError::Store("circuit breaker lock poisoned".to_string()))
+    }
+}
+
+fn key(provider_id: &str, account_id: &str) -> (String, String) {
+    (provider_id.to_string(), account_id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circuit_opens_after_threshold_and_half_opens_after_backoff() {
+        let breaker = CircuitBreakerState::new(Duration::from_secs(60));
+        let start = Instant::now();
+
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            breaker
+                .record_failure_at("codex", "acc-1", "HTTP 401", start)
+                .expect("record should succeed");
+        }
+        assert_eq!(
+            breaker
+                .open_error_at("codex", "acc-1", start)
+                .expect("check should succeed"),
+            None
+        );
+
+        breaker
+            .record_failure_at("codex", "acc-1", "HTTP 401", start)
+            .expect("record should succeed");
+        assert_eq!(
+            breaker
+                .open_error_at("codex", "acc-1", start)
+                .expect("check should succeed"),
+            Some("HTTP 401".to_string())
+        );
+        assert_eq!(
+            breaker
+                .open_error_at("codex", "acc-1", start + Duration::from_secs(60))
+                .expect("check should succeed"),
+            None
+        );
+
+        breaker
+            .record_success("codex", "acc-1")
+            .expect("record should succeed");
+        breaker
+            .record_failure_at("codex", "acc-1", "HTTP 401", start)
+            .expect("record should succeed");
+        assert_eq!(
+            breaker
+                .open_error_at("codex", "acc-1", start)
+                .expect("check should succeed"),
+            None
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use std::time::Duration;
+
+use serde::Serialize;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -16,10 +19,110 @@ pub enum BackendError {
     Crypto(String),
     #[error("provider error: {0}")]
     Provider(String),
+    #[error("rate limited: {message}")]
+    RateLimit {
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    #[error("service unavailable: {message}")]
+    ServiceUnavailable {
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    #[error("authentication expired: {message}")]
+    AuthExpired {
+        account_id: Option<String>,
+        message: String,
+    },
+    #[error("network timeout after {elapsed_ms}ms: {message}")]
+    NetworkTimeout { elapsed_ms: u64, message: String },
     #[error("validation error: {0}")]
     Validation(String),
     #[error("account not found")]
     AccountNotFound,
 }
 
+impl BackendError {
+    /// Stable camelCase name of the variant, used as `AppError::kind`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Path(_) => "path",
+            Self::Io(_) => "io",
+            Self::Json(_) => "json",
+            Self::Store(_) => "store",
+            Self::Keyring(_) => "keyring",
+            Self::Crypto(_) => "crypto",
+            Self::Provider(_) => "provider",
+            Self::RateLimit { .. } => "rateLimit",
+            Self::ServiceUnavailable { .. } => "serviceUnavailable",
+            Self::AuthExpired { .. } => "authExpired",
+            Self::NetworkTimeout { .. } => "networkTimeout",
+            Self::Validation(_) => "validation",
+            Self::AccountNotFound => "accountNotFound",
+        }
+    }
+
+    /// Server-requested delay carried by rate-limit and 503 errors.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimit { retry_after, .. } | Self::ServiceUnavailable { retry_after, .. } => {
+                *retry_after
+            }
+            _ => None,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, BackendError>;
+
+/// Error payload returned by Tauri commands that surface provider failures,
+/// so the frontend can branch on `kind` instead of parsing messages.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppError {
+    pub kind: &'static str,
+    pub message: String,
+    pub retry_after_ms: Option<u64>,
+}
+
+impl From<BackendError> for AppError {
+    fn from(err: BackendError) -> Self {
+        let retry_after_ms = err
+            .retry_after()
+            .map(|retry_after| u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX));
+        Self {
+            kind: err.kind(),
+            message: err.to_string(),
+            retry_after_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn app_error_serializes_kind_and_retry_after() {
+        let err = AppError::from(BackendError::RateLimit {
+            retry_after: Some(Duration::from_secs(2)),
+            message: "slow down".to_string(),
+        });
+        let value = serde_json::to_value(&err).expect("app error should serialize");
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "kind": "rateLimit",
+                "message": "rate limited: slow down",
+                "retryAfterMs": 2000,
+            })
+        );
+
+        let err = AppError::from(BackendError::AuthExpired {
+            account_id: Some("acc-1".to_string()),
+            message: "token revoked".to_string(),
+        });
+        assert_eq!(err.kind, "authExpired");
+        assert_eq!(err.retry_after_ms, None);
+    }
+}
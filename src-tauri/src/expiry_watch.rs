@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio_util::sync::CancellationToken;
+
+use crate::account_store::AccountStore;
+use crate::models::AccountRecord;
+use crate::notifications;
+use crate::utils::{now_unix_ms, rfc3339_to_unix_ms};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30 * 60);
+const WARNING_WINDOW_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialsExpiryWarningEvent {
+    pub account_id: String,
+    pub provider_id: String,
+    pub expires_in_ms: i64,
+}
+
+/// Managed handle used to stop the expiry watcher when the app exits.
+pub struct ExpiryWatch(pub CancellationToken);
+
+/// Checks every `CHECK_INTERVAL` for credentials expiring within the next
+/// 24 hours and warns once per account and expiry.
+pub fn spawn(app: AppHandle, cancel: CancellationToken) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        let mut warned: HashSet<(String, String)> = HashSet::new();
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = interval.tick() => warn_expiring_credentials(&app, &mut warned),
+            }
+        }
+        log::info!("[expiry] watcher stopped");
+    });
+}
+
+fn warn_expiring_credentials(app: &AppHandle, warned: &mut HashSet<(String, String)>) {
+    let Some(store) = app.try_state::<AccountStore>() else {
+        return;
+    };
+    let accounts = match store.list_accounts() {
+        Ok(accounts) => accounts,
+        Err(err) => {
+            log::warn!("[expiry] failed to list accounts: {}", err);
+            return;
+        }
+    };
+
+    let now_ms = now_unix_ms();
+    for account in accounts {
+        let Some(expires_in_ms) = expires_in_ms(&account, now_ms) else {
+            continue;
+        };
+        let expires_at = account.credentials_expires_at.clone().unwrap_or_default();
+        if !warned.insert((account.id.clone(), expires_at)) {
+            continue;
+        }
+
+        log::info!(
+            "[expiry] credentials expiring provider={} account={} expires_in_ms={}",
+            account.provider_id,
+            account.id,
+            expires_in_ms
+        );
+        let _ = app.emit(
+            "credentials:expiry-warning",
+            CredentialsExpiryWarningEvent {
+                account_id: account.id.clone(),
+                provider_id: account.provider_id.clone(),
+                expires_in_ms,
+            },
+        );
+        notifications::notify_credentials_expiry(app, &account.provider_id, expires_in_ms);
+    }
+}
+
+/// Milliseconds until the account's credentials expire, when that falls
+/// within the warning window.
+fn expires_in_ms(account: &AccountRecord, now_ms: i64) -> Option<i64> {
+    if account.disabled.unwrap_or(false) {
+        return None;
+    }
+    let expires_at = rfc3339_to_unix_ms(account.credentials_expires_at.as_deref()?)?;
+    let remaining = expires_at - now_ms;
+    (remaining > 0 && remaining <= WARNING_WINDOW_MS).then_some(remaining)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::unix_ms_to_rfc3339;
+
+    fn account(expires_at_ms: Option<i64>) -> AccountRecord {
+        serde_json::from_value(serde_json::json!({
+            "id": "acc-1",
+            "providerId": "claude",
+            "label": "Work",
+            "settings": {},
+            "createdAt": "2026-01-01T00:00:00Z",
+            "updatedAt": "2026-01-01T00:00:00Z",
+            "credentialsExpiresAt": expires_at_ms.and_then(unix_ms_to_rfc3339),
+        }))
+        .expect("account should deserialize")
+    }
+
+    #[test]
+    fn expiry_warning_only_within_next_day() {
+        let now = 1_800_000_000_000;
+        let hour = 60 * 60 * 1000;
+
+        assert_eq!(expires_in_ms(&account(Some(now + hour)), now), Some(hour));
+        assert_eq!(
+            expires_in_ms(&account(Some(now + WARNING_WINDOW_MS)), now),
+            Some(WARNING_WINDOW_MS)
+        );
+        assert_eq!(expires_in_ms(&account(Some(now + 25 * hour)), now), None);
+        assert_eq!(expires_in_ms(&account(Some(now - hour)), now), None);
+        assert_eq!(expires_in_ms(&account(None), now), None);
+    }
+}
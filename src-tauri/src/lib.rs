@@ -2,37 +2,59 @@ mod account_store;
 #[cfg(target_os = "macos")]
 mod app_nap;
 mod auth;
+mod autostart;
+mod circuit_breaker;
 mod error;
+mod expiry_watch;
+mod log_export;
 mod models;
+mod notifications;
 mod oauth;
 mod panel;
 mod probe;
+mod probe_cache;
+mod probe_history;
 mod providers;
 mod secrets;
+mod settings_store;
+mod shortcut;
 mod tray;
 mod utils;
 #[cfg(target_os = "macos")]
 mod webkit_config;
 
 use std::collections::HashSet;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use account_store::AccountStore;
-use auth::{AuthState, PendingOAuth};
-use futures::future::join_all;
-use models::{AccountRecord, CreateAccountInput, UpdateAccountInput};
-use probe::{ProbeBatchCompleteEvent, ProbeBatchStarted, ProbeResultEvent, ProviderMeta};
+use auth::{AuthState, CallbackMode, PendingOAuth};
+use circuit_breaker::CircuitBreakerState;
+use error::AppError;
+use models::{
+    is_valid_provider_id, is_valid_strategy_id, AccountErrorInfo, AccountRecord,
+    AccountWithCredentialStatus, CreateAccountInput, UpdateAccountInput,
+};
+use probe::{ProbeBatchContext, ProbeBatchStarted, ProbeResultEvent, ProviderMeta, ProviderOutput};
+use probe_cache::ProbeCache;
+use probe_history::{HighUsageTracker, ProbeHistory, ProbeOutcome, ThresholdTracker};
+use providers::usage::UsageThresholds;
 use providers::{
     clients, find_provider_contract, validate_auth_strategy_for_provider, ProviderDescriptor,
 };
-use tauri::{Emitter, Manager, State};
+use settings_store::SettingsStore;
+use tauri::{Emitter, Listener, Manager, State};
 use tauri_plugin_log::{Target, TargetKind};
-use utils::now_unix_ms;
+use utils::{elapsed_ms_since, now_instant, unix_ms_to_rfc3339};
 use uuid::Uuid;
 
 const DEFAULT_OAUTH_TIMEOUT_MS: u64 = 180_000;
+// `oauthTimeoutMs` in the settings store overrides the default wait of
+// `finish_*_oauth` when the caller passes no `timeoutMs`.
+const OAUTH_TIMEOUT_SETTING: &str = "oauthTimeoutMs";
+const MIN_OAUTH_TIMEOUT_MS: u64 = 10_000;
+const MAX_OAUTH_TIMEOUT_MS: u64 = 600_000;
 const OPENCODE_LOGIN_URL: &str = "https://opencode.ai/auth";
 const OPENCODE_COOKIE_POLL_INTERVAL_MS: u64 = 400;
 const OPENCODE_COOKIE_URLS: [&str; 3] = [
@@ -51,14 +73,144 @@ fn init_panel(app_handle: tauri::AppHandle) -> Result<(), String> {
     panel::init(&app_handle).map_err(|err| err.to_string())
 }
 
+#[cfg(target_os = "macos")]
 #[tauri::command]
 fn hide_panel(app_handle: tauri::AppHandle) {
-    use tauri_nspanel::ManagerExt;
-    if let Ok(panel) = app_handle.get_webview_panel("main") {
-        panel.hide();
+    if let Err(err) = panel::set_visible(&app_handle, false) {
+        log::warn!("[panel] hide failed: {}", err);
     }
 }
 
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn hide_panel(_app_handle: tauri::AppHandle) {}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn show_panel(app_handle: tauri::AppHandle) -> Result<(), String> {
+    panel::set_visible(&app_handle, true)
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn show_panel(_app_handle: tauri::AppHandle) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn set_panel_auto_hide(
+    app_handle: tauri::AppHandle,
+    settings: State<'_, SettingsStore>,
+    enabled: bool,
+) -> Result<(), String> {
+    settings
+        .set_panel_auto_hide(enabled)
+        .map_err(|err| err.to_string())?;
+    panel::set_hides_on_deactivate(&app_handle, enabled).map_err(|err| err.to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn set_panel_auto_hide(
+    _app_handle: tauri::AppHandle,
+    settings: State<'_, SettingsStore>,
+    enabled: bool,
+) -> Result<(), String> {
+    settings
+        .set_panel_auto_hide(enabled)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn set_launch_on_login(
+    app_handle: tauri::AppHandle,
+    settings: State<'_, SettingsStore>,
+    enabled: bool,
+) -> Result<(), String> {
+    let enabled = autostart::with_backend(&app_handle, |backend| {
+        autostart::set_launch_on_login(backend, &settings, enabled)
+    })
+    .map_err(|err| err.to_string())?;
+    log::info!("[autostart] launch on login enabled={}", enabled);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_launch_on_login(
+    app_handle: tauri::AppHandle,
+    settings: State<'_, SettingsStore>,
+) -> Result<bool, String> {
+    autostart::with_backend(&app_handle, |backend| {
+        autostart::sync_launch_on_login(backend, &settings)
+    })
+    .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn set_log_level(
+    app_handle: tauri::AppHandle,
+    settings: State<'_, SettingsStore>,
+    level: String,
+) -> Result<(), String> {
+    let filter = settings_store::parse_log_level(&level)
+        .ok_or_else(|| format!("unsupported log level: {}", level.trim()))?;
+    log::info!("[logs] log level changing to {:?}", filter);
+    log::set_max_level(filter);
+    settings
+        .set_log_level(filter)
+        .map_err(|err| err.to_string())?;
+    // Rebuild so the tray's Debug Level submenu checks the new level.
+    tray::refresh_provider_status(&app_handle);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_log_level() -> String {
+    settings_store::log_level_name(log::max_level()).to_string()
+}
+
+#[tauri::command]
+fn get_log_dir(app_handle: tauri::AppHandle) -> Result<String, String> {
+    log_export::log_dir(&app_handle)
+        .map(|dir| dir.to_string_lossy().into_owned())
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn export_log_file(
+    app_handle: tauri::AppHandle,
+    settings: State<'_, SettingsStore>,
+) -> Result<String, String> {
+    log_export::export_log_file(&app_handle, &settings).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn set_panel_shortcut(
+    app_handle: tauri::AppHandle,
+    settings: State<'_, SettingsStore>,
+    shortcut: String,
+) -> Result<(), String> {
+    shortcut::replace(&app_handle, &settings, &shortcut)
+}
+
+#[cfg(target_os = "macos")]
+#[tauri::command]
+fn toggle_panel(app_handle: tauri::AppHandle) -> bool {
+    panel::toggle(&app_handle).unwrap_or_else(|err| {
+        log::warn!("[panel] toggle failed: {}", err);
+        false
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+fn toggle_panel(_app_handle: tauri::AppHandle) -> bool {
+    false
+}
+
 #[tauri::command]
 fn list_providers_meta() -> Vec<ProviderMeta> {
     probe::all_provider_meta()
@@ -67,9 +219,10 @@ fn list_providers_meta() -> Vec<ProviderMeta> {
 #[tauri::command(rename_all = "camelCase")]
 async fn start_provider_probe_batch(
     app_handle: tauri::AppHandle,
-    store: State<'_, AccountStore>,
+    settings: State<'_, SettingsStore>,
     batch_id: Option<String>,
     provider_ids: Option<Vec<String>>,
+    force: Option<bool>,
 ) -> Result<ProbeBatchStarted, String> {
     let batch_id = batch_id
         .and_then(|id| {
@@ -96,52 +249,120 @@ async fn start_provider_probe_batch(
         known_ids.clone()
     };
 
-    if selected_ids.is_empty() {
-        let _ = app_handle.emit(
-            "probe:batch-complete",
-            ProbeBatchCompleteEvent {
+    tauri::async_runtime::spawn(run_probe_batch(
+        app_handle,
+        batch_id.clone(),
+        selected_ids.clone(),
+        force.unwrap_or(false),
+        probe_max_concurrency(&settings),
+        probe_cache_ttl_ms(&settings),
+        usage_thresholds(&settings),
+    ));
+
+    Ok(ProbeBatchStarted {
+        batch_id,
+        provider_ids: selected_ids,
+    })
+}
+
+/// Probes the selected providers in the background, emitting `probe:result`
+/// as each provider finishes and `probe:batch-complete` once all are done.
+async fn run_probe_batch(
+    app_handle: tauri::AppHandle,
+    batch_id: String,
+    provider_ids: Vec<String>,
+    force: bool,
+    max_concurrency: usize,
+    cache_ttl_ms: u64,
+    usage_thresholds: UsageThresholds,
+) {
+    let store = app_handle.state::<AccountStore>();
+    let breaker = app_handle.state::<CircuitBreakerState>();
+    let history = app_handle.state::<ProbeHistory>();
+    let thresholds = app_handle.state::<ThresholdTracker>();
+    let cache = app_handle.state::<ProbeCache>();
+    let batch = ProbeBatchContext::new(
+        &batch_id,
+        force,
+        breaker.inner(),
+        history.inner(),
+        thresholds.inner(),
+    )
+    .with_cache(cache.inner(), cache_ttl_ms)
+    .with_usage_thresholds(usage_thresholds);
+
+    probe::run_with_concurrency_limit(
+        &provider_ids,
+        max_concurrency,
+        |provider_id| {
+            let (app_handle, store, batch) = (&app_handle, store.inner(), &batch);
+            async move {
+                match probe::probe_provider(app_handle, store, provider_id, batch).await {
+                    Ok(output) => output,
+                    Err(err) => probe::build_error_output(provider_id, err.to_string()),
+                }
+            }
+        },
+        |output| {
+            let event = ProbeResultEvent {
                 batch_id: batch_id.clone(),
-            },
-        );
-        return Ok(ProbeBatchStarted {
+                output,
+            };
+            if let Err(err) = app_handle.emit("probe:result", event) {
+                log::warn!("[probe] failed to emit result batch={}: {}", batch_id, err);
+            }
+        },
+    )
+    .await;
+
+    if let Err(err) = app_handle.emit("probe:batch-complete", batch.complete_event()) {
+        log::warn!(
+            "[probe] failed to emit batch completion batch={}: {}",
             batch_id,
-            provider_ids: selected_ids,
-        });
+            err
+        );
     }
+}
 
-    let outputs = join_all(selected_ids.iter().map(|provider_id| async {
-        match probe::probe_provider(&app_handle, store.inner(), provider_id).await {
-            Ok(output) => output,
-            Err(err) => probe::build_error_output(provider_id, err.to_string()),
-        }
-    }))
-    .await;
+#[tauri::command(rename_all = "camelCase")]
+async fn probe_single_account(
+    app_handle: tauri::AppHandle,
+    store: State<'_, AccountStore>,
+    breaker: State<'_, CircuitBreakerState>,
+    history: State<'_, ProbeHistory>,
+    thresholds: State<'_, ThresholdTracker>,
+    cache: State<'_, ProbeCache>,
+    settings: State<'_, SettingsStore>,
+    account_id: String,
+) -> Result<ProbeResultEvent, String> {
+    let batch_id = Uuid::new_v4().to_string();
+    let batch = ProbeBatchContext::new(
+        &batch_id,
+        true,
+        breaker.inner(),
+        history.inner(),
+        thresholds.inner(),
+    )
+    .for_account(&account_id)
+    .with_cache(cache.inner(), probe_cache_ttl_ms(&settings))
+    .with_usage_thresholds(usage_thresholds(&settings));
 
-    for output in outputs {
-        app_handle
-            .emit(
-                "probe:result",
-                ProbeResultEvent {
-                    batch_id: batch_id.clone(),
-                    output,
-                },
-            )
-            .map_err(|err| err.to_string())?;
-    }
+    let output = probe::probe_single_account(&app_handle, store.inner(), &batch)
+        .await
+        .map_err(|err| err.to_string())?;
+    let result = ProbeResultEvent {
+        batch_id: batch_id.clone(),
+        output,
+    };
 
     app_handle
-        .emit(
-            "probe:batch-complete",
-            ProbeBatchCompleteEvent {
-                batch_id: batch_id.clone(),
-            },
-        )
+        .emit("probe:result", result.clone())
+        .map_err(|err| err.to_string())?;
+    app_handle
+        .emit("probe:batch-complete", batch.complete_event())
         .map_err(|err| err.to_string())?;
 
-    Ok(ProbeBatchStarted {
-        batch_id,
-        provider_ids: selected_ids,
-    })
+    Ok(result)
 }
 
 #[tauri::command]
@@ -149,11 +370,60 @@ fn list_providers() -> Vec<ProviderDescriptor> {
     providers::all_provider_descriptors()
 }
 
+#[tauri::command]
+fn list_provider_aliases() -> Vec<(String, String)> {
+    providers::list_provider_aliases()
+}
+
+#[tauri::command]
+fn validate_provider_id(provider_id: String) -> bool {
+    is_valid_provider_id(&provider_id)
+}
+
+#[tauri::command]
+fn validate_strategy_id(strategy_id: String) -> bool {
+    is_valid_strategy_id(&strategy_id)
+}
+
+#[tauri::command]
+fn validate_account_label(label: String) -> Result<(), String> {
+    models::validate_account_label(&label)
+}
+
+#[tauri::command]
+fn validate_provider_settings(
+    provider_id: String,
+    settings: serde_json::Value,
+) -> Result<(), String> {
+    let provider = find_provider_contract(provider_id.trim())
+        .ok_or_else(|| format!("provider '{}' is not registered", provider_id.trim()))?;
+    providers::validate_provider_settings(provider, &settings)
+}
+
 #[tauri::command]
 fn list_accounts(store: State<'_, AccountStore>) -> Result<Vec<AccountRecord>, String> {
     store.list_accounts().map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+fn list_accounts_with_credential_status(
+    store: State<'_, AccountStore>,
+) -> Result<Vec<AccountWithCredentialStatus>, String> {
+    store
+        .list_accounts_with_credential_status()
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn get_account_with_credential_status(
+    store: State<'_, AccountStore>,
+    account_id: String,
+) -> Result<Option<AccountWithCredentialStatus>, String> {
+    store
+        .get_account_with_credential_status(&account_id)
+        .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 fn get_account(
     store: State<'_, AccountStore>,
@@ -164,6 +434,16 @@ fn get_account(
         .map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+fn get_account_last_error(
+    store: State<'_, AccountStore>,
+    account_id: String,
+) -> Result<Option<AccountErrorInfo>, String> {
+    store
+        .get_account_last_error(&account_id)
+        .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 fn create_account(
     store: State<'_, AccountStore>,
@@ -183,13 +463,265 @@ fn update_account(
         .map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+fn set_account_disabled(
+    store: State<'_, AccountStore>,
+    account_id: String,
+    disabled: bool,
+) -> Result<AccountRecord, String> {
+    store
+        .update_account(
+            &account_id,
+            UpdateAccountInput::default().with_disabled(disabled),
+        )
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn update_account_tags(
+    store: State<'_, AccountStore>,
+    account_id: String,
+    tags: Vec<String>,
+) -> Result<AccountRecord, String> {
+    store
+        .update_account(&account_id, UpdateAccountInput::default().with_tags(tags))
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn update_account_notes(
+    store: State<'_, AccountStore>,
+    account_id: String,
+    notes: String,
+) -> Result<AccountRecord, String> {
+    store
+        .update_account(&account_id, UpdateAccountInput::default().with_notes(notes))
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn set_account_icon(
+    store: State<'_, AccountStore>,
+    account_id: String,
+    icon: Option<String>,
+) -> Result<(), String> {
+    store
+        .set_icon_override(&account_id, icon)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn reorder_accounts(
+    store: State<'_, AccountStore>,
+    account_ids: Vec<String>,
+) -> Result<(), String> {
+    store
+        .reorder_accounts(&account_ids)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn move_account_before(
+    store: State<'_, AccountStore>,
+    account_id: String,
+    target_id: String,
+) -> Result<(), String> {
+    store
+        .move_account_before(&account_id, &target_id)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn list_accounts_by_tag(
+    store: State<'_, AccountStore>,
+    tag: String,
+) -> Result<Vec<AccountRecord>, String> {
+    store
+        .filter_accounts_by_tag(&tag)
+        .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 fn delete_account(
     store: State<'_, AccountStore>,
+    cache: State<'_, ProbeCache>,
     account_id: String,
 ) -> Result<Option<AccountRecord>, String> {
-    store
+    let deleted = store
         .delete_account(&account_id)
+        .map_err(|err| err.to_string())?;
+    if let Some(account) = deleted.as_ref() {
+        cache
+            .invalidate(Some(&account.provider_id))
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(deleted)
+}
+
+#[tauri::command]
+fn list_deleted_accounts(store: State<'_, AccountStore>) -> Result<Vec<AccountRecord>, String> {
+    store.list_deleted_accounts().map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn restore_account(
+    store: State<'_, AccountStore>,
+    account_id: String,
+) -> Result<AccountRecord, String> {
+    store
+        .restore_account(&account_id)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn purge_account(
+    store: State<'_, AccountStore>,
+    account_id: String,
+) -> Result<Option<AccountRecord>, String> {
+    store
+        .purge_account(&account_id)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn purge_accounts_older_than(
+    store: State<'_, AccountStore>,
+    days: u32,
+) -> Result<Vec<AccountRecord>, String> {
+    store
+        .purge_accounts_older_than(days)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn reset_circuit_breaker(
+    breaker: State<'_, CircuitBreakerState>,
+    provider_id: String,
+    account_id: Option<String>,
+) -> Result<(), String> {
+    breaker
+        .reset(&provider_id, account_id.as_deref())
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn get_account_probe_history(
+    history: State<'_, ProbeHistory>,
+    account_id: String,
+) -> Result<Vec<ProbeOutcome>, String> {
+    history.outcomes(&account_id).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn get_setting(
+    settings: State<'_, SettingsStore>,
+    key: String,
+) -> Result<Option<serde_json::Value>, String> {
+    settings.get_setting(&key).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn set_setting(
+    settings: State<'_, SettingsStore>,
+    cache: State<'_, ProbeCache>,
+    key: String,
+    value: serde_json::Value,
+) -> Result<(), String> {
+    settings
+        .set_setting(&key, value)
+        .map_err(|err| err.to_string())?;
+    if matches!(key.trim(), "usageWarnThreshold" | "usageCriticalThreshold") {
+        // Cached outputs carry colors from the previous thresholds.
+        cache.invalidate(None).map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+fn probe_max_concurrency(settings: &SettingsStore) -> usize {
+    settings
+        .get_setting("probeMaxConcurrency")
+        .ok()
+        .flatten()
+        .and_then(|value| value.as_u64())
+        .filter(|value| *value > 0)
+        .map_or(probe::DEFAULT_PROBE_MAX_CONCURRENCY, |value| value as usize)
+}
+
+fn probe_cache_ttl_ms(settings: &SettingsStore) -> u64 {
+    settings
+        .get_setting("probeCacheTtlMs")
+        .ok()
+        .flatten()
+        .and_then(|value| value.as_u64())
+        .unwrap_or(probe_cache::DEFAULT_PROBE_CACHE_TTL_MS)
+}
+
+fn oauth_timeout_ms(settings: &SettingsStore) -> u64 {
+    settings
+        .get_setting(OAUTH_TIMEOUT_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|value| value.as_u64())
+        .unwrap_or(DEFAULT_OAUTH_TIMEOUT_MS)
+        .clamp(MIN_OAUTH_TIMEOUT_MS, MAX_OAUTH_TIMEOUT_MS)
+}
+
+/// Recounts high-usage accounts after a provider result. The badge stays at 0
+/// while usage notifications are disabled.
+fn refresh_usage_badge(app: &tauri::AppHandle, output: &ProviderOutput) {
+    let Some(accounts) = probe::high_usage_accounts(output) else {
+        return;
+    };
+    let Some(tracker) = app.try_state::<HighUsageTracker>() else {
+        return;
+    };
+    let count = match tracker.replace(&output.provider_id, accounts) {
+        Ok(count) => count,
+        Err(err) => {
+            log::warn!("[tray] failed to count high usage accounts: {}", err);
+            return;
+        }
+    };
+    let enabled = app
+        .try_state::<SettingsStore>()
+        .is_some_and(|settings| notifications::notifications_enabled(&settings));
+    tray::update_badge(app, if enabled { count } else { 0 });
+}
+
+fn usage_thresholds(settings: &SettingsStore) -> UsageThresholds {
+    let read_threshold = |key: &str, default: f64| {
+        settings
+            .get_setting(key)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_f64())
+            .unwrap_or(default)
+    };
+    UsageThresholds {
+        warn: read_threshold(
+            "usageWarnThreshold",
+            providers::usage::DEFAULT_USAGE_WARN_THRESHOLD,
+        ),
+        critical: read_threshold(
+            "usageCriticalThreshold",
+            providers::usage::DEFAULT_USAGE_CRITICAL_THRESHOLD,
+        ),
+    }
+}
+
+#[tauri::command]
+fn export_accounts(store: State<'_, AccountStore>) -> Result<String, String> {
+    store.export_accounts().map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn import_accounts(
+    store: State<'_, AccountStore>,
+    json: String,
+    overwrite: bool,
+) -> Result<Vec<AccountRecord>, String> {
+    store
+        .import_accounts(&json, overwrite)
         .map_err(|err| err.to_string())
 }
 
@@ -204,6 +736,30 @@ fn set_account_credentials(
         .map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+fn set_copilot_pat(
+    app: tauri::AppHandle,
+    store: State<'_, AccountStore>,
+    account_id: String,
+    token: String,
+) -> Result<(), String> {
+    let account = ensure_provider_account_with_auth_strategy(
+        store.inner(),
+        &account_id,
+        "copilot",
+        "Copilot",
+        "pat",
+        "personal access token",
+    )?;
+    let credentials = clients::copilot::CopilotPatCredentials::new(&token);
+    if credentials.token.is_empty() {
+        return Err("Copilot personal access token is required".to_string());
+    }
+    let credentials = serde_json::to_value(credentials).map_err(|err| err.to_string())?;
+    secrets::set_account_credentials(&app, store.inner(), &account.id, &credentials)
+        .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 fn has_account_credentials(
     store: State<'_, AccountStore>,
@@ -212,12 +768,78 @@ fn has_account_credentials(
     secrets::has_account_credentials(store.inner(), &account_id).map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+async fn validate_account_credentials(
+    app_handle: tauri::AppHandle,
+    store: State<'_, AccountStore>,
+    account_id: String,
+) -> Result<bool, AppError> {
+    probe::validate_account_credentials(&app_handle, store.inner(), &account_id)
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+async fn refresh_account_credentials(
+    app_handle: tauri::AppHandle,
+    store: State<'_, AccountStore>,
+    account_id: String,
+) -> Result<i64, AppError> {
+    probe::refresh_account_credentials(&app_handle, store.inner(), &account_id)
+        .await
+        .map_err(AppError::from)
+}
+
+#[tauri::command]
+async fn rotate_master_key(
+    app_handle: tauri::AppHandle,
+    store: State<'_, AccountStore>,
+) -> Result<u32, String> {
+    secrets::rotate_master_key(&app_handle, store.inner())
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn submit_master_key_passphrase(passphrase: String) -> Result<(), String> {
+    #[cfg(feature = "passphrase-key")]
+    {
+        secrets::submit_passphrase(passphrase).map_err(|err| err.to_string())
+    }
+    #[cfg(not(feature = "passphrase-key"))]
+    {
+        drop(passphrase);
+        Err("passphrase keys are not enabled in this build".to_string())
+    }
+}
+
 #[tauri::command]
 fn clear_account_credentials(
     store: State<'_, AccountStore>,
+    cache: State<'_, ProbeCache>,
     account_id: String,
 ) -> Result<(), String> {
-    secrets::clear_account_credentials(store.inner(), &account_id).map_err(|err| err.to_string())
+    secrets::clear_account_credentials(store.inner(), &account_id)
+        .map_err(|err| err.to_string())?;
+    let provider_id = store
+        .get_account(&account_id)
+        .map_err(|err| err.to_string())?
+        .map(|account| account.provider_id);
+    // An unknown account leaves nothing to pin the entry to; drop everything.
+    cache
+        .invalidate(provider_id.as_deref())
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn clear_probe_cache(
+    cache: State<'_, ProbeCache>,
+    provider_id: Option<String>,
+) -> Result<(), String> {
+    let provider_id = provider_id.map(|id| id.trim().to_ascii_lowercase());
+    cache
+        .invalidate(provider_id.as_deref())
+        .map_err(|err| err.to_string())
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -227,6 +849,7 @@ struct OAuthStartResponse {
     url: String,
     redirect_uri: String,
     user_code: Option<String>,
+    state: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -236,6 +859,35 @@ struct OAuthResult {
     expires_at: i64,
 }
 
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthBrowserOpenedEvent {
+    request_id: String,
+    url: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthProgressEvent {
+    request_id: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthPollingEvent {
+    request_id: String,
+    attempt: u32,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthCompletedEvent {
+    provider_id: String,
+    request_id: String,
+    account_id: String,
+    correlation_id: String,
+}
+
 fn normalized_callback_path(callback_path: &str) -> String {
     if callback_path.starts_with('/') {
         callback_path.to_string()
@@ -317,9 +969,11 @@ fn close_webview_window_if_exists(app: &tauri::AppHandle, label: &str) {
     }
 }
 
+/// Returns the session `Cookie` header and the auth cookie's expiry in unix
+/// milliseconds, when the window holds an auth cookie.
 fn opencode_cookie_header_from_window(
     window: &tauri::WebviewWindow,
-) -> Result<Option<String>, String> {
+) -> Result<Option<(String, Option<i64>)>, String> {
     let mut total_cookies_seen = 0usize;
 
     for raw_url in OPENCODE_COOKIE_URLS {
@@ -330,6 +984,7 @@ fn opencode_cookie_header_from_window(
             .map_err(|err| format!("Failed to read OpenCode cookies: {err}"))?;
         let mut source_pairs: Vec<(String, String)> = Vec::new();
         let mut seen_names = HashSet::new();
+        let mut auth_attributes = Vec::new();
 
         for cookie in cookies {
             let name = cookie.name().trim();
@@ -344,6 +999,18 @@ fn opencode_cookie_header_from_window(
                 continue;
             }
 
+            if name == "auth" || name == "__Host-auth" {
+                if let Some(max_age) = cookie.max_age() {
+                    auth_attributes.push(format!("Max-Age={}", max_age.whole_seconds()));
+                }
+                if let Some(expires) = cookie.expires_datetime() {
+                    auth_attributes.push(format!(
+                        "Expires={}",
+                        httpdate::fmt_http_date(std::time::SystemTime::from(expires))
+                    ));
+                }
+            }
+
             source_pairs.push((name.to_string(), value.to_string()));
         }
 
@@ -357,6 +1024,7 @@ fn opencode_cookie_header_from_window(
             source_pairs
                 .iter()
                 .map(|(name, value)| (name.as_str(), value.as_str())),
+            &auth_attributes.join("; "),
         );
 
         let has_auth_cookie = source_pairs
@@ -389,26 +1057,50 @@ fn opencode_cookie_header_from_window(
 }
 
 fn start_pkce_oauth_flow<F>(
+    app: &tauri::AppHandle,
     auth_state: &AuthState,
     account_id: String,
     callback_path: &str,
-    callback_port: Option<u16>,
+    callback_mode: CallbackMode,
     build_url: F,
 ) -> Result<OAuthStartResponse, String>
 where
     F: FnOnce(&str, &str, &str) -> Result<String, String>,
 {
     let pkce = oauth::generate_pkce();
-    let state = Uuid::new_v4().to_string();
-    let (port, receiver, cancel_flag) = auth::start_local_callback_listener_with_options(
-        state.clone(),
-        callback_path,
-        callback_port,
-    )
-    .map_err(|err| err.to_string())?;
+    let state = auth_state.sign_state();
+    let (redirect_uri, receiver, cancel_flag) = match callback_mode {
+        CallbackMode::LocalServer { port } => {
+            let (port, receiver, cancel_flag) = auth::start_local_callback_listener_with_options(
+                state.clone(),
+                callback_path,
+                port,
+                auth_state.state_verifier(),
+            )
+            .map_err(|err| err.to_string())?;
+            let callback_path = normalized_callback_path(callback_path);
+            (
+                format!("http://localhost:{port}{callback_path}"),
+                receiver,
+                cancel_flag,
+            )
+        }
+        CallbackMode::DeepLink => {
+            let cancel_flag = Arc::new(AtomicBool::new(false));
+            let receiver = auth::listen_for_deep_link_callback(
+                app,
+                state.clone(),
+                cancel_flag.clone(),
+                auth_state.state_verifier(),
+            );
+            (
+                auth::DEEP_LINK_REDIRECT_URI.to_string(),
+                receiver,
+                cancel_flag,
+            )
+        }
+    };
 
-    let callback_path = normalized_callback_path(callback_path);
-    let redirect_uri = format!("http://localhost:{port}{callback_path}");
     let url = build_url(&redirect_uri, &pkce.challenge, &state)?;
     let request_id = Uuid::new_v4().to_string();
 
@@ -420,16 +1112,19 @@ where
         receiver,
     );
     auth_state.insert(request_id.clone(), pending);
+    emit_auth_browser_opened(app, &request_id, &url);
 
     Ok(OAuthStartResponse {
         request_id,
         url,
         redirect_uri,
         user_code: None,
+        state: Some(state),
     })
 }
 
 async fn wait_for_pkce_callback(
+    app: &tauri::AppHandle,
     auth_state: &AuthState,
     request_id: &str,
     timeout_ms: Option<u64>,
@@ -441,6 +1136,7 @@ async fn wait_for_pkce_callback(
         .take_receiver()
         .ok_or_else(|| "OAuth flow is already waiting for completion".to_string())?;
     let timeout_ms = timeout_ms.unwrap_or(DEFAULT_OAUTH_TIMEOUT_MS).max(1);
+    emit_auth_progress(app, "auth:waiting", request_id);
 
     let callback = match tokio::time::timeout(Duration::from_millis(timeout_ms), receiver).await {
         Ok(result) => match result {
@@ -456,93 +1152,254 @@ async fn wait_for_pkce_callback(
             return Err("OAuth callback timed out".to_string());
         }
     };
+    if !auth_state.verify_state(&callback.state) {
+        auth_state.remove(request_id);
+        return Err("OAuth state signature mismatch".to_string());
+    }
+    emit_auth_progress(app, "auth:received", request_id);
+
+    Ok((pending, callback))
+}
+
+fn persist_oauth_credentials(
+    app: &tauri::AppHandle,
+    store: &AccountStore,
+    auth_state: &AuthState,
+    request_id: &str,
+    account_id: &str,
+    credentials: &serde_json::Value,
+) -> Result<(), String> {
+    if let Err(err) = secrets::set_account_credentials(app, store, account_id, credentials) {
+        auth_state.remove(request_id);
+        return Err(err.to_string());
+    }
+
+    auth_state.remove(request_id);
+    Ok(())
+}
+
+fn emit_auth_browser_opened(app: &tauri::AppHandle, request_id: &str, url: &str) {
+    let _ = app.emit(
+        "auth:browser-opened",
+        AuthBrowserOpenedEvent {
+            request_id: request_id.to_string(),
+            url: url.to_string(),
+        },
+    );
+}
+
+fn emit_auth_progress(app: &tauri::AppHandle, event: &str, request_id: &str) {
+    let _ = app.emit(
+        event,
+        AuthProgressEvent {
+            request_id: request_id.to_string(),
+        },
+    );
+}
+
+fn emit_auth_completed(
+    app: &tauri::AppHandle,
+    provider_id: &str,
+    request_id: &str,
+    account_id: &str,
+    correlation_id: &str,
+) {
+    let _ = app.emit(
+        "auth:completed",
+        AuthCompletedEvent {
+            provider_id: provider_id.to_string(),
+            request_id: request_id.to_string(),
+            account_id: account_id.to_string(),
+            correlation_id: correlation_id.to_string(),
+        },
+    );
+}
+
+fn persist_opencode_workspace_setting(
+    store: &AccountStore,
+    account_id: &str,
+    workspace_id: &str,
+) -> Result<(), String> {
+    let workspace_id = workspace_id.trim();
+    if workspace_id.is_empty() {
+        return Ok(());
+    }
+
+    let account = store
+        .get_account(account_id)
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| "Account not found".to_string())?;
+
+    if account.provider_id != "opencode" {
+        return Ok(());
+    }
+
+    let mut settings = account
+        .settings
+        .as_object()
+        .cloned()
+        .unwrap_or_else(serde_json::Map::new);
+
+    if !clients::opencode::remember_workspace(&mut settings, workspace_id) {
+        return Ok(());
+    }
+
+    store
+        .update_account(
+            account_id,
+            UpdateAccountInput::default().with_settings(serde_json::Value::Object(settings)),
+        )
+        .map_err(|err| err.to_string())?;
+
+    Ok(())
+}
+
+fn get_opencode_account(store: &AccountStore, account_id: &str) -> Result<AccountRecord, String> {
+    let account = store
+        .get_account(account_id)
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| "Account not found".to_string())?;
+    if account.provider_id != "opencode" {
+        return Err(format!(
+            "Account '{}' is not an OpenCode account",
+            account.id
+        ));
+    }
+    Ok(account)
+}
+
+#[tauri::command]
+fn list_opencode_workspaces(
+    store: State<'_, AccountStore>,
+    account_id: String,
+) -> Result<Vec<String>, String> {
+    let account = get_opencode_account(store.inner(), &account_id)?;
+    Ok(clients::opencode::workspace_ids(&account.settings))
+}
+
+#[tauri::command]
+fn set_opencode_active_workspace(
+    store: State<'_, AccountStore>,
+    account_id: String,
+    workspace_id: String,
+) -> Result<(), String> {
+    let account = get_opencode_account(store.inner(), &account_id)?;
+    let workspace_id = clients::opencode::normalize_workspace_id(Some(&workspace_id))
+        .ok_or_else(|| "workspaceId is invalid".to_string())?;
+    if !clients::opencode::workspace_ids(&account.settings).contains(&workspace_id) {
+        return Err(format!(
+            "Workspace '{}' is not known for this account",
+            workspace_id
+        ));
+    }
 
-    Ok((pending, callback))
+    persist_opencode_workspace_setting(store.inner(), &account.id, &workspace_id)?;
+    log::info!(
+        "[opencode] active workspace set account={} workspace_id={}",
+        account.id,
+        workspace_id
+    );
+    Ok(())
 }
 
-fn persist_oauth_credentials(
+fn load_provider_credentials<T: serde::de::DeserializeOwned>(
     app: &tauri::AppHandle,
     store: &AccountStore,
-    auth_state: &AuthState,
-    request_id: &str,
     account_id: &str,
-    credentials: &serde_json::Value,
-) -> Result<(), String> {
-    if let Err(err) = secrets::set_account_credentials(app, store, account_id, credentials) {
-        auth_state.remove(request_id);
-        return Err(err.to_string());
-    }
-
-    auth_state.remove(request_id);
-    Ok(())
+    provider_label: &str,
+) -> Result<T, String> {
+    let credentials = secrets::get_account_credentials(app, store, account_id)
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("{provider_label} account has no credentials"))?;
+    serde_json::from_value(credentials)
+        .map_err(|err| format!("Invalid {provider_label} credentials: {err}"))
 }
 
-fn persist_opencode_workspace_setting(
+fn replace_account_settings(
     store: &AccountStore,
     account_id: &str,
-    workspace_id: &str,
+    settings: serde_json::Map<String, serde_json::Value>,
 ) -> Result<(), String> {
-    let workspace_id = workspace_id.trim();
-    if workspace_id.is_empty() {
-        return Ok(());
-    }
-
-    let account = store
-        .get_account(account_id)
-        .map_err(|err| err.to_string())?
-        .ok_or_else(|| "Account not found".to_string())?;
+    store
+        .update_account(
+            account_id,
+            UpdateAccountInput::default().with_settings(serde_json::Value::Object(settings)),
+        )
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
 
-    if account.provider_id != "opencode" {
-        return Ok(());
+#[tauri::command]
+async fn list_codex_organizations(
+    app: tauri::AppHandle,
+    store: State<'_, AccountStore>,
+    account_id: String,
+) -> Result<Vec<clients::codex::CodexOrganization>, String> {
+    let account = ensure_oauth_account(store.inner(), &account_id, "codex", "Codex")?;
+    let mut credentials: clients::codex::CodexCredentials =
+        load_provider_credentials(&app, store.inner(), &account.id, "Codex")?;
+    if credentials.is_expired() {
+        probe::refresh_account_credentials(&app, store.inner(), &account.id)
+            .await
+            .map_err(|err| err.to_string())?;
+        credentials = load_provider_credentials(&app, store.inner(), &account.id, "Codex")?;
     }
 
+    clients::codex::fetch_organizations(&credentials.access_token)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+fn set_codex_organization(
+    store: State<'_, AccountStore>,
+    account_id: String,
+    org_id: String,
+) -> Result<(), String> {
+    let account = ensure_oauth_account(store.inner(), &account_id, "codex", "Codex")?;
+    let org_id = org_id.trim();
     let mut settings = account
         .settings
         .as_object()
         .cloned()
         .unwrap_or_else(serde_json::Map::new);
-
-    let unchanged = settings
-        .get("workspaceId")
-        .and_then(|value| value.as_str())
-        .map(|value| value == workspace_id)
-        .unwrap_or(false);
-    if unchanged {
-        return Ok(());
+    if org_id.is_empty() {
+        settings.remove(clients::codex::ORGANIZATION_ID_SETTING);
+    } else {
+        settings.insert(
+            clients::codex::ORGANIZATION_ID_SETTING.to_string(),
+            serde_json::Value::String(org_id.to_string()),
+        );
     }
 
-    settings.insert(
-        "workspaceId".to_string(),
-        serde_json::Value::String(workspace_id.to_string()),
+    replace_account_settings(store.inner(), &account.id, settings)?;
+    log::info!(
+        "[codex] organization set account={} org_id={}",
+        account.id,
+        if org_id.is_empty() { "<token>" } else { org_id }
     );
-
-    store
-        .update_account(
-            account_id,
-            UpdateAccountInput {
-                auth_strategy_id: None,
-                label: None,
-                settings: Some(serde_json::Value::Object(settings)),
-                clear_last_error: false,
-            },
-        )
-        .map_err(|err| err.to_string())?;
-
     Ok(())
 }
 
+#[tauri::command]
+fn infer_zai_region(api_key: String) -> Option<String> {
+    clients::zai::infer_region_from_key(&api_key).map(str::to_string)
+}
+
 #[tauri::command]
 fn start_codex_oauth(
+    app: tauri::AppHandle,
     store: State<'_, AccountStore>,
     auth_state: State<'_, AuthState>,
     account_id: String,
 ) -> Result<OAuthStartResponse, String> {
     let _account = ensure_oauth_account(store.inner(), &account_id, "codex", "Codex")?;
     start_pkce_oauth_flow(
+        &app,
         auth_state.inner(),
         account_id,
         "/auth/callback",
-        Some(1455),
+        CallbackMode::LocalServer { port: Some(1455) },
         |redirect_uri, challenge, state| {
             clients::codex::build_authorize_url(redirect_uri, challenge, state)
                 .map_err(|err| err.to_string())
@@ -555,11 +1412,18 @@ async fn finish_codex_oauth(
     app: tauri::AppHandle,
     store: State<'_, AccountStore>,
     auth_state: State<'_, AuthState>,
+    settings: State<'_, SettingsStore>,
     request_id: String,
     timeout_ms: Option<u64>,
 ) -> Result<OAuthResult, String> {
+    let timeout_ms = timeout_ms.unwrap_or_else(|| oauth_timeout_ms(&settings));
     let (pending, callback) =
-        wait_for_pkce_callback(auth_state.inner(), &request_id, timeout_ms).await?;
+        wait_for_pkce_callback(&app, auth_state.inner(), &request_id, Some(timeout_ms)).await?;
+    log::info!(
+        "[codex] finish_oauth correlation={} account={}",
+        callback.correlation_id,
+        pending.account_id
+    );
 
     let credentials = match clients::codex::exchange_code(
         &callback.code,
@@ -570,6 +1434,12 @@ async fn finish_codex_oauth(
     {
         Ok(credentials) => credentials,
         Err(err) => {
+            log::warn!(
+                "[codex] finish_oauth token exchange failed correlation={} account={} error={}",
+                callback.correlation_id,
+                pending.account_id,
+                err
+            );
             auth_state.remove(&request_id);
             return Err(err.to_string());
         }
@@ -586,6 +1456,19 @@ async fn finish_codex_oauth(
         &credentials_value,
     )?;
 
+    log::info!(
+        "[codex] finish_oauth completed correlation={} account={}",
+        callback.correlation_id,
+        pending.account_id
+    );
+    emit_auth_completed(
+        &app,
+        "codex",
+        &request_id,
+        &pending.account_id,
+        &callback.correlation_id,
+    );
+
     Ok(OAuthResult {
         account_id: pending.account_id.clone(),
         expires_at: credentials.expires_at,
@@ -599,16 +1482,18 @@ fn cancel_codex_oauth(auth_state: State<'_, AuthState>, request_id: String) -> b
 
 #[tauri::command]
 fn start_antigravity_oauth(
+    app: tauri::AppHandle,
     store: State<'_, AccountStore>,
     auth_state: State<'_, AuthState>,
     account_id: String,
 ) -> Result<OAuthStartResponse, String> {
     let _account = ensure_oauth_account(store.inner(), &account_id, "antigravity", "Antigravity")?;
     start_pkce_oauth_flow(
+        &app,
         auth_state.inner(),
         account_id,
         "/auth/callback",
-        None,
+        CallbackMode::LocalServer { port: None },
         |redirect_uri, challenge, state| {
             clients::antigravity::build_authorize_url(redirect_uri, challenge, state)
                 .map_err(|err| err.to_string())
@@ -621,11 +1506,18 @@ async fn finish_antigravity_oauth(
     app: tauri::AppHandle,
     store: State<'_, AccountStore>,
     auth_state: State<'_, AuthState>,
+    settings: State<'_, SettingsStore>,
     request_id: String,
     timeout_ms: Option<u64>,
 ) -> Result<OAuthResult, String> {
+    let timeout_ms = timeout_ms.unwrap_or_else(|| oauth_timeout_ms(&settings));
     let (pending, callback) =
-        wait_for_pkce_callback(auth_state.inner(), &request_id, timeout_ms).await?;
+        wait_for_pkce_callback(&app, auth_state.inner(), &request_id, Some(timeout_ms)).await?;
+    log::info!(
+        "[antigravity] finish_oauth correlation={} account={}",
+        callback.correlation_id,
+        pending.account_id
+    );
 
     let credentials = match clients::antigravity::exchange_code(
         &callback.code,
@@ -636,6 +1528,12 @@ async fn finish_antigravity_oauth(
     {
         Ok(credentials) => credentials,
         Err(err) => {
+            log::warn!(
+                "[antigravity] finish_oauth token exchange failed correlation={} account={} error={}",
+                callback.correlation_id,
+                pending.account_id,
+                err
+            );
             auth_state.remove(&request_id);
             return Err(err.to_string());
         }
@@ -652,12 +1550,121 @@ async fn finish_antigravity_oauth(
         &credentials_value,
     )?;
 
+    log::info!(
+        "[antigravity] finish_oauth completed correlation={} account={}",
+        callback.correlation_id,
+        pending.account_id
+    );
+    emit_auth_completed(
+        &app,
+        "antigravity",
+        &request_id,
+        &pending.account_id,
+        &callback.correlation_id,
+    );
+
     Ok(OAuthResult {
         account_id: pending.account_id.clone(),
         expires_at: credentials.expires_at,
     })
 }
 
+#[tauri::command]
+async fn list_antigravity_projects(
+    app: tauri::AppHandle,
+    store: State<'_, AccountStore>,
+    account_id: String,
+) -> Result<Vec<String>, String> {
+    let account = ensure_oauth_account(store.inner(), &account_id, "antigravity", "Antigravity")?;
+    let mut credentials: clients::antigravity::AntigravityCredentials =
+        load_provider_credentials(&app, store.inner(), &account.id, "Antigravity")?;
+    if credentials.access_token.trim().is_empty() || credentials.is_expired() {
+        probe::refresh_account_credentials(&app, store.inner(), &account.id)
+            .await
+            .map_err(|err| err.to_string())?;
+        credentials = load_provider_credentials(&app, store.inner(), &account.id, "Antigravity")?;
+    }
+
+    let mut projects = clients::antigravity::list_projects(&credentials.access_token)
+        .await
+        .map_err(|err| err.to_string())?;
+    for project in [&credentials.managed_project_id, &credentials.project_id]
+        .into_iter()
+        .flatten()
+    {
+        let project = project.trim();
+        if !project.is_empty() && !projects.iter().any(|known| known == project) {
+            projects.push(project.to_string());
+        }
+    }
+    Ok(projects)
+}
+
+#[tauri::command]
+fn set_antigravity_preferred_project(
+    store: State<'_, AccountStore>,
+    account_id: String,
+    project_id: String,
+) -> Result<(), String> {
+    let account = ensure_oauth_account(store.inner(), &account_id, "antigravity", "Antigravity")?;
+    let project_id = project_id.trim();
+    let mut settings = account
+        .settings
+        .as_object()
+        .cloned()
+        .unwrap_or_else(serde_json::Map::new);
+    if project_id.is_empty() {
+        settings.remove(clients::antigravity::PREFERRED_PROJECT_ID_SETTING);
+    } else {
+        settings.insert(
+            clients::antigravity::PREFERRED_PROJECT_ID_SETTING.to_string(),
+            serde_json::Value::String(project_id.to_string()),
+        );
+    }
+
+    replace_account_settings(store.inner(), &account.id, settings)?;
+    log::info!(
+        "[antigravity] preferred project set account={} project_id={}",
+        account.id,
+        if project_id.is_empty() {
+            "<auto>"
+        } else {
+            project_id
+        }
+    );
+    Ok(())
+}
+
+#[tauri::command]
+fn update_antigravity_model_filter(
+    store: State<'_, AccountStore>,
+    account_id: String,
+    whitelist: Vec<String>,
+    blacklist: Vec<String>,
+) -> Result<(), String> {
+    let account = ensure_oauth_account(store.inner(), &account_id, "antigravity", "Antigravity")?;
+    let mut settings = account
+        .settings
+        .as_object()
+        .cloned()
+        .unwrap_or_else(serde_json::Map::new);
+    for (key, ids) in [
+        (clients::antigravity::MODEL_WHITELIST_SETTING, whitelist),
+        (clients::antigravity::MODEL_BLACKLIST_SETTING, blacklist),
+    ] {
+        let ids = clients::antigravity::normalize_model_ids(&ids);
+        if ids.is_empty() {
+            settings.remove(key);
+        } else {
+            settings.insert(key.to_string(), serde_json::Value::from(ids));
+        }
+    }
+
+    replace_account_settings(store.inner(), &account.id, settings)?;
+    log::info!("[antigravity] model filter updated account={}", account.id);
+    Ok(())
+}
+
 #[tauri::command]
 fn cancel_antigravity_oauth(auth_state: State<'_, AuthState>, request_id: String) -> bool {
     auth_state.cancel(&request_id)
@@ -665,16 +1672,18 @@ fn cancel_antigravity_oauth(auth_state: State<'_, AuthState>, request_id: String
 
 #[tauri::command]
 fn start_claude_oauth(
+    app: tauri::AppHandle,
     store: State<'_, AccountStore>,
     auth_state: State<'_, AuthState>,
     account_id: String,
 ) -> Result<OAuthStartResponse, String> {
     let _account = ensure_oauth_account(store.inner(), &account_id, "claude", "Claude")?;
     start_pkce_oauth_flow(
+        &app,
         auth_state.inner(),
         account_id,
         "/callback",
-        None,
+        CallbackMode::LocalServer { port: None },
         |redirect_uri, challenge, state| {
             clients::claude::build_authorize_url(redirect_uri, challenge, state)
                 .map_err(|err| err.to_string())
@@ -687,11 +1696,18 @@ async fn finish_claude_oauth(
     app: tauri::AppHandle,
     store: State<'_, AccountStore>,
     auth_state: State<'_, AuthState>,
+    settings: State<'_, SettingsStore>,
     request_id: String,
     timeout_ms: Option<u64>,
 ) -> Result<OAuthResult, String> {
+    let timeout_ms = timeout_ms.unwrap_or_else(|| oauth_timeout_ms(&settings));
     let (pending, callback) =
-        wait_for_pkce_callback(auth_state.inner(), &request_id, timeout_ms).await?;
+        wait_for_pkce_callback(&app, auth_state.inner(), &request_id, Some(timeout_ms)).await?;
+    log::info!(
+        "[claude] finish_oauth correlation={} account={}",
+        callback.correlation_id,
+        pending.account_id
+    );
 
     let credentials = match clients::claude::exchange_code(
         &callback.code,
@@ -703,6 +1719,12 @@ async fn finish_claude_oauth(
     {
         Ok(credentials) => credentials,
         Err(err) => {
+            log::warn!(
+                "[claude] finish_oauth token exchange failed correlation={} account={} error={}",
+                callback.correlation_id,
+                pending.account_id,
+                err
+            );
             auth_state.remove(&request_id);
             return Err(err.to_string());
         }
@@ -719,6 +1741,19 @@ async fn finish_claude_oauth(
         &credentials_value,
     )?;
 
+    log::info!(
+        "[claude] finish_oauth completed correlation={} account={}",
+        callback.correlation_id,
+        pending.account_id
+    );
+    emit_auth_completed(
+        &app,
+        "claude",
+        &request_id,
+        &pending.account_id,
+        &callback.correlation_id,
+    );
+
     Ok(OAuthResult {
         account_id: pending.account_id.clone(),
         expires_at: credentials.expires_at,
@@ -732,6 +1767,7 @@ fn cancel_claude_oauth(auth_state: State<'_, AuthState>, request_id: String) ->
 
 #[tauri::command]
 async fn start_copilot_oauth(
+    app: tauri::AppHandle,
     store: State<'_, AccountStore>,
     auth_state: State<'_, AuthState>,
     account_id: String,
@@ -742,7 +1778,9 @@ async fn start_copilot_oauth(
         .await
         .map_err(|err| err.to_string())?;
     let request_id = Uuid::new_v4().to_string();
-    let expires_at = now_unix_ms().saturating_add(device_response.expires_in.saturating_mul(1000));
+    let expires_at = auth_state
+        .now_unix_ms()
+        .saturating_add(device_response.expires_in.saturating_mul(1000));
 
     let pending = PendingOAuth::new_device_flow(
         account_id,
@@ -757,12 +1795,14 @@ async fn start_copilot_oauth(
         .verification_uri_complete
         .clone()
         .unwrap_or_else(|| redirect_uri.clone());
+    emit_auth_browser_opened(&app, &request_id, &url);
 
     Ok(OAuthStartResponse {
         request_id,
         url,
         redirect_uri,
         user_code: Some(device_response.user_code),
+        state: None,
     })
 }
 
@@ -771,6 +1811,7 @@ async fn finish_copilot_oauth(
     app: tauri::AppHandle,
     store: State<'_, AccountStore>,
     auth_state: State<'_, AuthState>,
+    settings: State<'_, SettingsStore>,
     request_id: String,
     timeout_ms: Option<u64>,
 ) -> Result<OAuthResult, String> {
@@ -783,10 +1824,18 @@ async fn finish_copilot_oauth(
         .clone()
         .ok_or_else(|| "OAuth flow not found".to_string())?;
     let interval = pending.device_interval.unwrap_or(5).max(1);
-    let mut timeout_ms = timeout_ms.unwrap_or(DEFAULT_OAUTH_TIMEOUT_MS).max(1);
+    let mut timeout_ms = timeout_ms
+        .unwrap_or_else(|| oauth_timeout_ms(&settings))
+        .max(1);
+    let correlation_id = Uuid::new_v4().to_string();
+    log::info!(
+        "[copilot] finish_oauth correlation={} account={}",
+        correlation_id,
+        pending.account_id
+    );
 
     if let Some(expires_at) = pending.device_expires_at {
-        let remaining = expires_at.saturating_sub(now_unix_ms());
+        let remaining = expires_at.saturating_sub(auth_state.now_unix_ms());
         if remaining <= 0 {
             auth_state.remove(&request_id);
             return Err("OAuth device code expired".to_string());
@@ -794,19 +1843,43 @@ async fn finish_copilot_oauth(
         timeout_ms = timeout_ms.min(remaining as u64);
     }
 
-    let poll_future =
-        clients::copilot::poll_for_token(&device_code, interval, Some(&pending.cancel_flag));
+    emit_auth_progress(&app, "auth:waiting", &request_id);
+    let poll_future = clients::copilot::poll_for_token(
+        &device_code,
+        interval,
+        Some(&pending.cancel_flag),
+        |attempt| {
+            let _ = app.emit(
+                "auth:polling",
+                AuthPollingEvent {
+                    request_id: request_id.clone(),
+                    attempt,
+                },
+            );
+        },
+    );
 
     let credentials =
         match tokio::time::timeout(Duration::from_millis(timeout_ms), poll_future).await {
             Ok(result) => match result {
                 Ok(credentials) => credentials,
                 Err(err) => {
+                    log::warn!(
+                        "[copilot] finish_oauth poll failed correlation={} account={} error={}",
+                        correlation_id,
+                        pending.account_id,
+                        err
+                    );
                     auth_state.remove(&request_id);
                     return Err(err.to_string());
                 }
             },
             Err(_) => {
+                log::warn!(
+                    "[copilot] finish_oauth timed out correlation={} account={}",
+                    correlation_id,
+                    pending.account_id
+                );
                 pending.cancel_flag.store(true, Ordering::SeqCst);
                 auth_state.remove(&request_id);
                 return Err("OAuth callback timed out".to_string());
@@ -824,6 +1897,19 @@ async fn finish_copilot_oauth(
         &credentials_value,
     )?;
 
+    log::info!(
+        "[copilot] finish_oauth completed correlation={} account={}",
+        correlation_id,
+        pending.account_id
+    );
+    emit_auth_completed(
+        &app,
+        "copilot",
+        &request_id,
+        &pending.account_id,
+        &correlation_id,
+    );
+
     Ok(OAuthResult {
         account_id: pending.account_id.clone(),
         expires_at: credentials.expires_at.unwrap_or(0),
@@ -877,7 +1963,9 @@ async fn start_opencode_oauth(
         account_id
     );
 
-    let expires_at = now_unix_ms().saturating_add(DEFAULT_OAUTH_TIMEOUT_MS as i64);
+    let expires_at = auth_state
+        .now_unix_ms()
+        .saturating_add(DEFAULT_OAUTH_TIMEOUT_MS as i64);
     let pending = PendingOAuth::new_device_flow(account_id, window_label, 1, expires_at);
     auth_state.insert(request_id.clone(), pending);
 
@@ -886,6 +1974,7 @@ async fn start_opencode_oauth(
         url: OPENCODE_LOGIN_URL.to_string(),
         redirect_uri: OPENCODE_LOGIN_URL.to_string(),
         user_code: None,
+        state: None,
     })
 }
 
@@ -894,6 +1983,7 @@ async fn finish_opencode_oauth(
     app: tauri::AppHandle,
     store: State<'_, AccountStore>,
     auth_state: State<'_, AuthState>,
+    settings: State<'_, SettingsStore>,
     request_id: String,
     timeout_ms: Option<u64>,
 ) -> Result<OAuthResult, String> {
@@ -906,15 +1996,19 @@ async fn finish_opencode_oauth(
         .clone()
         .ok_or_else(|| "OAuth flow not found".to_string())?;
 
-    let mut timeout_ms = timeout_ms.unwrap_or(DEFAULT_OAUTH_TIMEOUT_MS).max(1);
+    let correlation_id = Uuid::new_v4().to_string();
+    let mut timeout_ms = timeout_ms
+        .unwrap_or_else(|| oauth_timeout_ms(&settings))
+        .max(1);
     if let Some(expires_at) = pending.device_expires_at {
-        let remaining = expires_at.saturating_sub(now_unix_ms());
+        let remaining = expires_at.saturating_sub(auth_state.now_unix_ms());
         if remaining <= 0 {
             auth_state.remove(&request_id);
             close_webview_window_if_exists(&app, &window_label);
             log::warn!(
-                "[opencode-auth] login flow timed out request_id={}",
-                request_id
+                "[opencode-auth] login flow timed out request_id={} correlation={}",
+                request_id,
+                correlation_id
             );
             return Err("OAuth callback timed out".to_string());
         }
@@ -922,12 +2016,14 @@ async fn finish_opencode_oauth(
     }
 
     log::info!(
-        "[opencode-auth] waiting for session capture request_id={} timeout_ms={}",
+        "[opencode-auth] waiting for session capture request_id={} correlation={} account={} timeout_ms={}",
         request_id,
+        correlation_id,
+        pending.account_id,
         timeout_ms
     );
 
-    let started_at = std::time::Instant::now();
+    let started_at = now_instant();
     let mut last_url_seen: Option<String> = None;
     let mut captured_workspace_id: Option<String> = None;
     let mut logged_cookie_without_workspace = false;
@@ -938,19 +2034,21 @@ async fn finish_opencode_oauth(
             auth_state.remove(&request_id);
             close_webview_window_if_exists(&app, &window_label);
             log::warn!(
-                "[opencode-auth] login flow cancelled request_id={}",
-                request_id
+                "[opencode-auth] login flow cancelled request_id={} correlation={}",
+                request_id,
+                correlation_id
             );
             return Err("OAuth cancelled".to_string());
         }
 
-        if started_at.elapsed() >= Duration::from_millis(timeout_ms) {
+        if elapsed_ms_since(started_at) >= timeout_ms {
             pending.cancel_flag.store(true, Ordering::SeqCst);
             auth_state.remove(&request_id);
             close_webview_window_if_exists(&app, &window_label);
             log::warn!(
-                "[opencode-auth] login flow timed out request_id={}",
-                request_id
+                "[opencode-auth] login flow timed out request_id={} correlation={}",
+                request_id,
+                correlation_id
             );
             return Err("OAuth callback timed out".to_string());
         }
@@ -958,8 +2056,9 @@ async fn finish_opencode_oauth(
         let Some(window) = app.get_webview_window(&window_label) else {
             auth_state.remove(&request_id);
             log::warn!(
-                "[opencode-auth] login window closed before capture request_id={}",
-                request_id
+                "[opencode-auth] login window closed before capture request_id={} correlation={}",
+                request_id,
+                correlation_id
             );
             return Err("OpenCode login window closed before session was captured".to_string());
         };
@@ -967,7 +2066,11 @@ async fn finish_opencode_oauth(
         if let Ok(url) = window.url() {
             let sanitized = sanitize_url_for_log(&url);
             if last_url_seen.as_deref() != Some(sanitized.as_str()) {
-                log::info!("[opencode-auth] navigation {}", sanitized);
+                log::info!(
+                    "[opencode-auth] navigation correlation={} url={}",
+                    correlation_id,
+                    sanitized
+                );
                 last_url_seen = Some(sanitized);
             }
 
@@ -976,7 +2079,8 @@ async fn finish_opencode_oauth(
             if let Some(workspace_id_from_url) = workspace_id_from_url {
                 if captured_workspace_id.as_deref() != Some(workspace_id_from_url.as_str()) {
                     log::info!(
-                        "[opencode-auth] captured workspace id from redirect workspace_id={}",
+                        "[opencode-auth] captured workspace id from redirect correlation={} workspace_id={}",
+                        correlation_id,
                         workspace_id_from_url
                     );
                 }
@@ -986,30 +2090,38 @@ async fn finish_opencode_oauth(
 
         let workspace_id_for_credentials = captured_workspace_id.clone();
 
-        let cookie_header = opencode_cookie_header_from_window(&window)?;
-        if cookie_header.is_some()
+        let cookie = opencode_cookie_header_from_window(&window)?;
+        if cookie.is_some()
             && workspace_id_for_credentials.is_none()
             && !logged_cookie_without_workspace
         {
-            log::info!("[opencode-auth] auth cookie detected, waiting for workspace redirect");
+            log::info!(
+                "[opencode-auth] auth cookie detected, waiting for workspace redirect correlation={}",
+                correlation_id
+            );
             logged_cookie_without_workspace = true;
         }
 
         if workspace_id_for_credentials.is_some()
-            && cookie_header.is_none()
+            && cookie.is_none()
             && !logged_workspace_without_cookie
         {
-            log::info!("[opencode-auth] workspace URL detected, waiting for auth cookie");
+            log::info!(
+                "[opencode-auth] workspace URL detected, waiting for auth cookie correlation={}",
+                correlation_id
+            );
             logged_workspace_without_cookie = true;
         }
 
-        if let (Some(cookie_header), Some(workspace_id)) =
-            (cookie_header, workspace_id_for_credentials)
+        if let (Some((cookie_header, expires_at)), Some(workspace_id)) =
+            (cookie, workspace_id_for_credentials)
         {
             let workspace_id_for_log = workspace_id.clone();
             let credentials = clients::opencode::OpenCodeCredentials {
                 kind: Some("cookie".to_string()),
                 cookie_header,
+                workspace_id: Some(workspace_id),
+                expires_at,
             };
             let credentials_value =
                 serde_json::to_value(credentials.with_kind()).map_err(|err| err.to_string())?;
@@ -1028,17 +2140,29 @@ async fn finish_opencode_oauth(
                 &pending.account_id,
                 &workspace_id_for_log,
             )?;
+            let _ = store.set_credentials_expires_at(
+                &pending.account_id,
+                expires_at.and_then(unix_ms_to_rfc3339),
+            );
 
             close_webview_window_if_exists(&app, &window_label);
             log::info!(
-                "[opencode-auth] session captured request_id={} account_id={} workspace_id={}",
+                "[opencode-auth] session captured request_id={} correlation={} account_id={} workspace_id={}",
                 request_id,
+                correlation_id,
                 pending.account_id,
                 workspace_id_for_log
             );
+            emit_auth_completed(
+                &app,
+                "opencode",
+                &request_id,
+                &pending.account_id,
+                &correlation_id,
+            );
             return Ok(OAuthResult {
                 account_id: pending.account_id.clone(),
-                expires_at: 0,
+                expires_at: expires_at.unwrap_or(0),
             });
         }
 
@@ -1070,6 +2194,82 @@ fn cancel_opencode_oauth(
     cancelled
 }
 
+/// Re-opens the OpenCode login window for an existing cookie account and
+/// waits for the new session, replacing the stored cookie on success.
+#[tauri::command]
+async fn refresh_opencode_session(
+    app: tauri::AppHandle,
+    store: State<'_, AccountStore>,
+    auth_state: State<'_, AuthState>,
+    settings: State<'_, SettingsStore>,
+    account_id: String,
+    timeout_ms: Option<u64>,
+) -> Result<OAuthResult, String> {
+    let started = start_opencode_oauth(
+        app.clone(),
+        store.clone(),
+        auth_state.clone(),
+        account_id.clone(),
+    )
+    .await?;
+    log::info!(
+        "[opencode-auth] session refresh started request_id={} account_id={}",
+        started.request_id,
+        account_id
+    );
+    finish_opencode_oauth(
+        app,
+        store,
+        auth_state,
+        settings,
+        started.request_id,
+        timeout_ms,
+    )
+    .await
+}
+
+#[tauri::command]
+fn cancel_opencode_session_refresh(
+    app: tauri::AppHandle,
+    auth_state: State<'_, AuthState>,
+    account_id: String,
+) -> bool {
+    auth_state
+        .request_ids_for_account(&account_id)
+        .into_iter()
+        .fold(false, |cancelled, request_id| {
+            cancel_opencode_oauth(app.clone(), auth_state.clone(), request_id) || cancelled
+        })
+}
+
+/// Cancels every pending OAuth flow and closes any OpenCode login windows.
+fn cancel_all_flows(app: &tauri::AppHandle, auth_state: &AuthState) -> usize {
+    let flows = auth_state.cancel_all();
+    for flow in &flows {
+        if let Some(label) = flow
+            .device_code
+            .as_deref()
+            .filter(|label| label.starts_with("opencode-auth-"))
+        {
+            close_webview_window_if_exists(app, label);
+        }
+    }
+    if !flows.is_empty() {
+        log::info!("[oauth] cancelled all flows count={}", flows.len());
+    }
+    flows.len()
+}
+
+#[tauri::command]
+fn cancel_all_oauth_flows(app: tauri::AppHandle, auth_state: State<'_, AuthState>) -> usize {
+    cancel_all_flows(&app, auth_state.inner())
+}
+
+#[tauri::command]
+fn list_active_oauth_flows(auth_state: State<'_, AuthState>) -> Vec<auth::OAuthFlowSummary> {
+    auth_state.summaries()
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let context = tauri::generate_context!();
@@ -1082,21 +2282,35 @@ pub fn run() {
         .plugin(tauri_plugin_keyring::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_nspanel::init())
-        .plugin(
-            tauri_plugin_log::Builder::new()
-                .targets([
-                    Target::new(TargetKind::Stdout),
-                    Target::new(TargetKind::LogDir { file_name: None }),
-                ])
-                .max_file_size(10_000_000)
-                .level(log::LevelFilter::Info)
-                .level_for("hyper", log::LevelFilter::Warn)
-                .level_for("reqwest", log::LevelFilter::Warn)
-                .build(),
-        )
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(shortcut::plugin())
         .setup(|app| {
+            // The stored level is read before the logger exists so startup
+            // logging already honours it.
+            let settings = SettingsStore::load(app.handle())
+                .map_err(|err| -> Box<dyn std::error::Error> { Box::new(err) })?;
+            let log_level = settings.log_level();
+            app.handle().plugin(
+                tauri_plugin_log::Builder::new()
+                    .targets([
+                        Target::new(TargetKind::Stdout),
+                        Target::new(TargetKind::LogDir { file_name: None }),
+                    ])
+                    .max_file_size(10_000_000)
+                    // Let every record through the dispatch; `log::max_level`
+                    // is the runtime filter that `set_log_level` adjusts.
+                    .level(log::LevelFilter::Trace)
+                    .level_for("hyper", log::LevelFilter::Warn)
+                    .level_for("reqwest", log::LevelFilter::Warn)
+                    .build(),
+            )?;
+            log::set_max_level(log_level);
+
+            #[cfg(feature = "mock-autostart")]
+            app.manage(autostart::MockAutostart::default());
             #[cfg(target_os = "macos")]
             app.set_activation_policy(tauri::ActivationPolicy::Accessory);
 
@@ -1108,14 +2322,97 @@ pub fn run() {
 
             let store = AccountStore::load(app.handle())
                 .map_err(|err| -> Box<dyn std::error::Error> { Box::new(err) })?;
+            secrets::init_key_version(&store)
+                .map_err(|err| -> Box<dyn std::error::Error> { Box::new(err) })?;
             app.manage(store);
+            let circuit_backoff = settings
+                .get_setting("circuitBreakerBackoffMs")
+                .ok()
+                .flatten()
+                .and_then(|value| value.as_u64())
+                .map(Duration::from_millis)
+                .unwrap_or(circuit_breaker::DEFAULT_BACKOFF);
+            app.manage(settings);
+            app.manage(CircuitBreakerState::new(circuit_backoff));
+            app.manage(ProbeHistory::default());
+            app.manage(ProbeCache::default());
+            app.manage(ThresholdTracker::default());
+            app.manage(HighUsageTracker::default());
             app.manage(AuthState::new());
 
+            let notify_handle = app.handle().clone();
+            app.listen(
+                "usage:threshold-crossed",
+                move |event| match serde_json::from_str::<probe::UsageThresholdEvent>(
+                    event.payload(),
+                ) {
+                    Ok(payload) => notifications::notify_usage_threshold(&notify_handle, &payload),
+                    Err(err) => log::warn!("[notifications] invalid threshold payload: {}", err),
+                },
+            );
+
+            let tray_handle = app.handle().clone();
+            app.listen("probe:batch-complete", move |_| {
+                tray::refresh_provider_status(&tray_handle);
+            });
+
+            let badge_handle = app.handle().clone();
+            app.listen("probe:result", move |event| {
+                match serde_json::from_str::<ProbeResultEvent>(event.payload()) {
+                    Ok(payload) => refresh_usage_badge(&badge_handle, &payload.output),
+                    Err(err) => log::warn!("[tray] invalid probe result payload: {}", err),
+                }
+            });
+
+            let expiry_cancel = tokio_util::sync::CancellationToken::new();
+            expiry_watch::spawn(app.handle().clone(), expiry_cancel.clone());
+            auth::spawn_stale_flow_cleanup(
+                app.handle().clone(),
+                MAX_OAUTH_TIMEOUT_MS as i64,
+                expiry_cancel.clone(),
+            );
+            app.manage(expiry_watch::ExpiryWatch(expiry_cancel));
+
             tray::create(app.handle())?;
+            shortcut::register_stored(app.handle());
+
+            let settings = app.state::<SettingsStore>();
+            if let Err(err) = autostart::with_backend(app.handle(), |backend| {
+                autostart::sync_launch_on_login(backend, &settings)
+            }) {
+                log::warn!("[autostart] failed to read launch on login: {}", err);
+            }
+            #[cfg(target_os = "macos")]
+            if !autostart::start_minimized(&settings) {
+                panel::set_visible(app.handle(), true)?;
+            }
 
             Ok(())
+        })
+        .on_window_event(|window, event| {
+            if window.label() != "main" {
+                return;
+            }
+            match event {
+                tauri::WindowEvent::CloseRequested { .. } => {
+                    let app = window.app_handle();
+                    if let Some(auth_state) = app.try_state::<AuthState>() {
+                        cancel_all_flows(app, auth_state.inner());
+                    }
+                }
+                tauri::WindowEvent::Destroyed => shortcut::unregister_all(window.app_handle()),
+                _ => {}
+            }
         });
 
+    #[cfg(not(feature = "mock-autostart"))]
+    {
+        builder = builder.plugin(tauri_plugin_autostart::init(
+            tauri_plugin_autostart::MacosLauncher::LaunchAgent,
+            None,
+        ));
+    }
+
     if has_updater_config {
         builder = builder.plugin(tauri_plugin_updater::Builder::new().build());
     }
@@ -1125,23 +2422,71 @@ pub fn run() {
             greet,
             init_panel,
             hide_panel,
+            show_panel,
+            toggle_panel,
+            set_panel_auto_hide,
+            set_panel_shortcut,
+            set_launch_on_login,
+            get_launch_on_login,
+            set_log_level,
+            get_log_level,
+            get_log_dir,
+            export_log_file,
             list_providers_meta,
             start_provider_probe_batch,
+            probe_single_account,
             list_providers,
+            list_provider_aliases,
+            validate_provider_id,
+            validate_strategy_id,
+            validate_account_label,
+            validate_provider_settings,
             list_accounts,
+            list_accounts_with_credential_status,
+            get_account_with_credential_status,
+            get_account_last_error,
             get_account,
             create_account,
             update_account,
+            set_account_disabled,
+            update_account_tags,
+            update_account_notes,
+            list_accounts_by_tag,
+            reorder_accounts,
+            move_account_before,
+            set_account_icon,
             delete_account,
+            list_deleted_accounts,
+            restore_account,
+            purge_account,
+            purge_accounts_older_than,
+            reset_circuit_breaker,
+            get_account_probe_history,
+            get_setting,
+            set_setting,
+            export_accounts,
+            import_accounts,
             set_account_credentials,
+            set_copilot_pat,
             has_account_credentials,
+            validate_account_credentials,
+            refresh_account_credentials,
+            rotate_master_key,
+            submit_master_key_passphrase,
             clear_account_credentials,
+            clear_probe_cache,
             start_codex_oauth,
             finish_codex_oauth,
             cancel_codex_oauth,
+            list_codex_organizations,
+            infer_zai_region,
+            set_codex_organization,
             start_antigravity_oauth,
             finish_antigravity_oauth,
             cancel_antigravity_oauth,
+            list_antigravity_projects,
+            set_antigravity_preferred_project,
+            update_antigravity_model_filter,
             start_claude_oauth,
             finish_claude_oauth,
             cancel_claude_oauth,
@@ -1150,8 +2495,25 @@ pub fn run() {
             cancel_copilot_oauth,
             start_opencode_oauth,
             finish_opencode_oauth,
-            cancel_opencode_oauth
+            cancel_opencode_oauth,
+            list_opencode_workspaces,
+            set_opencode_active_workspace,
+            refresh_opencode_session,
+            cancel_opencode_session_refresh,
+            cancel_all_oauth_flows,
+            list_active_oauth_flows
         ])
-        .run(context)
-        .expect("error while running tauri application");
+        .build(context)
+        .expect("error while building tauri application")
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                if let Some(watch) = app_handle.try_state::<expiry_watch::ExpiryWatch>() {
+                    watch.0.cancel();
+                }
+                if let Some(auth_state) = app_handle.try_state::<AuthState>() {
+                    cancel_all_flows(app_handle, auth_state.inner());
+                }
+                shortcut::unregister_all(app_handle);
+            }
+        });
 }
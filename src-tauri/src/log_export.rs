@@ -0,0 +1,121 @@
+//! Sanitized export of the backend log file for bug reports.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::{Captures, Regex};
+use tauri::{AppHandle, Manager};
+
+use crate::error::{BackendError, Result};
+use crate::settings_store::SettingsStore;
+use crate::utils::mask_workspace_id;
+
+const LOG_EXPORT_ENABLED_SETTING: &str = "logExportEnabled";
+
+pub fn log_dir(app: &AppHandle) -> Result<PathBuf> {
+    app.path()
+        .app_log_dir()
+        .map_err(|err| BackendError::Path(err.to_string()))
+}
+
+/// Debug builds can always export; release builds need `logExportEnabled`.
+fn export_allowed(settings: &SettingsStore) -> bool {
+    cfg!(debug_assertions)
+        || settings
+            .get_setting(LOG_EXPORT_ENABLED_SETTING)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false)
+}
+
+/// Contents of the most recently written log file with secrets redacted.
+pub fn export_log_file(app: &AppHandle, settings: &SettingsStore) -> Result<String> {
+    if !export_allowed(settings) {
+        return Err(BackendError::Validation(format!(
+            "log export is disabled; set {LOG_EXPORT_ENABLED_SETTING} to enable it"
+        )));
+    }
+    let dir = log_dir(app)?;
+    let path = latest_log_file(&dir)?
+        .ok_or_else(|| BackendError::Path(format!("no log file in {}", dir.display())))?;
+    let bytes = fs::read(&path)?;
+    log::info!("[logs] exporting log file path={}", path.display());
+    Ok(redact_log(&String::from_utf8_lossy(&bytes)))
+}
+
+fn latest_log_file(dir: &Path) -> Result<Option<PathBuf>> {
+    let mut latest = None;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("log") {
+            continue;
+        }
+        let modified = entry.metadata()?.modified()?;
+        let is_newer = match &latest {
+            Some((latest_modified, _)) => modified > *latest_modified,
+            None => true,
+        };
+        if is_newer {
+            latest = Some((modified, path));
+        }
+    }
+    Ok(latest.map(|(_, path)| path))
+}
+
+fn redact_log(contents: &str) -> String {
+    let redacted = bearer_regex().replace_all(contents, "Bearer [REDACTED]");
+    let redacted = access_token_regex().replace_all(&redacted, "access_token=[REDACTED]");
+    let redacted = cookie_regex().replace_all(&redacted, "Cookie: [REDACTED]");
+    workspace_id_regex()
+        .replace_all(&redacted, |captures: &Captures| {
+            mask_workspace_id(&captures[0])
+        })
+        .into_owned()
+}
+
+fn bearer_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"Bearer [^ ]+").expect("bearer regex should compile"))
+}
+
+fn access_token_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| {
+        Regex::new(r"access_token=[^ &]+").expect("access token regex should compile")
+    })
+}
+
+fn cookie_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r"Cookie: [^\r\n]+").expect("cookie regex should compile"))
+}
+
+fn workspace_id_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX
+        .get_or_init(|| Regex::new(r"wrk_[A-Za-z0-9]{6,}").expect("workspace regex should compile"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact_log_masks_tokens_cookies_and_workspace_ids() {
+        let contents = "GET /usage Authorization: Bearer sk-live-123 ok\n\
+            callback?access_token=abc.def&state=xyz\n\
+            Cookie: auth=secret; theme=dark\r\n\
+            [opencode] workspace=wrk_abcdef123456 short=wrk_abc\n";
+
+        assert_eq!(
+            redact_log(contents),
+            "GET /usage Authorization: Bearer [REDACTED] ok\n\
+            callback?access_token=[REDACTED]&state=xyz\n\
+            Cookie: [REDACTED]\r\n\
+            [opencode] workspace=***123456 short=wrk_abc\n"
+        );
+    }
+}
@@ -2,6 +2,10 @@ use serde::{Deserialize, Serialize};
 
 const MIN_ID_LEN: usize = 2;
 const MAX_ID_LEN: usize = 64;
+const MAX_TAG_LEN: usize = 32;
+pub const MAX_NOTES_CHARS: usize = 1000;
+const MAX_LABEL_CHARS: usize = 100;
+const MAX_ICON_OVERRIDE_LEN: usize = 64 * 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,12 +19,34 @@ pub struct AccountRecord {
     pub settings: serde_json::Value,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub credentials: Option<EncryptedCredentials>,
+    #[serde(default)]
     pub created_at: String,
     pub updated_at: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_fetch_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_error: Option<String>,
+    /// When `last_error` was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limited_until: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials_expires_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Manual position set by `reorder_accounts`; unset accounts sort last.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<i64>,
+    /// Replaces the provider icon in single-account probe output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_override: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +56,50 @@ pub struct EncryptedCredentials {
     pub key_version: u32,
     pub nonce: String,
     pub ciphertext: String,
+    /// HMAC-SHA256 over the other fields; empty for legacy blobs.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub hmac: String,
+    /// Set once by the v1 -> v2 store migration on blobs written before `hmac`
+    /// existed; only these may be read without a MAC.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub legacy: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountWithCredentialStatus {
+    pub account: AccountRecord,
+    pub has_credentials: bool,
+}
+
+impl From<AccountRecord> for AccountWithCredentialStatus {
+    fn from(account: AccountRecord) -> Self {
+        Self {
+            has_credentials: account.credentials.is_some(),
+            account,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountErrorInfo {
+    pub message: String,
+    pub occurred_at: String,
+}
+
+impl AccountErrorInfo {
+    /// Records written before `last_error_at` existed fall back to `updated_at`.
+    pub fn from_account(account: &AccountRecord) -> Option<Self> {
+        let message = account.last_error.clone()?;
+        Some(Self {
+            message,
+            occurred_at: account
+                .last_error_at
+                .clone()
+                .unwrap_or_else(|| account.updated_at.clone()),
+        })
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -44,7 +114,7 @@ pub struct CreateAccountInput {
     pub settings: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateAccountInput {
     #[serde(default)]
@@ -55,6 +125,80 @@ pub struct UpdateAccountInput {
     pub settings: Option<serde_json::Value>,
     #[serde(default)]
     pub clear_last_error: bool,
+    #[serde(default)]
+    pub disabled: Option<bool>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+/// Test-only builders; commands deserialize this input from the frontend.
+#[cfg(test)]
+impl CreateAccountInput {
+    pub fn for_provider(provider_id: impl Into<String>) -> Self {
+        Self {
+            provider_id: provider_id.into(),
+            auth_strategy_id: None,
+            label: None,
+            settings: None,
+        }
+    }
+
+    pub fn with_auth_strategy(mut self, id: impl Into<String>) -> Self {
+        self.auth_strategy_id = Some(id.into());
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_settings(mut self, settings: serde_json::Value) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+}
+
+/// Builders start from `UpdateAccountInput::default()`, which changes nothing.
+impl UpdateAccountInput {
+    pub fn with_settings(mut self, settings: serde_json::Value) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    pub fn with_disabled(mut self, disabled: bool) -> Self {
+        self.disabled = Some(disabled);
+        self
+    }
+
+    pub fn with_tags<I, T>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.tags = Some(tags.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = Some(notes.into());
+        self
+    }
+}
+
+#[cfg(test)]
+impl UpdateAccountInput {
+    pub fn with_auth_strategy(mut self, id: impl Into<String>) -> Self {
+        self.auth_strategy_id = Some(id.into());
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
 }
 
 pub fn normalize_optional_string(input: Option<String>) -> Option<String> {
@@ -89,6 +233,53 @@ pub fn is_valid_provider_id(value: &str) -> bool {
     })
 }
 
+/// Labels are 1-100 characters after trimming, without control characters.
+pub fn validate_account_label(value: &str) -> Result<(), String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err("label cannot be empty".to_string());
+    }
+    if trimmed.chars().count() > MAX_LABEL_CHARS {
+        return Err(format!(
+            "label must be at most {MAX_LABEL_CHARS} characters"
+        ));
+    }
+    if trimmed.chars().any(char::is_control) {
+        return Err("label cannot contain control characters".to_string());
+    }
+    Ok(())
+}
+
+/// Tags match `^[a-z0-9][a-z0-9-]{0,31}$`.
+pub fn is_valid_tag(value: &str) -> bool {
+    if value.is_empty() || value.len() > MAX_TAG_LEN {
+        return false;
+    }
+
+    let mut chars = value.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+
+    if !(first.is_ascii_lowercase() || first.is_ascii_digit()) {
+        return false;
+    }
+
+    chars.all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-')
+}
+
+/// An app-relative path (`/icons/work.svg`) or an inline SVG data URI
+/// (`data:image/svg+xml;base64,...` or `data:image/svg+xml,...`).
+pub fn is_valid_icon_override(value: &str) -> bool {
+    if value.len() > MAX_ICON_OVERRIDE_LEN {
+        return false;
+    }
+    if let Some(rest) = value.strip_prefix("data:image/svg+xml") {
+        return rest.starts_with(';') || rest.starts_with(',');
+    }
+    value.starts_with('/') && !value.starts_with("//")
+}
+
 pub fn is_valid_strategy_id(value: &str) -> bool {
     if value.len() < MIN_ID_LEN || value.len() > MAX_ID_LEN {
         return false;
@@ -105,3 +296,18 @@ pub fn is_valid_strategy_id(value: &str) -> bool {
 
     chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '.' || ch == '_' || ch == '-')
 }
+
+#[cfg(test)]
+mod tests {
+    use super::validate_account_label;
+
+    #[test]
+    fn account_label_validation_checks_length_and_control_characters() {
+        assert!(validate_account_label("  Work  ").is_ok());
+        assert!(validate_account_label(&"a".repeat(100)).is_ok());
+        assert!(validate_account_label("   ").is_err());
+        assert!(validate_account_label(&"a".repeat(101)).is_err());
+        assert!(validate_account_label("Work\u{7}").is_err());
+        assert!(validate_account_label("Wo\nrk").is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::{NotificationExt, PermissionState};
+
+use crate::probe::UsageThresholdEvent;
+use crate::providers;
+use crate::settings_store::SettingsStore;
+
+const NOTIFICATIONS_ENABLED_SETTING: &str = "notificationsEnabled";
+const NOTIFICATION_PERMISSION_SETTING: &str = "notificationPermission";
+
+/// Shows a native notification for a usage threshold crossing. Failures are
+/// logged and otherwise ignored; alerts are best effort.
+pub fn notify_usage_threshold(app: &AppHandle, event: &UsageThresholdEvent) {
+    let Some(settings) = app.try_state::<SettingsStore>() else {
+        return;
+    };
+    if !notifications_enabled(&settings) || !ensure_permission(app, &settings) {
+        return;
+    }
+
+    let provider = provider_name(&event.provider_id);
+    show(
+        app,
+        "Usage Alert",
+        format!("{provider}: {} at {:.0}%", event.label, event.used_percent),
+    );
+}
+
+pub fn notify_credentials_expiry(app: &AppHandle, provider_id: &str, expires_in_ms: i64) {
+    let Some(settings) = app.try_state::<SettingsStore>() else {
+        return;
+    };
+    if !notifications_enabled(&settings) || !ensure_permission(app, &settings) {
+        return;
+    }
+
+    let hours = (expires_in_ms.max(0) + 3_599_999) / 3_600_000;
+    show(
+        app,
+        "Credentials Expiring",
+        format!(
+            "Your {} credentials expire in {hours} hours. Click to re-authenticate.",
+            provider_name(provider_id)
+        ),
+    );
+}
+
+fn provider_name(provider_id: &str) -> &str {
+    providers::find_provider_runtime(provider_id)
+        .map(|runtime| runtime.name())
+        .unwrap_or(provider_id)
+}
+
+// Desktop notifications have no action buttons, so alerts are text only.
+fn show(app: &AppHandle, title: &str, body: String) {
+    if let Err(err) = app.notification().builder().title(title).body(body).show() {
+        log::warn!(
+            "[notifications] failed to show notification title={} error={}",
+            title,
+            err
+        );
+    }
+}
+
+pub fn notifications_enabled(settings: &SettingsStore) -> bool {
+    settings
+        .get_setting(NOTIFICATIONS_ENABLED_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|value| value.as_bool())
+        .unwrap_or(true)
+}
+
+/// Asks the OS for permission once and remembers the answer in settings.
+fn ensure_permission(app: &AppHandle, settings: &SettingsStore) -> bool {
+    let stored = settings
+        .get_setting(NOTIFICATION_PERMISSION_SETTING)
+        .ok()
+        .flatten()
+        .and_then(|value| value.as_str().map(str::to_string));
+    if let Some(stored) = stored {
+        return stored == "granted";
+    }
+
+    let notification = app.notification();
+    let state = match notification.permission_state() {
+        Ok(PermissionState::Granted) => PermissionState::Granted,
+        Ok(PermissionState::Denied) => PermissionState::Denied,
+        _ => notification
+            .request_permission()
+            .unwrap_or(PermissionState::Denied),
+    };
+    let granted = state == PermissionState::Granted;
+    log::info!("[notifications] permission requested granted={}", granted);
+    let _ = settings.set_setting(
+        NOTIFICATION_PERMISSION_SETTING,
+        serde_json::json!(if granted { "granted" } else { "denied" }),
+    );
+    granted
+}
@@ -1,8 +1,21 @@
-use tauri::{Manager, Position, Size};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::{LogicalSize, Manager, Position, Size, WindowEvent};
 use tauri_nspanel::{
     tauri_panel, CollectionBehavior, ManagerExt, PanelLevel, StyleMask, WebviewWindowExt,
 };
 
+use crate::settings_store::SettingsStore;
+
+const PANEL_WIDTH_SETTING: &str = "panelWidth";
+const PANEL_HEIGHT_SETTING: &str = "panelHeight";
+const DEFAULT_PANEL_SIZE: (f64, f64) = (400.0, 500.0);
+const MIN_PANEL_SIZE: (f64, f64) = (320.0, 240.0);
+const MAX_PANEL_SIZE: (f64, f64) = (2560.0, 1600.0);
+const PANEL_SIZE_SAVE_DEBOUNCE: Duration = Duration::from_millis(300);
+
 // Define our panel class and event handler together
 tauri_panel! {
     panel!(OpenBurnPanel {
@@ -24,6 +37,17 @@ pub fn init(app_handle: &tauri::AppHandle) -> tauri::Result<()> {
 
     let window = app_handle.get_webview_window("main").unwrap();
 
+    match stored_panel_size(app_handle) {
+        Some((width, height)) => window.set_size(LogicalSize::new(width, height))?,
+        None => {
+            let (width, height) = DEFAULT_PANEL_SIZE;
+            window.set_size(LogicalSize::new(width, height))?;
+            // First launch: nothing to restore, so start on the primary screen's center.
+            window.center()?;
+        }
+    }
+    persist_panel_size_on_resize(app_handle, &window);
+
     let panel = window.to_panel::<OpenBurnPanel>()?;
 
     // Disable native shadow - it causes gray border on transparent windows
@@ -44,6 +68,13 @@ pub fn init(app_handle: &tauri::AppHandle) -> tauri::Result<()> {
 
     panel.set_style_mask(StyleMask::empty().nonactivating_panel().value());
 
+    let auto_hide = app_handle
+        .try_state::<SettingsStore>()
+        .is_some_and(|settings| settings.panel_auto_hide());
+    if auto_hide {
+        set_hides_on_deactivate(app_handle, true)?;
+    }
+
     // Set up event handler to hide panel when it loses focus
     let event_handler = OpenBurnPanelEventHandler::new();
 
@@ -59,6 +90,112 @@ pub fn init(app_handle: &tauri::AppHandle) -> tauri::Result<()> {
     Ok(())
 }
 
+/// Sets `hidesOnDeactivate` on the panel, so it hides whenever another app
+/// becomes active. Does nothing until the panel has been created.
+pub fn set_hides_on_deactivate(app_handle: &tauri::AppHandle, enabled: bool) -> tauri::Result<()> {
+    use objc2::msg_send;
+    use objc2::runtime::{AnyObject, Bool};
+
+    if app_handle.get_webview_panel("main").is_err() {
+        return Ok(());
+    }
+    let Some(window) = app_handle.get_webview_window("main") else {
+        return Ok(());
+    };
+    let ns_window = window.ns_window()?.cast::<AnyObject>();
+    unsafe {
+        let _: () = msg_send![ns_window, setHidesOnDeactivate: Bool::new(enabled)];
+    }
+    Ok(())
+}
+
+fn clamp_panel_size(width: f64, height: f64) -> (f64, f64) {
+    (
+        width.clamp(MIN_PANEL_SIZE.0, MAX_PANEL_SIZE.0),
+        height.clamp(MIN_PANEL_SIZE.1, MAX_PANEL_SIZE.1),
+    )
+}
+
+/// The saved logical size, clamped, or `None` until both values are stored.
+fn stored_panel_size(app_handle: &tauri::AppHandle) -> Option<(f64, f64)> {
+    let settings = app_handle.try_state::<SettingsStore>()?;
+    let read = |key: &str| {
+        settings
+            .get_setting(key)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_f64())
+            .filter(|value| value.is_finite())
+    };
+    let width = read(PANEL_WIDTH_SETTING)?;
+    let height = read(PANEL_HEIGHT_SETTING)?;
+    Some(clamp_panel_size(width, height))
+}
+
+/// Saves the panel's logical size 300ms after the last resize. Writes run in
+/// the background so neither init nor resizing waits on disk.
+fn persist_panel_size_on_resize(app_handle: &tauri::AppHandle, window: &tauri::WebviewWindow) {
+    let generation = Arc::new(AtomicU64::new(0));
+    let handle = app_handle.clone();
+    let scale_window = window.clone();
+    window.on_window_event(move |event| {
+        let WindowEvent::Resized(size) = event else {
+            return;
+        };
+        let Ok(scale_factor) = scale_window.scale_factor() else {
+            return;
+        };
+        let logical = size.to_logical::<f64>(scale_factor);
+        let (width, height) = clamp_panel_size(logical.width, logical.height);
+
+        let current = generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let generation = generation.clone();
+        let handle = handle.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(PANEL_SIZE_SAVE_DEBOUNCE).await;
+            if generation.load(Ordering::SeqCst) != current {
+                return;
+            }
+            let Some(settings) = handle.try_state::<SettingsStore>() else {
+                return;
+            };
+            for (key, value) in [(PANEL_WIDTH_SETTING, width), (PANEL_HEIGHT_SETTING, height)] {
+                if let Err(err) = settings.set_setting(key, serde_json::json!(value.round())) {
+                    log::warn!("[panel] failed to save {}: {}", key, err);
+                }
+            }
+        });
+    });
+}
+
+/// Shows or hides the main panel, initializing it first when showing.
+/// Returns whether the panel is visible afterwards.
+pub fn set_visible(app_handle: &tauri::AppHandle, visible: bool) -> tauri::Result<bool> {
+    if visible {
+        init(app_handle)?;
+    }
+
+    let Ok(panel) = app_handle.get_webview_panel("main") else {
+        return Ok(false);
+    };
+
+    if visible {
+        panel.show_and_make_key();
+    } else {
+        panel.hide();
+    }
+
+    Ok(visible)
+}
+
+pub fn toggle(app_handle: &tauri::AppHandle) -> tauri::Result<bool> {
+    let is_visible = app_handle
+        .get_webview_panel("main")
+        .map(|panel| panel.is_visible())
+        .unwrap_or(false);
+    set_visible(app_handle, !is_visible)
+}
+
 pub fn position_panel_at_tray_icon(
     app_handle: &tauri::AppHandle,
     icon_position: Position,
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::error::{BackendError, Result};
+use crate::probe::ProviderOutput;
+
+pub const DEFAULT_PROBE_CACHE_TTL_MS: u64 = 30_000;
+
+#[derive(Debug, Clone)]
+pub struct CachedProbeResult {
+    pub output: ProviderOutput,
+    pub cached_at_ms: i64,
+    pub ttl_ms: u64,
+}
+
+impl CachedProbeResult {
+    fn is_fresh(&self, now_ms: i64) -> bool {
+        let ttl_ms = i64::try_from(self.ttl_ms).unwrap_or(i64::MAX);
+        now_ms.saturating_sub(self.cached_at_ms) < ttl_ms
+    }
+}
+
+/// Latest provider output per `provider_id`, reused by non-forced batches
+/// while younger than its TTL. In-memory only.
+#[derive(Debug, Default)]
+pub struct ProbeCache {
+    entries: Mutex<HashMap<String, CachedProbeResult>>,
+}
+
+impl ProbeCache {
+    pub fn get_fresh(&self, provider_id: &str, now_ms: i64) -> Result<Option<ProviderOutput>> {
+        let entries = self.lock_entries()?;
+        Ok(entries
+            .get(provider_id)
+            .filter(|entry| entry.is_fresh(now_ms))
+            .map(|entry| entry.output.clone()))
+    }
+
+    pub fn insert(
+        &self,
+        provider_id: &str,
+        output: ProviderOutput,
+        now_ms: i64,
+        ttl_ms: u64,
+    ) -> Result<()> {
+        let mut entries = self.lock_entries()?;
+        entries.insert(
+            provider_id.to_string(),
+            CachedProbeResult {
+                output,
+                cached_at_ms: now_ms,
+                ttl_ms,
+            },
+        );
+        Ok(())
+    }
+
+    /// Drops one provider's entry, or every entry when `provider_id` is `None`.
+    pub fn invalidate(&self, provider_id: Option<&str>) -> Result<()> {
+        let mut entries = self.lock_entries()?;
+        match provider_id {
+            Some(provider_id) => {
+                entries.remove(provider_id);
+            }
+            None => entries.clear(),
+        }
+        Ok(())
+    }
+
+    fn lock_entries(&self) -> Result<MutexGuard<'_, HashMap<String, CachedProbeResult>>> {
+        self.entries
+            .lock()
+            .map_err(|_| BackendError::Store("probe cache lock poisoned".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::probe::build_error_output;
+
+    #[test]
+    fn entries_expire_after_ttl_and_can_be_invalidated() {
+        let cache = ProbeCache::default();
+        cache
+            .insert("codex", build_error_output("codex", "boom"), 1_000, 30_000)
+            .expect("insert should work");
+        cache
+            .insert("zai", build_error_output("zai", "boom"), 1_000, 30_000)
+            .expect("insert should work");
+
+        assert!(cache
+            .get_fresh("codex", 30_999)
+            .expect("get should work")
+            .is_some());
+        assert!(cache
+            .get_fresh("codex", 31_000)
+            .expect("get should work")
+            .is_none());
+
+        cache
+            .invalidate(Some("codex"))
+            .expect("invalidate should work");
+        assert!(cache
+            .get_fresh("codex", 2_000)
+            .expect("get should work")
+            .is_none());
+        assert!(cache
+            .get_fresh("zai", 2_000)
+            .expect("get should work")
+            .is_some());
+
+        cache.invalidate(None).expect("invalidate should work");
+        assert!(cache
+            .get_fresh("zai", 2_000)
+            .expect("get should work")
+            .is_none());
+    }
+}
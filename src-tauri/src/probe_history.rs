@@ -0,0 +1,274 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::Serialize;
+
+use crate::error::{BackendError, Result};
+use crate::providers::usage::{trend_line, MetricLine, ProgressFormat};
+
+const MAX_OUTCOMES_PER_ACCOUNT: usize = 20;
+pub const USAGE_ALERT_PERCENT: f64 = 90.0;
+const USAGE_ALERT_RESET_PERCENT: f64 = 80.0;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProbeOutcome {
+    pub timestamp_ms: i64,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+    /// Used percentage of each progress line, keyed by line label.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub usage_percent: HashMap<String, f64>,
+}
+
+/// Recent probe outcomes per account, oldest first. In-memory only.
+#[derive(Debug, Default)]
+pub struct ProbeHistory {
+    last_probe_outcomes: Mutex<HashMap<String, VecDeque<ProbeOutcome>>>,
+}
+
+impl ProbeHistory {
+    pub fn record(&self, account_id: &str, outcome: ProbeOutcome) -> Result<()> {
+        let mut history = self.lock_history()?;
+        let outcomes = history.entry(account_id.to_string()).or_default();
+        if outcomes.len() == MAX_OUTCOMES_PER_ACCOUNT {
+            outcomes.pop_front();
+        }
+        outcomes.push_back(outcome);
+        Ok(())
+    }
+
+    pub fn outcomes(&self, account_id: &str) -> Result<Vec<ProbeOutcome>> {
+        let history = self.lock_history()?;
+        Ok(history
+            .get(account_id.trim())
+            .map(|outcomes| outcomes.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    /// Usage of the most recent successful outcome that reported any.
+    pub fn last_usage_percent(&self, account_id: &str) -> Result<Option<HashMap<String, f64>>> {
+        let history = self.lock_history()?;
+        Ok(history.get(account_id).and_then(|outcomes| {
+            outcomes
+                .iter()
+                .rev()
+                .find(|outcome| outcome.success && !outcome.usage_percent.is_empty())
+                .map(|outcome| outcome.usage_percent.clone())
+        }))
+    }
+
+    fn lock_history(&self) -> Result<MutexGuard<'_, HashMap<String, VecDeque<ProbeOutcome>>>> {
+        self.last_probe_outcomes
+            .lock()
+            .map_err(|_| BackendError::Store("probe history lock poisoned".to_string()))
+    }
+}
+
+/// `(account_id, label)` pairs currently above `USAGE_ALERT_PERCENT`. A pair
+/// is re-armed only once its usage drops below `USAGE_ALERT_RESET_PERCENT`.
+#[derive(Debug, Default)]
+pub struct ThresholdTracker {
+    crossed: Mutex<HashSet<(String, String)>>,
+}
+
+impl ThresholdTracker {
+    /// Returns `true` when this observation newly crosses the alert threshold.
+    pub fn observe(&self, account_id: &str, label: &str, used_percent: f64) -> Result<bool> {
+        let mut crossed = self
+            .crossed
+            .lock()
+            .map_err(|_| BackendError::Store("threshold tracker lock poisoned".to_string()))?;
+        let key = (account_id.to_string(), label.to_string());
+        if used_percent >= USAGE_ALERT_PERCENT {
+            return Ok(crossed.insert(key));
+        }
+        if used_percent < USAGE_ALERT_RESET_PERCENT {
+            crossed.remove(&key);
+        }
+        Ok(false)
+    }
+}
+
+/// High-usage accounts per provider from each provider's latest probe result.
+#[derive(Debug, Default)]
+pub struct HighUsageTracker {
+    accounts: Mutex<HashMap<String, HashSet<String>>>,
+}
+
+impl HighUsageTracker {
+    /// Replaces `provider_id`'s high-usage accounts and returns the total
+    /// across all providers.
+    pub fn replace(&self, provider_id: &str, accounts: HashSet<String>) -> Result<u32> {
+        let mut by_provider = self
+            .accounts
+            .lock()
+            .map_err(|_| BackendError::Store("high usage tracker lock poisoned".to_string()))?;
+        if accounts.is_empty() {
+            by_provider.remove(provider_id);
+        } else {
+            by_provider.insert(provider_id.to_string(), accounts);
+        }
+        let total = by_provider.values().map(HashSet::len).sum::<usize>();
+        Ok(u32::try_from(total).unwrap_or(u32::MAX))
+    }
+}
+
+pub fn usage_percent_by_label(lines: &[MetricLine]) -> HashMap<String, f64> {
+    lines
+        .iter()
+        .filter_map(|line| match line {
+            MetricLine::Progress {
+                label, used, limit, ..
+            } if *limit > 0.0 => Some((label.clone(), used * 100.0 / limit)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// One trend line per label present in both snapshots, in `current` label order.
+pub fn trend_lines(
+    current: &HashMap<String, f64>,
+    previous: &HashMap<String, f64>,
+) -> Vec<MetricLine> {
+    let mut labels = current
+        .keys()
+        .filter(|label| previous.contains_key(*label))
+        .collect::<Vec<_>>();
+    labels.sort();
+    labels
+        .into_iter()
+        .map(|label| {
+            trend_line(
+                &format!("{label} trend"),
+                current[label],
+                previous[label],
+                ProgressFormat::Percent,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(timestamp_ms: i64, success: bool) -> ProbeOutcome {
+        ProbeOutcome {
+            timestamp_ms,
+            success,
+            error: (!success).then(|| "HTTP 500".to_string()),
+            duration_ms: 10,
+            usage_percent: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn history_is_capped_and_keeps_outcome_order() {
+        let history = ProbeHistory::default();
+        for index in 0..25 {
+            history
+                .record("acc-1", outcome(index, index % 2 == 0))
+                .expect("record should succeed");
+        }
+
+        let outcomes = history.outcomes("acc-1").expect("history should load");
+        assert_eq!(outcomes.len(), MAX_OUTCOMES_PER_ACCOUNT);
+        assert_eq!(outcomes.first().map(|entry| entry.timestamp_ms), Some(5));
+        assert_eq!(outcomes.last().map(|entry| entry.timestamp_ms), Some(24));
+        for pair in outcomes.windows(2) {
+            assert_ne!(pair[0].success, pair[1].success);
+        }
+        assert!(outcomes
+            .iter()
+            .all(|entry| entry.success == entry.error.is_none()));
+        assert!(history
+            .outcomes("acc-2")
+            .expect("history should load")
+            .is_empty());
+    }
+
+    #[test]
+    fn trend_lines_compare_against_last_successful_usage() {
+        let history = ProbeHistory::default();
+        let lines = vec![
+            trend_progress("Session", 40.0, 100.0),
+            trend_progress("Credits", 250.0, 1000.0),
+        ];
+        history
+            .record(
+                "acc-1",
+                ProbeOutcome {
+                    usage_percent: usage_percent_by_label(&lines),
+                    ..outcome(1, true)
+                },
+            )
+            .expect("record should succeed");
+        history
+            .record("acc-1", outcome(2, false))
+            .expect("record should succeed");
+
+        let previous = history
+            .last_usage_percent("acc-1")
+            .expect("history should load")
+            .expect("previous usage should exist");
+        let current = usage_percent_by_label(&[trend_progress("Session", 55.0, 100.0)]);
+        let trends = trend_lines(&current, &previous);
+
+        assert_eq!(trends.len(), 1);
+        match &trends[0] {
+            MetricLine::Trend {
+                label,
+                current,
+                previous,
+                ..
+            } => {
+                assert_eq!(label, "Session trend");
+                assert_eq!(*current, 55.0);
+                assert_eq!(*previous, 40.0);
+            }
+            other => panic!("expected trend line, got {other:?}"),
+        }
+        assert_eq!(
+            history
+                .last_usage_percent("acc-2")
+                .expect("history should load"),
+            None
+        );
+    }
+
+    fn trend_progress(label: &str, used: f64, limit: f64) -> MetricLine {
+        MetricLine::Progress {
+            label: label.to_string(),
+            used,
+            limit,
+            format: ProgressFormat::Percent,
+            resets_at: None,
+            period_duration_ms: None,
+            color: None,
+        }
+    }
+
+    #[test]
+    fn threshold_tracker_fires_once_until_usage_drops_below_reset() {
+        let tracker = ThresholdTracker::default();
+        let observe = |used| {
+            tracker
+                .observe("acc-1", "Session", used)
+                .expect("observe should succeed")
+        };
+
+        assert!(!observe(85.0));
+        assert!(observe(90.0));
+        assert!(!observe(95.0));
+        assert!(!observe(85.0));
+        assert!(!observe(92.0));
+        assert!(!observe(79.9));
+        assert!(observe(91.0));
+        assert!(tracker
+            .observe("acc-2", "Session", 91.0)
+            .expect("observe should succeed"));
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::OnceLock;
 use std::time::Duration;
 
 use reqwest::Client;
@@ -7,7 +8,11 @@ use tokio::time::sleep;
 use url::Url;
 
 use crate::error::{BackendError, Result};
-use crate::providers::common::{format_http_error, format_status_error};
+use crate::providers::common::{
+    format_http_error, format_status_error, http_status_error, parse_retry_after, send_with_retry,
+    ProviderHttpClient,
+};
+use crate::providers::usage::read_json_string;
 use crate::utils::now_unix_ms;
 
 const CLIENT_ID: &str = "1071006060591-tmhssin2h21lcre235vtolojh4g403ep.apps.googleusercontent.com";
@@ -23,6 +28,9 @@ const FETCH_ENDPOINTS: [&str; 3] = [ENDPOINT_DAILY, ENDPOINT_AUTOPUSH, ENDPOINT_
 const LOAD_ENDPOINTS: [&str; 3] = [ENDPOINT_PROD, ENDPOINT_DAILY, ENDPOINT_AUTOPUSH];
 
 pub const DEFAULT_PROJECT_ID: &str = "rising-fact-p41fc";
+pub const PREFERRED_PROJECT_ID_SETTING: &str = "preferredProjectId";
+pub const MODEL_WHITELIST_SETTING: &str = "modelWhitelistAdditions";
+pub const MODEL_BLACKLIST_SETTING: &str = "modelBlacklistOverrides";
 
 const SCOPES: [&str; 5] = [
     "https://www.googleapis.com/auth/cloud-platform",
@@ -40,6 +48,11 @@ const CLIENT_METADATA: &str =
 const ONBOARD_ATTEMPTS: usize = 5;
 const ONBOARD_DELAY_MS: u64 = 2000;
 
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(USER_AGENT).into_client())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AntigravityCredentials {
     #[serde(rename = "type", default)]
@@ -120,6 +133,8 @@ pub struct AntigravityTier {
     pub id: Option<String>,
     #[serde(rename = "isDefault", default)]
     pub is_default: Option<bool>,
+    #[serde(rename = "cloudaicompanionProject", default)]
+    pub cloudaicompanion_project: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -199,8 +214,8 @@ pub async fn exchange_code(
     verifier: &str,
     redirect_uri: &str,
 ) -> Result<AntigravityCredentials> {
-    let client = Client::new();
-    let response = client
+    let client = shared_client();
+    let request = client
         .post(TOKEN_URL)
         .header("content-type", "application/x-www-form-urlencoded")
         .form(&[
@@ -210,12 +225,8 @@ pub async fn exchange_code(
             ("grant_type", "authorization_code"),
             ("redirect_uri", redirect_uri),
             ("code_verifier", verifier),
-        ])
-        .send()
-        .await
-        .map_err(|err| {
-            BackendError::Provider(format!("Antigravity OAuth token request failed: {err}"))
-        })?;
+        ]);
+    let response = send_with_retry(request, "Antigravity OAuth token request failed").await?;
 
     let token = handle_token_response(response).await?;
     let refresh_token = token
@@ -239,8 +250,8 @@ pub async fn refresh_credentials(
     project_id: Option<&str>,
     managed_project_id: Option<&str>,
 ) -> Result<AntigravityCredentials> {
-    let client = Client::new();
-    let response = client
+    let client = shared_client();
+    let request = client
         .post(TOKEN_URL)
         .header("content-type", "application/x-www-form-urlencoded")
         .form(&[
@@ -248,12 +259,8 @@ pub async fn refresh_credentials(
             ("client_secret", CLIENT_SECRET),
             ("refresh_token", refresh_token),
             ("grant_type", "refresh_token"),
-        ])
-        .send()
-        .await
-        .map_err(|err| {
-            BackendError::Provider(format!("Antigravity OAuth refresh failed: {err}"))
-        })?;
+        ]);
+    let response = send_with_retry(request, "Antigravity OAuth refresh failed").await?;
 
     let token = handle_token_response(response).await?;
     let expires_at = expires_at_from(token.expires_in);
@@ -271,11 +278,19 @@ pub async fn refresh_credentials(
     })
 }
 
+/// Loads usage; a `pinned_project_id` is queried as-is, skipping onboarding.
 pub async fn fetch_usage(
     access_token: &str,
     fallback_project_id: &str,
+    pinned_project_id: Option<&str>,
 ) -> Result<AntigravityUsageResponse> {
     let mut load = load_code_assist(access_token).await?;
+    if let Some(project_id) = pinned_project_id {
+        let models = fetch_available_models(access_token, project_id)
+            .await
+            .unwrap_or_default();
+        return Ok(AntigravityUsageResponse { load, models });
+    }
     let mut project_id = extract_load_project_id(&load).filter(|value| !value.trim().is_empty());
 
     if project_id.is_none() {
@@ -311,31 +326,89 @@ pub async fn fetch_project_id(access_token: &str) -> Option<String> {
     extract_load_project_id(&load)
 }
 
+/// Project pinned in account settings with `set_antigravity_preferred_project`.
+pub fn preferred_project_id(settings: &serde_json::Value) -> Option<String> {
+    read_json_string(
+        settings,
+        &[
+            PREFERRED_PROJECT_ID_SETTING,
+            "preferred_project_id",
+            "projectId",
+        ],
+    )
+}
+
+/// Trimmed, de-duplicated model ids for the model filter settings.
+pub fn normalize_model_ids(ids: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for id in ids {
+        let id = id.trim();
+        if !id.is_empty()
+            && !normalized
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(id))
+        {
+            normalized.push(id.to_string());
+        }
+    }
+    normalized
+}
+
+pub async fn list_projects(access_token: &str) -> Result<Vec<String>> {
+    let load = load_code_assist(access_token).await?;
+    Ok(available_project_ids(&load))
+}
+
+/// Project ids advertised by `loadCodeAssist`: the current project first,
+/// then any attached to the allowed tiers.
+pub fn available_project_ids(load: &AntigravityLoadResponse) -> Vec<String> {
+    let tier_projects = load
+        .allowed_tiers
+        .iter()
+        .flatten()
+        .chain(load.current_tier.iter())
+        .chain(load.paid_tier.iter())
+        .filter_map(|tier| tier.cloudaicompanion_project.as_ref());
+
+    let mut projects: Vec<String> = Vec::new();
+    for project in load
+        .cloudaicompanion_project
+        .iter()
+        .chain(tier_projects)
+        .filter_map(extract_project_id)
+    {
+        let project = project.trim().to_string();
+        if !project.is_empty() && !projects.contains(&project) {
+            projects.push(project);
+        }
+    }
+    projects
+}
+
 async fn load_code_assist(access_token: &str) -> Result<AntigravityLoadResponse> {
-    let client = Client::new();
+    let client = shared_client();
     let request_body = serde_json::json!({ "metadata": metadata_payload() });
     let mut errors = Vec::new();
     let endpoints = load_endpoints();
 
     for endpoint in endpoints {
         let url = format!("{endpoint}/v1internal:loadCodeAssist");
-        let response = client
+        let request = client
             .post(&url)
             .bearer_auth(access_token)
             .header("content-type", "application/json")
             .header("user-agent", LOAD_USER_AGENT)
             .header("x-goog-api-client", API_CLIENT)
             .header("client-metadata", CLIENT_METADATA)
-            .json(&request_body)
-            .send()
-            .await;
+            .json(&request_body);
 
-        let response = match response {
+        let response = match send_with_retry(request, &format!("{endpoint} request error")).await {
             Ok(response) => response,
-            Err(err) => {
-                errors.push(format!("{endpoint} request error: {err}"));
+            Err(BackendError::Provider(message) | BackendError::NetworkTimeout { message, .. }) => {
+                errors.push(message);
                 continue;
             }
+            Err(err) => return Err(err),
         };
 
         let status = response.status();
@@ -367,29 +440,27 @@ async fn fetch_available_models(
     access_token: &str,
     project_id: &str,
 ) -> Result<HashMap<String, AntigravityModelInfo>> {
-    let client = Client::new();
+    let client = shared_client();
     let request_body = serde_json::json!({ "project": project_id });
     let mut errors = Vec::new();
 
     for endpoint in FETCH_ENDPOINTS {
         let url = format!("{endpoint}/v1internal:fetchAvailableModels");
-        let response = client
+        let request = client
             .post(&url)
             .bearer_auth(access_token)
             .header("content-type", "application/json")
-            .header("user-agent", USER_AGENT)
             .header("x-goog-api-client", API_CLIENT)
             .header("client-metadata", CLIENT_METADATA)
-            .json(&request_body)
-            .send()
-            .await;
+            .json(&request_body);
 
-        let response = match response {
+        let response = match send_with_retry(request, &format!("{endpoint} request error")).await {
             Ok(response) => response,
-            Err(err) => {
-                errors.push(format!("{endpoint} request error: {err}"));
+            Err(BackendError::Provider(message) | BackendError::NetworkTimeout { message, .. }) => {
+                errors.push(message);
                 continue;
             }
+            Err(err) => return Err(err),
         };
 
         let status = response.status();
@@ -422,7 +493,7 @@ async fn fetch_available_models(
 }
 
 async fn try_onboard_user(access_token: &str, tier_id: &str) -> Option<String> {
-    let client = Client::new();
+    let client = shared_client();
     let request_body = serde_json::json!({
         "tierId": tier_id,
         "metadata": metadata_payload(),
@@ -435,7 +506,6 @@ async fn try_onboard_user(access_token: &str, tier_id: &str) -> Option<String> {
                 .post(&url)
                 .bearer_auth(access_token)
                 .header("content-type", "application/json")
-                .header("user-agent", USER_AGENT)
                 .header("x-goog-api-client", API_CLIENT)
                 .header("client-metadata", CLIENT_METADATA)
                 .json(&request_body)
@@ -554,9 +624,10 @@ fn expires_at_from(expires_in: Option<i64>) -> i64 {
 async fn handle_token_response(response: reqwest::Response) -> Result<TokenResponse> {
     let status = response.status();
     if !status.is_success() {
+        let retry_after = parse_retry_after(response.headers());
         let body = response.text().await.unwrap_or_else(|_| "".to_string());
         let message = format_http_error("OAuth token request failed", status, &body);
-        return Err(BackendError::Provider(message));
+        return Err(http_status_error(status, retry_after, message));
     }
 
     response
@@ -564,3 +635,26 @@ async fn handle_token_response(response: reqwest::Response) -> Result<TokenRespo
         .await
         .map_err(|err| BackendError::Provider(format!("OAuth token decode failed: {err}")))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_ids_are_collected_from_load_and_tiers() {
+        let load = serde_json::from_value::<AntigravityLoadResponse>(serde_json::json!({
+            "cloudaicompanionProject": "proj-current",
+            "currentTier": { "id": "standard-tier", "cloudaicompanionProject": "proj-current" },
+            "allowedTiers": [
+                { "id": "free-tier", "isDefault": true },
+                { "id": "standard-tier", "cloudaicompanionProject": { "id": "proj-team" } },
+            ],
+        }))
+        .expect("load response should deserialize");
+
+        assert_eq!(
+            available_project_ids(&load),
+            vec!["proj-current".to_string(), "proj-team".to_string()]
+        );
+    }
+}
@@ -4,11 +4,12 @@ pub mod probe;
 use crate::models::AccountRecord;
 
 use super::contract::{oauth_provider_contract, ProviderContract};
-use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
+use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime, RefreshFuture};
 
-pub const CONTRACT: ProviderContract = oauth_provider_contract("antigravity", "Antigravity");
+pub const CONTRACT: ProviderContract = oauth_provider_contract("antigravity", "Antigravity")
+    .with_documentation_url("https://antigravity.google/docs");
 
-const LINES: [ManifestLineSpec; 4] = [
+const LINES: [ManifestLineSpec; 5] = [
     ManifestLineSpec {
         line_type: "progress",
         label: "Gemini 3 Pro",
@@ -29,12 +30,25 @@ const LINES: [ManifestLineSpec; 4] = [
         label: "Prompt Credits",
         scope: "detail",
     },
+    // Placeholder for the model quota table; the skeleton has no table shape.
+    ManifestLineSpec {
+        line_type: "badge",
+        label: probe::MODEL_TABLE_LABEL,
+        scope: "detail",
+    },
 ];
 
-const PRIMARY_CANDIDATES: [&str; 5] = [
+// Display names reported by `fetchAvailableModels`, matched case-insensitively
+// after the probe strips the variant suffix (e.g. "Gemini 3.1 Pro (High)",
+// "Claude Opus 4.6 (Thinking)", "GPT-OSS 120B (Medium)"). Older generations stay
+// listed for accounts that have not been moved to the newer models yet.
+const PRIMARY_CANDIDATES: [&str; 8] = [
+    "Gemini 3.1 Pro",
     "Gemini 3 Pro",
     "Gemini 3 Flash",
+    "Claude Opus 4.6",
     "Claude Opus 4.5",
+    "Claude Sonnet 4.6",
     "Claude Sonnet 4.5",
     "GPT-OSS 120B",
 ];
@@ -69,6 +83,14 @@ impl ProviderRuntime for AntigravityRuntime {
         &PRIMARY_CANDIDATES
     }
 
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    fn colors_usage(&self) -> bool {
+        true
+    }
+
     fn probe<'a>(
         &self,
         account: &'a AccountRecord,
@@ -76,4 +98,12 @@ impl ProviderRuntime for AntigravityRuntime {
     ) -> ProbeFuture<'a> {
         Box::pin(probe::probe(account, credentials))
     }
+
+    fn refresh_credentials<'a>(
+        &self,
+        account: &'a AccountRecord,
+        credentials: serde_json::Value,
+    ) -> RefreshFuture<'a> {
+        Box::pin(probe::refresh(account, credentials))
+    }
 }
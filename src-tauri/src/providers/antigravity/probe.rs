@@ -3,15 +3,21 @@ use std::collections::HashMap;
 use crate::error::{BackendError, Result};
 use crate::models::AccountRecord;
 use crate::providers::common::normalize_percent;
+use crate::providers::runtime::{prioritize_primary_lines, RefreshedCredentials};
 use crate::providers::usage::{
-    plan_label, status_line, unix_to_rfc3339, MetricLine, ProbeSuccess, ProgressFormat,
-    PERIOD_30_DAYS_MS, PERIOD_5_HOURS_MS,
+    normalize_metric_line, plan_label, status_line, unix_to_rfc3339, usage_color, MetricLine,
+    ProbeSuccess, ProgressFormat, PERIOD_30_DAYS_MS, PERIOD_5_HOURS_MS,
 };
 
 use super::client as antigravity;
+use super::{LINES, PRIMARY_CANDIDATES};
+
+pub const MODEL_TABLE_LABEL: &str = "Model Quotas";
+// Above this many model lines the detail view gets a single table instead.
+const MODEL_TABLE_THRESHOLD: usize = 3;
 
 pub async fn probe(
-    _account: &AccountRecord,
+    account: &AccountRecord,
     credentials: serde_json::Value,
 ) -> Result<ProbeSuccess> {
     let mut credentials = serde_json::from_value::<antigravity::AntigravityCredentials>(
@@ -95,19 +101,15 @@ pub async fn probe(
         }
     }
 
-    let effective_project_id = credentials
-        .managed_project_id
-        .as_deref()
-        .filter(|value| !value.trim().is_empty())
-        .or_else(|| {
-            credentials
-                .project_id
-                .as_deref()
-                .filter(|value| !value.trim().is_empty())
-        })
-        .unwrap_or(antigravity::DEFAULT_PROJECT_ID);
+    let preferred_project_id = antigravity::preferred_project_id(&account.settings);
+    let effective_project_id = effective_project_id(preferred_project_id.as_deref(), &credentials);
 
-    let usage = antigravity::fetch_usage(&credentials.access_token, effective_project_id).await?;
+    let usage = antigravity::fetch_usage(
+        &credentials.access_token,
+        &effective_project_id,
+        preferred_project_id.as_deref(),
+    )
+    .await?;
 
     if let Some(project_id) = antigravity::extract_load_project_id(&usage.load) {
         let trimmed = project_id.trim();
@@ -134,7 +136,13 @@ pub async fn probe(
         }
     }
 
-    let mut lines = build_antigravity_model_lines(&usage.models);
+    let mut lines = collapse_antigravity_model_lines(prioritize_primary_lines(
+        build_antigravity_model_lines(
+            &usage.models,
+            &AntigravityModelFilter::from_settings(&account.settings),
+        ),
+        &PRIMARY_CANDIDATES,
+    ));
     if let Some(prompt_credits_line) = build_antigravity_prompt_credits_line(&usage.load) {
         lines.push(prompt_credits_line);
     }
@@ -177,11 +185,67 @@ pub async fn probe(
 
     Ok(ProbeSuccess {
         plan,
-        lines,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
         updated_credentials,
+        updated_settings: None,
+    })
+}
+
+pub async fn refresh(
+    _account: &AccountRecord,
+    credentials: serde_json::Value,
+) -> Result<RefreshedCredentials> {
+    let credentials = serde_json::from_value::<antigravity::AntigravityCredentials>(credentials)
+        .map_err(|err| BackendError::Provider(format!("Invalid Antigravity credentials: {err}")))?;
+    let refresh_parts = antigravity::parse_refresh_token(&credentials.refresh_token);
+    if refresh_parts.refresh_token.trim().is_empty() {
+        return Err(BackendError::Provider(
+            "Antigravity OAuth credentials are missing refresh token".to_string(),
+        ));
+    }
+
+    let project_id = credentials
+        .project_id
+        .filter(|value| !value.trim().is_empty())
+        .or(refresh_parts.project_id);
+    let managed_project_id = credentials
+        .managed_project_id
+        .filter(|value| !value.trim().is_empty())
+        .or(refresh_parts.managed_project_id);
+    let refreshed = antigravity::refresh_credentials(
+        &refresh_parts.refresh_token,
+        project_id.as_deref(),
+        managed_project_id.as_deref(),
+    )
+    .await?
+    .with_kind();
+    Ok(RefreshedCredentials {
+        credentials: serde_json::to_value(&refreshed).map_err(|err| {
+            BackendError::Provider(format!("Invalid Antigravity credentials: {err}"))
+        })?,
+        expires_at: refreshed.expires_at,
+        updated_settings: None,
     })
 }
 
+fn effective_project_id(
+    preferred_project_id: Option<&str>,
+    credentials: &antigravity::AntigravityCredentials,
+) -> String {
+    let non_empty = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    preferred_project_id
+        .map(str::to_string)
+        .or_else(|| non_empty(&credentials.managed_project_id))
+        .or_else(|| non_empty(&credentials.project_id))
+        .unwrap_or_else(|| antigravity::DEFAULT_PROJECT_ID.to_string())
+}
+
 #[derive(Debug, Clone)]
 struct AntigravityModelLine {
     label: String,
@@ -209,17 +273,56 @@ fn build_antigravity_prompt_credits_line(
         },
         resets_at: None,
         period_duration_ms: Some(PERIOD_30_DAYS_MS),
-        color: None,
+        color: usage_color(used, monthly),
     })
 }
 
+/// Per-account model overrides from `update_antigravity_model_filter`.
+#[derive(Debug, Clone, Default)]
+struct AntigravityModelFilter {
+    whitelist: Vec<String>,
+    blacklist: Vec<String>,
+}
+
+impl AntigravityModelFilter {
+    fn from_settings(settings: &serde_json::Value) -> Self {
+        let read = |key: &str| -> Vec<String> {
+            settings
+                .get(key)
+                .and_then(serde_json::Value::as_array)
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(serde_json::Value::as_str)
+                        .map(|value| value.trim().to_ascii_uppercase())
+                        .filter(|value| !value.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        Self {
+            whitelist: read(antigravity::MODEL_WHITELIST_SETTING),
+            blacklist: read(antigravity::MODEL_BLACKLIST_SETTING),
+        }
+    }
+
+    fn allows(&self, model_id: &str) -> bool {
+        self.whitelist.contains(&model_id.to_ascii_uppercase())
+    }
+
+    fn blocks(&self, model_id: &str) -> bool {
+        self.blacklist.contains(&model_id.to_ascii_uppercase())
+    }
+}
+
 fn build_antigravity_model_lines(
     models: &HashMap<String, antigravity::AntigravityModelInfo>,
+    filter: &AntigravityModelFilter,
 ) -> Vec<MetricLine> {
     let mut deduped: HashMap<String, AntigravityModelLine> = HashMap::new();
 
     for (model_key, model) in models {
-        if !should_include_antigravity_model(model_key, model) {
+        if !should_include_antigravity_model(model_key, model, filter) {
             continue;
         }
 
@@ -283,11 +386,54 @@ fn build_antigravity_model_lines(
             format: ProgressFormat::Percent,
             resets_at: line.resets_at,
             period_duration_ms: Some(PERIOD_5_HOURS_MS),
-            color: None,
+            color: usage_color(line.used, 100.0),
         })
         .collect()
 }
 
+/// Keeps only the overview model lines and moves the full set into a table.
+fn collapse_antigravity_model_lines(lines: Vec<MetricLine>) -> Vec<MetricLine> {
+    if lines.len() <= MODEL_TABLE_THRESHOLD {
+        return lines;
+    }
+
+    let rows = lines
+        .iter()
+        .filter_map(|line| match line {
+            MetricLine::Progress {
+                label,
+                used,
+                resets_at,
+                ..
+            } => Some(vec![
+                label.clone(),
+                format!("{used:.0}"),
+                resets_at.clone().unwrap_or_else(|| "-".to_string()),
+            ]),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut collapsed = lines
+        .into_iter()
+        .filter(|line| {
+            LINES
+                .iter()
+                .any(|spec| spec.scope == "overview" && spec.label == line.label())
+        })
+        .collect::<Vec<_>>();
+    collapsed.push(MetricLine::Table {
+        label: MODEL_TABLE_LABEL.to_string(),
+        columns: vec![
+            "Model".to_string(),
+            "Used %".to_string(),
+            "Resets At".to_string(),
+        ],
+        rows,
+    });
+    collapsed
+}
+
 fn antigravity_model_rank(label: &str) -> u8 {
     let lower = label.to_ascii_lowercase();
     if lower.contains("gemini") && lower.contains("pro") {
@@ -334,9 +480,12 @@ fn normalize_antigravity_label(label: &str) -> String {
     trimmed.to_string()
 }
 
+/// Whitelisted models skip the static blacklist and the name heuristics;
+/// they still need quota info and a display label.
 fn should_include_antigravity_model(
     model_key: &str,
     model: &antigravity::AntigravityModelInfo,
+    filter: &AntigravityModelFilter,
 ) -> bool {
     if model.quota_info.is_none() {
         return false;
@@ -349,16 +498,21 @@ fn should_include_antigravity_model(
     if model_id.is_empty() {
         return false;
     }
-    if is_blacklisted_antigravity_model(model_id) {
+    if filter.blocks(model_id) {
         return false;
     }
+    if !filter.allows(model_id) {
+        if is_blacklisted_antigravity_model(model_id) {
+            return false;
+        }
 
-    let lower = model_id.to_ascii_lowercase();
-    if lower.starts_with("chat_") || lower.starts_with("tab_") || lower.starts_with("rev") {
-        return false;
-    }
-    if lower.contains("image") || lower.contains("mquery") || lower.contains("lite") {
-        return false;
+        let lower = model_id.to_ascii_lowercase();
+        if lower.starts_with("chat_") || lower.starts_with("tab_") || lower.starts_with("rev") {
+            return false;
+        }
+        if lower.contains("image") || lower.contains("mquery") || lower.contains("lite") {
+            return false;
+        }
     }
 
     let has_display_name = model
@@ -410,3 +564,103 @@ fn parse_antigravity_reset_time(value: Option<&serde_json::Value>) -> Option<Str
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model_line(label: &str, used: f64) -> MetricLine {
+        MetricLine::Progress {
+            label: label.to_string(),
+            used,
+            limit: 100.0,
+            format: ProgressFormat::Percent,
+            resets_at: None,
+            period_duration_ms: Some(PERIOD_5_HOURS_MS),
+            color: None,
+        }
+    }
+
+    #[test]
+    fn preferred_project_overrides_credential_projects() {
+        let credentials =
+            serde_json::from_value::<antigravity::AntigravityCredentials>(serde_json::json!({
+                "access_token": "token",
+                "refresh_token": "refresh",
+                "project_id": "proj-default",
+                "managed_project_id": "proj-managed",
+            }))
+            .expect("credentials should deserialize");
+
+        let settings = serde_json::json!({ "preferredProjectId": " proj-pinned " });
+        let preferred = antigravity::preferred_project_id(&settings);
+        assert_eq!(preferred.as_deref(), Some("proj-pinned"));
+        assert_eq!(
+            effective_project_id(preferred.as_deref(), &credentials),
+            "proj-pinned"
+        );
+        assert_eq!(effective_project_id(None, &credentials), "proj-managed");
+    }
+
+    #[test]
+    fn model_filter_overrides_static_blacklist() {
+        let model =
+            serde_json::from_value::<antigravity::AntigravityModelInfo>(serde_json::json!({
+                "model": "MODEL_GOOGLE_GEMINI_2_5_PRO",
+                "displayName": "Gemini 2.5 Pro",
+                "quotaInfo": { "remainingFraction": 0.5 },
+            }))
+            .expect("model should deserialize");
+        let key = "gemini-2.5-pro";
+
+        let default_filter = AntigravityModelFilter::default();
+        assert!(!should_include_antigravity_model(
+            key,
+            &model,
+            &default_filter
+        ));
+
+        let settings = serde_json::json!({
+            "modelWhitelistAdditions": ["model_google_gemini_2_5_pro"],
+        });
+        let filter = AntigravityModelFilter::from_settings(&settings);
+        assert!(should_include_antigravity_model(key, &model, &filter));
+
+        let settings = serde_json::json!({
+            "modelWhitelistAdditions": ["MODEL_GOOGLE_GEMINI_2_5_PRO"],
+            "modelBlacklistOverrides": ["MODEL_GOOGLE_GEMINI_2_5_PRO"],
+        });
+        let filter = AntigravityModelFilter::from_settings(&settings);
+        assert!(!should_include_antigravity_model(key, &model, &filter));
+    }
+
+    #[test]
+    fn many_model_lines_collapse_into_table() {
+        let few = vec![
+            model_line("Gemini 3 Pro", 10.0),
+            model_line("GPT-OSS 120B", 20.0),
+        ];
+        assert_eq!(collapse_antigravity_model_lines(few).len(), 2);
+
+        let lines = collapse_antigravity_model_lines(vec![
+            model_line("Gemini 3 Pro", 10.0),
+            model_line("Gemini 3 Flash", 20.0),
+            model_line("Claude Sonnet 4.5", 30.0),
+            model_line("GPT-OSS 120B", 40.0),
+        ]);
+        let labels = lines.iter().map(MetricLine::label).collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            vec!["Gemini 3 Pro", "Gemini 3 Flash", MODEL_TABLE_LABEL]
+        );
+
+        match lines.last() {
+            Some(MetricLine::Table { columns, rows, .. }) => {
+                assert_eq!(columns, &vec!["Model", "Used %", "Resets At"]);
+                assert_eq!(rows.len(), 4);
+                assert_eq!(rows[3], vec!["GPT-OSS 120B", "40", "-"]);
+            }
+            other => panic!("expected table line, got {other:?}"),
+        }
+    }
+}
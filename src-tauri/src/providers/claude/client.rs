@@ -1,10 +1,15 @@
+use std::sync::OnceLock;
+
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::error::{BackendError, Result};
-use crate::providers::common::format_http_error;
-use crate::utils::now_unix_ms;
+use crate::providers::common::{
+    decode_jwt_payload, format_http_error_with_context, http_status_error, parse_retry_after,
+    send_with_retry, ProviderHttpClient, DEFAULT_USER_AGENT,
+};
+use crate::utils::{now_unix_ms, rfc3339_to_unix_ms, unix_ms_to_rfc3339};
 
 const CLIENT_ID: &str = "9d1c250a-e61b-44d9-88ed-5944d1962f5e";
 const AUTH_URL: &str = "https://claude.ai/oauth/authorize";
@@ -14,6 +19,11 @@ const BETA_HEADER: &str = "oauth-2025-04-20";
 const SCOPE: &str =
     "org:create_api_key user:profile user:inference user:sessions:claude_code user:mcp_servers";
 
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(DEFAULT_USER_AGENT).into_client())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClaudeCredentials {
     #[serde(rename = "type", default)]
@@ -26,6 +36,14 @@ pub struct ClaudeCredentials {
     pub expires_at: i64,
     #[serde(rename = "subscriptionType", alias = "subscription_type", default)]
     pub subscription_type: Option<String>,
+    /// RFC 3339 subscription renewal date read from the token claims.
+    #[serde(
+        rename = "renewalDate",
+        alias = "renewal_date",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub renewal_date: Option<String>,
 }
 
 impl ClaudeCredentials {
@@ -44,6 +62,57 @@ struct TokenResponse {
     access_token: String,
     refresh_token: String,
     expires_in: i64,
+    #[serde(default)]
+    id_token: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaudeSubscriptionInfo {
+    pub renewal_date: Option<String>,
+    pub subscription_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubscriptionClaims {
+    #[serde(default)]
+    subscription_renewal_date: Option<serde_json::Value>,
+    #[serde(default)]
+    current_period_end: Option<serde_json::Value>,
+    #[serde(default)]
+    subscription_type: Option<String>,
+}
+
+/// Reads subscription details from a JWT payload without verifying it; the
+/// values are only used for display.
+pub fn extract_subscription_from_token(token: &str) -> Option<ClaudeSubscriptionInfo> {
+    let claims = serde_json::from_value::<SubscriptionClaims>(decode_jwt_payload(token)?).ok()?;
+
+    let info = ClaudeSubscriptionInfo {
+        renewal_date: claims
+            .subscription_renewal_date
+            .as_ref()
+            .or(claims.current_period_end.as_ref())
+            .and_then(claim_to_rfc3339),
+        subscription_type: claims
+            .subscription_type
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty()),
+    };
+    (info.renewal_date.is_some() || info.subscription_type.is_some()).then_some(info)
+}
+
+/// Accepts unix seconds, unix milliseconds or an RFC 3339 string.
+fn claim_to_rfc3339(value: &serde_json::Value) -> Option<String> {
+    if let Some(timestamp) = value.as_i64() {
+        let timestamp_ms = if timestamp > 10_000_000_000 {
+            timestamp
+        } else {
+            timestamp.saturating_mul(1000)
+        };
+        return unix_ms_to_rfc3339(timestamp_ms);
+    }
+    let value = value.as_str()?.trim();
+    rfc3339_to_unix_ms(value).and_then(unix_ms_to_rfc3339)
 }
 
 pub fn build_authorize_url(redirect_uri: &str, challenge: &str, state: &str) -> Result<String> {
@@ -67,20 +136,16 @@ pub async fn exchange_code(
     verifier: &str,
     redirect_uri: &str,
 ) -> Result<ClaudeCredentials> {
-    let client = Client::new();
-    let response = client
-        .post(TOKEN_URL)
-        .json(&serde_json::json!({
-            "code": code,
-            "state": state,
-            "grant_type": "authorization_code",
-            "client_id": CLIENT_ID,
-            "redirect_uri": redirect_uri,
-            "code_verifier": verifier,
-        }))
-        .send()
-        .await
-        .map_err(|err| BackendError::Provider(format!("OAuth token request failed: {err}")))?;
+    let client = shared_client();
+    let request = client.post(TOKEN_URL).json(&serde_json::json!({
+        "code": code,
+        "state": state,
+        "grant_type": "authorization_code",
+        "client_id": CLIENT_ID,
+        "redirect_uri": redirect_uri,
+        "code_verifier": verifier,
+    }));
+    let response = send_with_retry(request, "OAuth token request failed").await?;
 
     handle_token_response(response).await
 }
@@ -97,12 +162,24 @@ pub struct ClaudeUsageResponse {
     pub seven_day_opus: Option<ClaudeUsageWindow>,
     #[serde(rename = "seven_day_sonnet")]
     pub seven_day_sonnet: Option<ClaudeUsageWindow>,
+    /// Internal name for the Opus window; the API sends it next to
+    /// `seven_day_opus`, so it cannot be a serde alias of that field.
     #[serde(rename = "iguana_necktie")]
     pub iguana_necktie: Option<ClaudeUsageWindow>,
     #[serde(rename = "extra_usage")]
     pub extra_usage: Option<ClaudeExtraUsage>,
 }
 
+impl ClaudeUsageResponse {
+    /// The Opus window, from `seven_day_opus` or else `iguana_necktie`.
+    pub fn opus_window(&self) -> Option<&ClaudeUsageWindow> {
+        self.seven_day_opus
+            .as_ref()
+            .filter(|window| window.utilization.is_some())
+            .or(self.iguana_necktie.as_ref())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClaudeUsageWindow {
     pub utilization: Option<f64>,
@@ -123,33 +200,26 @@ pub struct ClaudeExtraUsage {
 }
 
 pub async fn refresh_credentials(refresh_token: &str) -> Result<ClaudeCredentials> {
-    let client = Client::new();
-    let response = client
-        .post(TOKEN_URL)
-        .json(&serde_json::json!({
-            "grant_type": "refresh_token",
-            "refresh_token": refresh_token,
-            "client_id": CLIENT_ID,
-        }))
-        .send()
-        .await
-        .map_err(|err| BackendError::Provider(format!("Claude OAuth refresh failed: {err}")))?;
+    let client = shared_client();
+    let request = client.post(TOKEN_URL).json(&serde_json::json!({
+        "grant_type": "refresh_token",
+        "refresh_token": refresh_token,
+        "client_id": CLIENT_ID,
+    }));
+    let response = send_with_retry(request, "Claude OAuth refresh failed").await?;
 
     handle_token_response(response).await
 }
 
 pub async fn fetch_usage(access_token: &str) -> Result<ClaudeUsageResponse> {
-    let client = Client::new();
-    let response = client
+    let client = shared_client();
+    let request = client
         .get(USAGE_URL)
         .bearer_auth(access_token)
         .header("anthropic-beta", BETA_HEADER)
         .header("accept", "application/json")
-        .header("content-type", "application/json")
-        .header("user-agent", "openburn")
-        .send()
-        .await
-        .map_err(|err| BackendError::Provider(format!("Claude usage request failed: {err}")))?;
+        .header("content-type", "application/json");
+    let response = send_with_retry(request, "Claude usage request failed").await?;
 
     let status = response.status();
     if status.is_success() {
@@ -159,17 +229,31 @@ pub async fn fetch_usage(access_token: &str) -> Result<ClaudeUsageResponse> {
             .map_err(|err| BackendError::Provider(format!("Claude usage decode failed: {err}")));
     }
 
+    let retry_after = parse_retry_after(response.headers());
     let body = response.text().await.unwrap_or_else(|_| "".to_string());
-    let message = format_http_error("Claude usage request failed", status, &body);
-    Err(BackendError::Provider(message))
+    let message = format_http_error_with_context(
+        "Claude usage request failed",
+        status,
+        &body,
+        "GET",
+        USAGE_URL,
+    );
+    Err(http_status_error(status, retry_after, message))
 }
 
 async fn handle_token_response(response: reqwest::Response) -> Result<ClaudeCredentials> {
     let status = response.status();
     if !status.is_success() {
+        let retry_after = parse_retry_after(response.headers());
         let body = response.text().await.unwrap_or_else(|_| "".to_string());
-        let message = format_http_error("OAuth token request failed", status, &body);
-        return Err(BackendError::Provider(message));
+        let message = format_http_error_with_context(
+            "OAuth token request failed",
+            status,
+            &body,
+            "POST",
+            TOKEN_URL,
+        );
+        return Err(http_status_error(status, retry_after, message));
     }
 
     let token = response
@@ -177,12 +261,20 @@ async fn handle_token_response(response: reqwest::Response) -> Result<ClaudeCred
         .await
         .map_err(|err| BackendError::Provider(format!("OAuth token decode failed: {err}")))?;
     let expires_at = now_unix_ms().saturating_add(token.expires_in.saturating_mul(1000));
+    let subscription = token
+        .id_token
+        .as_deref()
+        .and_then(extract_subscription_from_token)
+        .or_else(|| extract_subscription_from_token(&token.access_token));
 
     Ok(ClaudeCredentials {
         kind: Some("oauth".to_string()),
         access_token: token.access_token,
         refresh_token: token.refresh_token,
         expires_at,
-        subscription_type: None,
+        subscription_type: subscription
+            .as_ref()
+            .and_then(|info| info.subscription_type.clone()),
+        renewal_date: subscription.and_then(|info| info.renewal_date),
     })
 }
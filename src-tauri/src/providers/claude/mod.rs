@@ -4,11 +4,13 @@ pub mod probe;
 use crate::models::AccountRecord;
 
 use super::contract::{oauth_provider_contract, ProviderContract};
-use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
+use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime, RefreshFuture};
 
-pub const CONTRACT: ProviderContract = oauth_provider_contract("claude", "Claude");
+pub const CONTRACT: ProviderContract = oauth_provider_contract("claude", "Claude")
+    .with_documentation_url("https://docs.anthropic.com/en/docs/claude-code/overview")
+    .with_dashboard_url("https://claude.ai/settings/billing");
 
-const LINES: [ManifestLineSpec; 4] = [
+const LINES: [ManifestLineSpec; 7] = [
     ManifestLineSpec {
         line_type: "progress",
         label: "Session",
@@ -24,11 +26,26 @@ const LINES: [ManifestLineSpec; 4] = [
         label: "Sonnet",
         scope: "detail",
     },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Opus",
+        scope: "detail",
+    },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "OAuth Apps",
+        scope: "detail",
+    },
     ManifestLineSpec {
         line_type: "progress",
         label: "Extra usage",
         scope: "detail",
     },
+    ManifestLineSpec {
+        line_type: "text",
+        label: "Renews",
+        scope: "detail",
+    },
 ];
 
 const PRIMARY_CANDIDATES: [&str; 1] = ["Session"];
@@ -63,6 +80,18 @@ impl ProviderRuntime for ClaudeRuntime {
         &PRIMARY_CANDIDATES
     }
 
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    fn dashboard_url(&self) -> Option<&'static str> {
+        CONTRACT.dashboard_url
+    }
+
+    fn colors_usage(&self) -> bool {
+        true
+    }
+
     fn probe<'a>(
         &self,
         account: &'a AccountRecord,
@@ -70,4 +99,12 @@ impl ProviderRuntime for ClaudeRuntime {
     ) -> ProbeFuture<'a> {
         Box::pin(probe::probe(account, credentials))
     }
+
+    fn refresh_credentials<'a>(
+        &self,
+        account: &'a AccountRecord,
+        credentials: serde_json::Value,
+    ) -> RefreshFuture<'a> {
+        Box::pin(probe::refresh(account, credentials))
+    }
 }
@@ -1,9 +1,11 @@
 use crate::error::{BackendError, Result};
 use crate::models::AccountRecord;
 use crate::providers::common::normalize_percent;
+use crate::providers::runtime::RefreshedCredentials;
 use crate::providers::usage::{
-    dollars_from_cents, normalize_resets_at, plan_label, progress_percent_line, status_line,
-    MetricLine, ProbeSuccess, ProgressFormat, PERIOD_5_HOURS_MS, PERIOD_7_DAYS_MS,
+    dollars_from_cents, normalize_metric_line, normalize_resets_at, plan_label,
+    progress_percent_line, status_line, usage_color, MetricLine, ProbeSuccess, ProgressFormat,
+    PERIOD_5_HOURS_MS, PERIOD_7_DAYS_MS,
 };
 
 use super::client as claude;
@@ -31,9 +33,8 @@ pub async fn probe(
     }
 
     if credentials.is_expired() {
-        let mut refreshed = claude::refresh_credentials(&credentials.refresh_token).await?;
-        refreshed.subscription_type = credentials.subscription_type.clone();
-        credentials = refreshed;
+        let refreshed = claude::refresh_credentials(&credentials.refresh_token).await?;
+        credentials = merge_refreshed(refreshed, &credentials);
         updated_credentials = Some(
             serde_json::to_value(credentials.clone().with_kind()).map_err(|err| {
                 BackendError::Provider(format!("Invalid Claude credentials: {err}"))
@@ -42,43 +43,60 @@ pub async fn probe(
     }
 
     let usage = claude::fetch_usage(&credentials.access_token).await?;
-    let mut lines = Vec::new();
+    let lines = usage_lines(usage, credentials.renewal_date.as_deref());
 
-    if let Some(session) = usage.five_hour {
-        if let Some(utilization) = session.utilization {
-            lines.push(progress_percent_line(
-                "Session",
-                normalize_percent(utilization).clamp(0.0, 100.0),
-                normalize_resets_at(session.resets_at),
-                Some(PERIOD_5_HOURS_MS),
-            ));
-        }
-    }
+    let plan = credentials
+        .subscription_type
+        .as_deref()
+        .map(plan_label)
+        .filter(|value| !value.is_empty());
 
-    if let Some(weekly) = usage.seven_day {
-        if let Some(utilization) = weekly.utilization {
-            lines.push(progress_percent_line(
-                "Weekly",
-                normalize_percent(utilization).clamp(0.0, 100.0),
-                normalize_resets_at(weekly.resets_at),
-                Some(PERIOD_7_DAYS_MS),
-            ));
-        }
+    Ok(ProbeSuccess {
+        plan,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
+        updated_credentials,
+        updated_settings: None,
+    })
+}
+
+/// Keeps the stored subscription type and falls back to the previous renewal
+/// date when the new token carries none.
+fn merge_refreshed(
+    refreshed: claude::ClaudeCredentials,
+    previous: &claude::ClaudeCredentials,
+) -> claude::ClaudeCredentials {
+    claude::ClaudeCredentials {
+        subscription_type: previous
+            .subscription_type
+            .clone()
+            .or(refreshed.subscription_type),
+        renewal_date: refreshed.renewal_date.or(previous.renewal_date.clone()),
+        ..refreshed
     }
+}
 
-    if let Some(sonnet) = usage.seven_day_sonnet {
-        if let Some(utilization) = sonnet.utilization {
-            lines.push(progress_percent_line(
-                "Sonnet",
-                normalize_percent(utilization).clamp(0.0, 100.0),
-                normalize_resets_at(sonnet.resets_at),
-                Some(PERIOD_7_DAYS_MS),
-            ));
+fn usage_lines(usage: claude::ClaudeUsageResponse, renewal_date: Option<&str>) -> Vec<MetricLine> {
+    let mut lines = Vec::new();
+    let windows = [
+        ("Session", usage.five_hour.as_ref(), PERIOD_5_HOURS_MS),
+        ("Weekly", usage.seven_day.as_ref(), PERIOD_7_DAYS_MS),
+        ("Sonnet", usage.seven_day_sonnet.as_ref(), PERIOD_7_DAYS_MS),
+        ("Opus", usage.opus_window(), PERIOD_7_DAYS_MS),
+        (
+            "OAuth Apps",
+            usage.seven_day_oauth_apps.as_ref(),
+            PERIOD_7_DAYS_MS,
+        ),
+    ];
+    for (label, window, period_ms) in windows {
+        if let Some(line) = window.and_then(|window| window_line(label, window, period_ms)) {
+            lines.push(line);
         }
     }
 
     if let Some(extra) = usage.extra_usage {
         if extra.is_enabled.unwrap_or(false) {
+            let format = ProgressFormat::currency(extra.currency.as_deref());
             let used = extra.used_credits;
             let limit = extra.monthly_limit;
             if let (Some(used), Some(limit)) = (used, limit) {
@@ -87,17 +105,25 @@ pub async fn probe(
                         label: "Extra usage".to_string(),
                         used: dollars_from_cents(used),
                         limit: dollars_from_cents(limit),
-                        format: ProgressFormat::Dollars,
+                        format,
                         resets_at: None,
                         period_duration_ms: None,
-                        color: None,
+                        color: usage_color(used, limit),
                     });
                 }
             } else if let Some(used) = used {
                 if used > 0.0 {
+                    let value = match format {
+                        ProgressFormat::Currency { symbol, decimals } => format!(
+                            "{symbol}{:.*}",
+                            usize::from(decimals),
+                            dollars_from_cents(used)
+                        ),
+                        _ => format!("${:.2}", dollars_from_cents(used)),
+                    };
                     lines.push(MetricLine::Text {
                         label: "Extra usage".to_string(),
-                        value: format!("${:.2}", dollars_from_cents(used)),
+                        value,
                         color: None,
                         subtitle: None,
                     });
@@ -110,15 +136,161 @@ pub async fn probe(
         lines.push(status_line("No usage data"));
     }
 
-    let plan = credentials
-        .subscription_type
-        .as_deref()
-        .map(plan_label)
-        .filter(|value| !value.is_empty());
+    if let Some(renewal_date) = renewal_date {
+        lines.push(MetricLine::Text {
+            label: "Renews".to_string(),
+            value: renewal_date.to_string(),
+            color: None,
+            subtitle: None,
+        });
+    }
 
-    Ok(ProbeSuccess {
-        plan,
-        lines,
-        updated_credentials,
+    lines
+}
+
+fn window_line(
+    label: &str,
+    window: &claude::ClaudeUsageWindow,
+    period_ms: u64,
+) -> Option<MetricLine> {
+    let utilization = window.utilization?;
+    Some(progress_percent_line(
+        label,
+        normalize_percent(utilization).clamp(0.0, 100.0),
+        normalize_resets_at(window.resets_at.clone()),
+        Some(period_ms),
+    ))
+}
+
+pub async fn refresh(
+    _account: &AccountRecord,
+    credentials: serde_json::Value,
+) -> Result<RefreshedCredentials> {
+    let credentials = serde_json::from_value::<claude::ClaudeCredentials>(credentials)
+        .map_err(|err| BackendError::Provider(format!("Invalid Claude credentials: {err}")))?;
+    if credentials.refresh_token.trim().is_empty() {
+        return Err(BackendError::Provider(
+            "Claude OAuth credentials are missing refresh token".to_string(),
+        ));
+    }
+
+    let refreshed = merge_refreshed(
+        claude::refresh_credentials(&credentials.refresh_token)
+            .await?
+            .with_kind(),
+        &credentials,
+    );
+    Ok(RefreshedCredentials {
+        credentials: serde_json::to_value(&refreshed)
+            .map_err(|err| BackendError::Provider(format!("Invalid Claude credentials: {err}")))?,
+        expires_at: refreshed.expires_at,
+        updated_settings: None,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+
+    use super::*;
+
+    fn usage(value: serde_json::Value) -> claude::ClaudeUsageResponse {
+        serde_json::from_value(value).expect("usage should deserialize")
+    }
+
+    fn labels(lines: &[MetricLine]) -> Vec<&str> {
+        lines.iter().map(MetricLine::label).collect()
+    }
+
+    #[test]
+    fn iguana_necktie_window_is_reported_as_opus() {
+        let lines = usage_lines(
+            usage(serde_json::json!({
+                "five_hour": { "utilization": 12.0, "resets_at": null },
+                "seven_day_opus": null,
+                "iguana_necktie": { "utilization": 40.0, "resets_at": null },
+                "seven_day_oauth_apps": { "utilization": 5.0, "resets_at": null },
+            })),
+            None,
+        );
+        assert_eq!(labels(&lines), vec!["Session", "Opus", "OAuth Apps"]);
+    }
+
+    #[test]
+    fn null_opus_windows_are_skipped() {
+        let lines = usage_lines(
+            usage(serde_json::json!({
+                "seven_day": { "utilization": 30.0, "resets_at": null },
+                "seven_day_opus": null,
+                "iguana_necktie": null,
+            })),
+            Some("2026-11-01T00:00:00Z"),
+        );
+        assert_eq!(labels(&lines), vec!["Weekly", "Renews"]);
+    }
+
+    #[test]
+    fn extra_usage_uses_account_currency() {
+        let lines = usage_lines(
+            usage(serde_json::json!({
+                "extra_usage": {
+                    "is_enabled": true,
+                    "monthly_limit": 5000.0,
+                    "used_credits": 1234.0,
+                    "utilization": null,
+                    "currency": "EUR",
+                },
+            })),
+            None,
+        );
+        match &lines[..] {
+            [MetricLine::Progress {
+                used,
+                limit,
+                format: ProgressFormat::Currency { symbol, decimals },
+                ..
+            }] => {
+                assert_eq!(symbol, "\u{20ac}");
+                assert_eq!(*decimals, 2);
+                assert_eq!(*used, 12.34);
+                assert_eq!(*limit, 50.0);
+            }
+            other => panic!("unexpected lines: {other:?}"),
+        }
+
+        let lines = usage_lines(
+            usage(serde_json::json!({
+                "extra_usage": {
+                    "is_enabled": true,
+                    "monthly_limit": null,
+                    "used_credits": 250.0,
+                    "utilization": null,
+                    "currency": "GBP",
+                },
+            })),
+            None,
+        );
+        match &lines[..] {
+            [MetricLine::Text { value, .. }] => assert_eq!(value, "\u{a3}2.50"),
+            other => panic!("unexpected lines: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn subscription_info_is_read_from_token_claims() {
+        let payload = URL_SAFE_NO_PAD.encode(
+            serde_json::json!({
+                "current_period_end": 1_793_491_200,
+                "subscription_type": "max",
+            })
+            .to_string(),
+        );
+        let info = claude::extract_subscription_from_token(&format!("header.{payload}.signature"))
+            .expect("claims should be parsed");
+        assert_eq!(info.renewal_date.as_deref(), Some("2026-11-01T00:00:00Z"));
+        assert_eq!(info.subscription_type.as_deref(), Some("max"));
+
+        assert_eq!(claude::extract_subscription_from_token("not-a-jwt"), None);
+    }
+}
@@ -1,20 +1,31 @@
-use base64::engine::general_purpose::URL_SAFE_NO_PAD;
-use base64::Engine;
+use std::sync::OnceLock;
+
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::error::{BackendError, Result};
-use crate::providers::common::format_http_error;
+use crate::providers::common::{
+    decode_jwt_payload, format_http_error_with_context, http_status_error, parse_retry_after,
+    send_with_retry, ProviderHttpClient, DEFAULT_USER_AGENT,
+};
 use crate::utils::now_unix_ms;
 
 const CLIENT_ID: &str = "app_EMoamEEZ73f0CkXaXp7hrann";
 const AUTH_URL: &str = "https://auth.openai.com/oauth/authorize";
 const TOKEN_URL: &str = "https://auth.openai.com/oauth/token";
 const USAGE_URL: &str = "https://chatgpt.com/backend-api/wham/usage";
+const ACCOUNTS_CHECK_URL: &str = "https://chatgpt.com/backend-api/accounts/check/v4-2023-04-27";
 const SCOPE: &str = "openid profile email offline_access";
 const ORIGINATOR: &str = "codex_cli_rs";
 
+pub const ORGANIZATION_ID_SETTING: &str = "organizationId";
+
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(DEFAULT_USER_AGENT).into_client())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodexCredentials {
     #[serde(rename = "type", default)]
@@ -94,8 +105,8 @@ pub async fn exchange_code(
     verifier: &str,
     redirect_uri: &str,
 ) -> Result<CodexCredentials> {
-    let client = Client::new();
-    let response = client
+    let client = shared_client();
+    let request = client
         .post(TOKEN_URL)
         .header("content-type", "application/x-www-form-urlencoded")
         .form(&[
@@ -104,10 +115,8 @@ pub async fn exchange_code(
             ("redirect_uri", redirect_uri),
             ("client_id", CLIENT_ID),
             ("code_verifier", verifier),
-        ])
-        .send()
-        .await
-        .map_err(|err| BackendError::Provider(format!("OAuth token request failed: {err}")))?;
+        ]);
+    let response = send_with_retry(request, "OAuth token request failed").await?;
 
     handle_token_response(response, None).await
 }
@@ -156,18 +165,16 @@ pub async fn refresh_credentials(
     refresh_token: &str,
     account_id: Option<&str>,
 ) -> Result<CodexCredentials> {
-    let client = Client::new();
-    let response = client
+    let client = shared_client();
+    let request = client
         .post(TOKEN_URL)
         .header("content-type", "application/x-www-form-urlencoded")
         .form(&[
             ("grant_type", "refresh_token"),
             ("refresh_token", refresh_token),
             ("client_id", CLIENT_ID),
-        ])
-        .send()
-        .await
-        .map_err(|err| BackendError::Provider(format!("Codex OAuth refresh failed: {err}")))?;
+        ]);
+    let response = send_with_retry(request, "Codex OAuth refresh failed").await?;
 
     handle_token_response(response, account_id)
         .await
@@ -187,22 +194,18 @@ pub async fn fetch_usage(
     access_token: &str,
     account_id: Option<&str>,
 ) -> Result<CodexUsageResponse> {
-    let client = Client::new();
+    let client = shared_client();
     let mut request = client
         .get(USAGE_URL)
         .bearer_auth(access_token)
         .header("accept", "application/json")
-        .header("content-type", "application/json")
-        .header("user-agent", "openburn");
+        .header("content-type", "application/json");
 
     if let Some(account_id) = account_id {
         request = request.header("ChatGPT-Account-Id", account_id);
     }
 
-    let response = request
-        .send()
-        .await
-        .map_err(|err| BackendError::Provider(format!("Codex usage request failed: {err}")))?;
+    let response = send_with_retry(request, "Codex usage request failed").await?;
     let status = response.status();
     if status.is_success() {
         return response
@@ -211,21 +214,107 @@ pub async fn fetch_usage(
             .map_err(|err| BackendError::Provider(format!("Codex usage decode failed: {err}")));
     }
 
+    let retry_after = parse_retry_after(response.headers());
     let body = response.text().await.unwrap_or_else(|_| "".to_string());
-    let message = format_http_error("Codex usage request failed", status, &body);
-    Err(BackendError::Provider(message))
+    let message = format_http_error_with_context(
+        "Codex usage request failed",
+        status,
+        &body,
+        "GET",
+        USAGE_URL,
+    );
+    Err(http_status_error(status, retry_after, message))
 }
 
-pub fn parse_jwt_claims(token: &str) -> Option<IdTokenClaims> {
-    let mut parts = token.split('.');
-    let _header = parts.next()?;
-    let payload = parts.next()?;
-    let _signature = parts.next()?;
-    if parts.next().is_some() {
-        return None;
+/// A ChatGPT workspace the user belongs to; `id` is sent as `ChatGPT-Account-Id`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexOrganization {
+    pub id: String,
+    pub name: Option<String>,
+    pub role: Option<String>,
+}
+
+/// Organization chosen with `set_codex_organization`, if any.
+pub fn organization_id(settings: &serde_json::Value) -> Option<&str> {
+    settings
+        .get(ORGANIZATION_ID_SETTING)
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+pub async fn fetch_organizations(access_token: &str) -> Result<Vec<CodexOrganization>> {
+    let client = shared_client();
+    let request = client
+        .get(ACCOUNTS_CHECK_URL)
+        .bearer_auth(access_token)
+        .header("accept", "application/json");
+
+    let response = send_with_retry(request, "Codex accounts request failed").await?;
+    let status = response.status();
+    if status.is_success() {
+        let body = response.json::<serde_json::Value>().await.map_err(|err| {
+            BackendError::Provider(format!("Codex accounts decode failed: {err}"))
+        })?;
+        return Ok(parse_organizations(&body));
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    let message = format_http_error_with_context(
+        "Codex accounts request failed",
+        status,
+        &body,
+        "GET",
+        ACCOUNTS_CHECK_URL,
+    );
+    Err(http_status_error(status, retry_after, message))
+}
+
+/// Reads `accounts[].account`, where the id and name sit either in a nested
+/// `account` object or directly on the entry. `accounts` may also be keyed by id.
+pub fn parse_organizations(body: &serde_json::Value) -> Vec<CodexOrganization> {
+    let entries: Vec<&serde_json::Value> = match body.get("accounts") {
+        Some(serde_json::Value::Array(items)) => items.iter().collect(),
+        Some(serde_json::Value::Object(items)) => items.values().collect(),
+        _ => return Vec::new(),
+    };
+
+    let mut organizations: Vec<CodexOrganization> = Vec::new();
+    for entry in entries {
+        let Some(membership) = entry.get("account") else {
+            continue;
+        };
+        let details = membership.get("account").unwrap_or(membership);
+        let text = |value: &serde_json::Value, key: &str| {
+            value
+                .get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let Some(id) = text(details, "id").or_else(|| text(details, "account_id")) else {
+            continue;
+        };
+        if organizations
+            .iter()
+            .any(|organization| organization.id == id)
+        {
+            continue;
+        }
+        organizations.push(CodexOrganization {
+            id,
+            name: text(details, "name"),
+            role: text(membership, "account_user_role"),
+        });
     }
-    let decoded = URL_SAFE_NO_PAD.decode(payload).ok()?;
-    serde_json::from_slice::<IdTokenClaims>(&decoded).ok()
+    organizations
+}
+
+pub fn parse_jwt_claims(token: &str) -> Option<IdTokenClaims> {
+    serde_json::from_value::<IdTokenClaims>(decode_jwt_payload(token)?).ok()
 }
 
 pub fn extract_account_id_from_claims(claims: &IdTokenClaims) -> Option<String> {
@@ -270,9 +359,16 @@ async fn handle_token_response(
 ) -> Result<CodexCredentials> {
     let status = response.status();
     if !status.is_success() {
+        let retry_after = parse_retry_after(response.headers());
         let body = response.text().await.unwrap_or_else(|_| "".to_string());
-        let message = format_http_error("OAuth token request failed", status, &body);
-        return Err(BackendError::Provider(message));
+        let message = format_http_error_with_context(
+            "OAuth token request failed",
+            status,
+            &body,
+            "POST",
+            TOKEN_URL,
+        );
+        return Err(http_status_error(status, retry_after, message));
     }
 
     let token = response
@@ -292,3 +388,46 @@ async fn handle_token_response(
         account_id,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn organizations_are_parsed_from_accounts_check() {
+        let body = serde_json::json!({
+            "accounts": [
+                {
+                    "account": {
+                        "account_user_role": "account-owner",
+                        "account": { "id": "org-personal", "name": null },
+                    },
+                },
+                {
+                    "account": {
+                        "account_user_role": "standard-user",
+                        "account": { "id": "org-team", "name": "Acme" },
+                    },
+                },
+                { "account": { "account": { "name": "No id" } } },
+            ],
+        });
+
+        assert_eq!(
+            parse_organizations(&body),
+            vec![
+                CodexOrganization {
+                    id: "org-personal".to_string(),
+                    name: None,
+                    role: Some("account-owner".to_string()),
+                },
+                CodexOrganization {
+                    id: "org-team".to_string(),
+                    name: Some("Acme".to_string()),
+                    role: Some("standard-user".to_string()),
+                },
+            ]
+        );
+        assert!(parse_organizations(&serde_json::json!({})).is_empty());
+    }
+}
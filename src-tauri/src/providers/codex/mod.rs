@@ -3,12 +3,29 @@ pub mod probe;
 
 use crate::models::AccountRecord;
 
-use super::contract::{oauth_provider_contract, ProviderContract};
-use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
+use self::client::ORGANIZATION_ID_SETTING;
+use super::contract::{
+    provider_contract, ProviderContract, SettingsContract, OAUTH_AUTH_STRATEGIES,
+};
+use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime, RefreshFuture};
 
-pub const CONTRACT: ProviderContract = oauth_provider_contract("codex", "Codex");
+pub const CREDENTIAL_EXPIRES_AT_SETTING: &str = "credentialExpiresAt";
 
-const LINES: [ManifestLineSpec; 4] = [
+pub const CONTRACT: ProviderContract = provider_contract(
+    "codex",
+    "Codex",
+    "oauth",
+    OAUTH_AUTH_STRATEGIES,
+    SettingsContract {
+        required_keys: &[],
+        optional_keys: &[CREDENTIAL_EXPIRES_AT_SETTING, ORGANIZATION_ID_SETTING],
+        allow_additional_keys: true,
+    },
+)
+.with_documentation_url("https://developers.openai.com/codex")
+.with_dashboard_url("https://chatgpt.com/settings");
+
+const LINES: [ManifestLineSpec; 5] = [
     ManifestLineSpec {
         line_type: "progress",
         label: "Session",
@@ -24,6 +41,11 @@ const LINES: [ManifestLineSpec; 4] = [
         label: "Reviews",
         scope: "detail",
     },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Review Secondary",
+        scope: "detail",
+    },
     ManifestLineSpec {
         line_type: "progress",
         label: "Credits",
@@ -63,6 +85,18 @@ impl ProviderRuntime for CodexRuntime {
         &PRIMARY_CANDIDATES
     }
 
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    fn dashboard_url(&self) -> Option<&'static str> {
+        CONTRACT.dashboard_url
+    }
+
+    fn colors_usage(&self) -> bool {
+        true
+    }
+
     fn probe<'a>(
         &self,
         account: &'a AccountRecord,
@@ -70,4 +104,12 @@ impl ProviderRuntime for CodexRuntime {
     ) -> ProbeFuture<'a> {
         Box::pin(probe::probe(account, credentials))
     }
+
+    fn refresh_credentials<'a>(
+        &self,
+        account: &'a AccountRecord,
+        credentials: serde_json::Value,
+    ) -> RefreshFuture<'a> {
+        Box::pin(probe::refresh(account, credentials))
+    }
 }
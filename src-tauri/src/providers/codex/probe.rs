@@ -1,15 +1,18 @@
 use crate::error::{BackendError, Result};
 use crate::models::AccountRecord;
 use crate::providers::common::normalize_percent;
+use crate::providers::runtime::RefreshedCredentials;
 use crate::providers::usage::{
-    duration_ms_from_seconds, parse_number, plan_label, progress_percent_line, status_line,
-    unix_to_rfc3339, MetricLine, ProbeSuccess, ProgressFormat, PERIOD_5_HOURS_MS, PERIOD_7_DAYS_MS,
+    duration_ms_from_seconds, normalize_metric_line, parse_number, plan_label,
+    progress_percent_line, status_line, unix_to_rfc3339, usage_color, MetricLine, ProbeSuccess,
+    ProgressFormat, PERIOD_30_DAYS_MS, PERIOD_5_HOURS_MS, PERIOD_7_DAYS_MS,
 };
 
 use super::client as codex;
+use super::CREDENTIAL_EXPIRES_AT_SETTING;
 
 pub async fn probe(
-    _account: &AccountRecord,
+    account: &AccountRecord,
     credentials: serde_json::Value,
 ) -> Result<ProbeSuccess> {
     let mut credentials = serde_json::from_value::<codex::CodexCredentials>(credentials)
@@ -22,6 +25,7 @@ pub async fn probe(
     }
 
     let mut updated_credentials = None;
+    let mut updated_settings = None;
     if credentials.kind.as_deref() != Some("oauth") {
         credentials.kind = Some("oauth".to_string());
         updated_credentials =
@@ -41,10 +45,61 @@ pub async fn probe(
                 BackendError::Provider(format!("Invalid Codex credentials: {err}"))
             })?,
         );
+        updated_settings = Some(settings_with_credential_expiry(
+            &account.settings,
+            credentials.expires_at,
+        ));
+    }
+
+    let chatgpt_account_id =
+        codex::organization_id(&account.settings).or(credentials.account_id.as_deref());
+    let usage = codex::fetch_usage(&credentials.access_token, chatgpt_account_id).await?;
+    let lines = usage_lines(&usage);
+
+    let plan = usage
+        .plan_type
+        .as_deref()
+        .map(plan_label)
+        .filter(|value| !value.is_empty());
+
+    Ok(ProbeSuccess {
+        plan,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
+        updated_credentials,
+        updated_settings,
+    })
+}
+
+pub async fn refresh(
+    account: &AccountRecord,
+    credentials: serde_json::Value,
+) -> Result<RefreshedCredentials> {
+    let credentials = serde_json::from_value::<codex::CodexCredentials>(credentials)
+        .map_err(|err| BackendError::Provider(format!("Invalid Codex credentials: {err}")))?;
+    if credentials.refresh_token.trim().is_empty() {
+        return Err(BackendError::Provider(
+            "Codex OAuth credentials are missing refresh token".to_string(),
+        ));
     }
 
-    let usage =
-        codex::fetch_usage(&credentials.access_token, credentials.account_id.as_deref()).await?;
+    let refreshed = codex::refresh_credentials(
+        &credentials.refresh_token,
+        credentials.account_id.as_deref(),
+    )
+    .await?
+    .with_kind();
+    Ok(RefreshedCredentials {
+        credentials: serde_json::to_value(&refreshed)
+            .map_err(|err| BackendError::Provider(format!("Invalid Codex credentials: {err}")))?,
+        expires_at: refreshed.expires_at,
+        updated_settings: Some(settings_with_credential_expiry(
+            &account.settings,
+            refreshed.expires_at,
+        )),
+    })
+}
+
+fn usage_lines(usage: &codex::CodexUsageResponse) -> Vec<MetricLine> {
     let mut lines = Vec::new();
 
     if let Some(primary) = usage
@@ -87,7 +142,22 @@ pub async fn probe(
                 "Reviews",
                 normalize_percent(used_percent).clamp(0.0, 100.0),
                 review.reset_at.and_then(unix_to_rfc3339),
-                duration_ms_from_seconds(review.limit_window_seconds).or(Some(PERIOD_7_DAYS_MS)),
+                duration_ms_from_seconds(review.limit_window_seconds).or(Some(PERIOD_30_DAYS_MS)),
+            ));
+        }
+    }
+
+    if let Some(review) = usage
+        .code_review_rate_limit
+        .as_ref()
+        .and_then(|value| value.secondary_window.as_ref())
+    {
+        if let Some(used_percent) = review.used_percent {
+            lines.push(progress_percent_line(
+                "Review Secondary",
+                normalize_percent(used_percent).clamp(0.0, 100.0),
+                review.reset_at.and_then(unix_to_rfc3339),
+                duration_ms_from_seconds(review.limit_window_seconds).or(Some(PERIOD_30_DAYS_MS)),
             ));
         }
     }
@@ -106,7 +176,7 @@ pub async fn probe(
                     },
                     resets_at: None,
                     period_duration_ms: None,
-                    color: None,
+                    color: usage_color(used, limit),
                 });
             }
         }
@@ -116,15 +186,53 @@ pub async fn probe(
         lines.push(status_line("No usage data"));
     }
 
-    let plan = usage
-        .plan_type
-        .as_deref()
-        .map(plan_label)
-        .filter(|value| !value.is_empty());
+    lines
+}
 
-    Ok(ProbeSuccess {
-        plan,
-        lines,
-        updated_credentials,
-    })
+fn settings_with_credential_expiry(
+    settings: &serde_json::Value,
+    expires_at: i64,
+) -> serde_json::Value {
+    let mut settings = settings.as_object().cloned().unwrap_or_default();
+    settings.insert(
+        CREDENTIAL_EXPIRES_AT_SETTING.to_string(),
+        serde_json::Value::from(expires_at),
+    );
+    serde_json::Value::Object(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn review_windows_are_reported_separately() {
+        let usage = serde_json::from_value::<codex::CodexUsageResponse>(serde_json::json!({
+            "code_review_rate_limit": {
+                "primary_window": { "used_percent": 20.0, "limit_window_seconds": null, "reset_at": null },
+                "secondary_window": { "used_percent": 45.0, "limit_window_seconds": 86400, "reset_at": null },
+            },
+        }))
+        .expect("usage should deserialize");
+
+        let lines = usage_lines(&usage);
+        let periods: Vec<(&str, Option<u64>)> = lines
+            .iter()
+            .map(|line| match line {
+                MetricLine::Progress {
+                    label,
+                    period_duration_ms,
+                    ..
+                } => (label.as_str(), *period_duration_ms),
+                other => panic!("unexpected line: {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            periods,
+            vec![
+                ("Reviews", Some(PERIOD_30_DAYS_MS)),
+                ("Review Secondary", Some(86_400_000)),
+            ]
+        );
+    }
 }
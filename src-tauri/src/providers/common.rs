@@ -1,10 +1,81 @@
+use std::future::Future;
+use std::time::{Duration, Instant, SystemTime};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::Rng;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use url::Url;
+
+use crate::error::{BackendError, Result};
+use crate::utils::{elapsed_ms_since, now_instant};
+
+pub const RETRY_ATTEMPTS: usize = 3;
+pub const RETRY_BASE_DELAY_MS: u64 = 500;
+pub const DEFAULT_USER_AGENT: &str = "openburn";
+const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+const SHORTEN_BODY_BYTE_LIMIT: usize = 400;
+const ERROR_BODY_HINT_BYTE_LIMIT: usize = 200;
+const ERROR_MESSAGE_KEYS: [&str; 4] = ["message", "error", "detail", "error_description"];
+
+/// Pooled HTTP client configuration shared by provider clients. Each provider
+/// keeps one instance in a `OnceLock` so keep-alive connections are reused.
+#[derive(Debug, Clone)]
+pub struct ProviderHttpClient {
+    client: Client,
+}
+
+impl ProviderHttpClient {
+    pub fn new(user_agent: &str) -> Self {
+        let client = Client::builder()
+            .user_agent(user_agent)
+            .connection_verbose(false)
+            .pool_max_idle_per_host(2)
+            .timeout(HTTP_TIMEOUT)
+            .build()
+            .unwrap_or_else(|err| {
+                log::warn!("[providers] http client build failed, using defaults: {err}");
+                Client::new()
+            });
+        Self { client }
+    }
+
+    pub fn into_client(self) -> Client {
+        self.client
+    }
+}
+
+/// Flattens a response body onto one line and caps it at
+/// `SHORTEN_BODY_BYTE_LIMIT` bytes, cutting on a char boundary.
 pub fn shorten_body(body: &str) -> String {
-    let trimmed = body.replace('\n', " ").trim().to_string();
-    if trimmed.len() > 400 {
-        format!("{}...", trimmed.chars().take(400).collect::<String>())
-    } else {
-        trimmed
+    shorten_to(body, SHORTEN_BODY_BYTE_LIMIT)
+}
+
+fn shorten_to(body: &str, byte_limit: usize) -> String {
+    let trimmed = body.replace('\n', " ");
+    let trimmed = trimmed.trim();
+    if trimmed.len() <= byte_limit {
+        return trimmed.to_string();
+    }
+    let mut end = byte_limit;
+    while !trimmed.is_char_boundary(end) {
+        end -= 1;
     }
+    format!("{}...", &trimmed[..end])
+}
+
+/// Decodes the payload of a `header.payload.signature` JWT without verifying
+/// the signature. Returns `None` unless the payload is a base64url JSON value.
+pub fn decode_jwt_payload(token: &str) -> Option<serde_json::Value> {
+    let mut parts = token.split('.');
+    let _header = parts.next()?;
+    let payload = parts.next()?;
+    let _signature = parts.next()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    let decoded = URL_SAFE_NO_PAD.decode(payload).ok()?;
+    serde_json::from_slice(&decoded).ok()
 }
 
 pub fn normalize_percent(value: f64) -> f64 {
@@ -15,15 +86,371 @@ pub fn normalize_percent(value: f64) -> f64 {
     }
 }
 
+/// `"HTTP {status} — {detail}"`, where `detail` is the error message from a
+/// JSON body or else the start of the raw body.
 pub fn format_status_error(status: reqwest::StatusCode, body: &str) -> String {
-    let body = shorten_body(body);
-    if body.is_empty() {
+    let detail = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| json_error_message(&value))
+        .unwrap_or_else(|| body.to_string());
+    let detail = shorten_to(&detail, ERROR_BODY_HINT_BYTE_LIMIT);
+    if detail.is_empty() {
         format!("HTTP {status}")
     } else {
-        format!("HTTP {status} - {body}")
+        format!("HTTP {status} — {detail}")
     }
 }
 
 pub fn format_http_error(context: &str, status: reqwest::StatusCode, body: &str) -> String {
-    format!("{context}: {}", format_status_error(status, body))
+    format_http_error_with_context(context, status, body, "", "")
+}
+
+/// First non-empty string under one of `ERROR_MESSAGE_KEYS`, looking inside
+/// nested objects such as `{"error": {"message": "..."}}`.
+fn json_error_message(value: &serde_json::Value) -> Option<String> {
+    ERROR_MESSAGE_KEYS
+        .iter()
+        .filter_map(|key| value.get(key))
+        .find_map(|field| match field {
+            serde_json::Value::String(message) => {
+                Some(message.trim().to_string()).filter(|message| !message.is_empty())
+            }
+            serde_json::Value::Object(_) => json_error_message(field),
+            _ => None,
+        })
+}
+
+pub fn format_http_error_with_context(
+    context: &str,
+    status: reqwest::StatusCode,
+    body: &str,
+    method: &str,
+    url: &str,
+) -> String {
+    let target = [
+        method.trim().to_ascii_uppercase(),
+        sanitize_request_url(url),
+    ]
+    .into_iter()
+    .filter(|part| !part.is_empty())
+    .collect::<Vec<_>>()
+    .join(" ");
+    let status_error = format_status_error(status, body);
+    if target.is_empty() {
+        format!("{context}: {status_error}")
+    } else {
+        format!("{context}: {target} {status_error}")
+    }
+}
+
+fn sanitize_request_url(raw: &str) -> String {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return String::new();
+    }
+
+    match Url::parse(trimmed) {
+        Ok(mut url) => {
+            url.set_query(None);
+            url.set_fragment(None);
+            let _ = url.set_username("");
+            let _ = url.set_password(None);
+            url.to_string()
+        }
+        Err(_) => trimmed
+            .split(['?', '#'])
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+/// Runs `f` up to `attempts` times, retrying transient provider errors with
+/// exponential backoff (±10% jitter, capped at `base_delay_ms * 2^(attempts-1)`).
+/// A server `Retry-After` is honoured when within that cap; a longer one ends
+/// the retries so the caller can record it instead of holding its slot.
+pub async fn retry_with_backoff<F, Fut, T>(
+    attempts: usize,
+    base_delay_ms: u64,
+    mut f: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let attempts = attempts.max(1);
+    let max_delay_ms = base_delay_ms.saturating_mul(1 << (attempts - 1).min(16));
+    let mut attempt = 0;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt + 1 < attempts => {
+                let Some(delay_ms) = retry_delay_ms(&err, base_delay_ms, attempt, max_delay_ms)
+                else {
+                    return Err(err);
+                };
+                log::debug!(
+                    "[providers] retrying transient error attempt={} delay_ms={} error={}",
+                    attempt + 1,
+                    delay_ms,
+                    err
+                );
+                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Sends `request` through `retry_with_backoff`, treating HTTP 429/503 and
+/// timeouts as transient. The final attempt's response is returned as-is so
+/// callers keep their own status handling.
+pub async fn send_with_retry(request: RequestBuilder, context: &str) -> Result<Response> {
+    let mut attempt = 0;
+    retry_with_backoff(RETRY_ATTEMPTS, RETRY_BASE_DELAY_MS, || {
+        attempt += 1;
+        let is_last_attempt = attempt >= RETRY_ATTEMPTS;
+        let request = request.try_clone();
+        async move {
+            let request = request.ok_or_else(|| {
+                BackendError::Provider(format!("{context}: request body cannot be retried"))
+            })?;
+            let started = now_instant();
+            let response = request
+                .send()
+                .await
+                .map_err(|err| request_error(context, started, err))?;
+            let status = response.status();
+            if is_last_attempt || !is_transient_status(status) {
+                return Ok(response);
+            }
+            let retry_after = parse_retry_after(response.headers());
+            Err(http_status_error(
+                status,
+                retry_after,
+                format!("{context}: HTTP {status}"),
+            ))
+        }
+    })
+    .await
+}
+
+fn is_transient_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status == StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// Maps a failed HTTP status to `BackendError::RateLimit` for 429,
+/// `BackendError::ServiceUnavailable` for 503, `BackendError::AuthExpired`
+/// for 401/403 and `BackendError::Provider` otherwise.
+pub fn http_status_error(
+    status: StatusCode,
+    retry_after: Option<Duration>,
+    message: String,
+) -> BackendError {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS => BackendError::RateLimit {
+            retry_after,
+            message,
+        },
+        StatusCode::SERVICE_UNAVAILABLE => BackendError::ServiceUnavailable {
+            retry_after,
+            message,
+        },
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => BackendError::AuthExpired {
+            account_id: None,
+            message,
+        },
+        _ => BackendError::Provider(message),
+    }
+}
+
+/// Maps a transport error to `BackendError::NetworkTimeout` when the request
+/// timed out and `BackendError::Provider` otherwise.
+pub fn request_error(context: &str, started: Instant, err: reqwest::Error) -> BackendError {
+    let message = format!("{context}: {err}");
+    if err.is_timeout() {
+        BackendError::NetworkTimeout {
+            elapsed_ms: elapsed_ms_since(started),
+            message,
+        }
+    } else {
+        BackendError::Provider(message)
+    }
+}
+
+fn is_transient_error(err: &BackendError) -> bool {
+    matches!(
+        err,
+        BackendError::RateLimit { .. }
+            | BackendError::ServiceUnavailable { .. }
+            | BackendError::NetworkTimeout { .. }
+    )
+}
+
+/// Delay before retrying `err`, or `None` when it should not be retried.
+fn retry_delay_ms(
+    err: &BackendError,
+    base_delay_ms: u64,
+    attempt: usize,
+    max_delay_ms: u64,
+) -> Option<u64> {
+    if !is_transient_error(err) {
+        return None;
+    }
+    match err.retry_after() {
+        Some(retry_after) => {
+            let hint_ms = u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX);
+            (hint_ms <= max_delay_ms).then_some(hint_ms)
+        }
+        None => Some(backoff_delay_ms(base_delay_ms, attempt).min(max_delay_ms)),
+    }
+}
+
+/// Parses a `Retry-After` header given either as delay seconds or an HTTP-date.
+pub fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let value = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let deadline = httpdate::parse_http_date(value).ok()?;
+    Some(
+        deadline
+            .duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+fn backoff_delay_ms(base_delay_ms: u64, attempt: usize) -> u64 {
+    let delay_ms = base_delay_ms.saturating_mul(1 << attempt.min(16)) as f64;
+    let jitter = rand::thread_rng().gen_range(0.9..=1.1);
+    (delay_ms * jitter).round() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_jwt_payload_requires_three_parts_and_json_payload() {
+        let payload = URL_SAFE_NO_PAD.encode(br#"{"sub":"user-1","exp":1700000000}"#);
+        assert_eq!(
+            decode_jwt_payload(&format!("header.{payload}.signature")),
+            Some(serde_json::json!({ "sub": "user-1", "exp": 1700000000 }))
+        );
+
+        assert_eq!(decode_jwt_payload("not-a-jwt"), None);
+        assert_eq!(decode_jwt_payload(&format!("header.{payload}")), None);
+        assert_eq!(
+            decode_jwt_payload(&format!("header.{payload}.signature.extra")),
+            None
+        );
+        assert_eq!(decode_jwt_payload("header.%%%.signature"), None);
+
+        let non_utf8 = URL_SAFE_NO_PAD.encode([0xff, 0xfe, 0x7b, 0x7d]);
+        assert_eq!(
+            decode_jwt_payload(&format!("header.{non_utf8}.signature")),
+            None
+        );
+    }
+
+    #[test]
+    fn format_http_error_prefers_json_message_and_falls_back_to_body_hint() {
+        let status = StatusCode::BAD_REQUEST;
+        assert_eq!(
+            format_http_error(
+                "OpenCode API error",
+                status,
+                r#"{"error":{"type":"invalid_request","message":"workspace not found"}}"#
+            ),
+            "OpenCode API error: HTTP 400 Bad Request — workspace not found"
+        );
+        assert_eq!(
+            format_http_error(
+                "OAuth token request failed",
+                status,
+                r#"{"error_description":"code expired"}"#
+            ),
+            "OAuth token request failed: HTTP 400 Bad Request — code expired"
+        );
+        assert_eq!(
+            format_http_error("Z.ai usage request failed", status, &"x".repeat(300)),
+            format!(
+                "Z.ai usage request failed: HTTP 400 Bad Request — {}...",
+                "x".repeat(ERROR_BODY_HINT_BYTE_LIMIT)
+            )
+        );
+        assert_eq!(
+            format_http_error("Z.ai usage request failed", status, "  "),
+            "Z.ai usage request failed: HTTP 400 Bad Request"
+        );
+        assert_eq!(
+            format_http_error_with_context(
+                "Z.ai usage request failed",
+                status,
+                r#"{"error":{"message":"bad key"}}"#,
+                "get",
+                "https://api.z.ai/usage?token=secret"
+            ),
+            "Z.ai usage request failed: GET https://api.z.ai/usage HTTP 400 Bad Request — bad key"
+        );
+    }
+
+    #[test]
+    fn retry_delay_honours_short_retry_after_and_gives_up_on_long_ones() {
+        let rate_limited = |seconds| BackendError::RateLimit {
+            retry_after: Some(Duration::from_secs(seconds)),
+            message: "slow down".to_string(),
+        };
+        assert_eq!(retry_delay_ms(&rate_limited(1), 500, 0, 1_000), Some(1_000));
+        assert_eq!(retry_delay_ms(&rate_limited(3600), 500, 0, 1_000), None);
+
+        let unavailable = BackendError::ServiceUnavailable {
+            retry_after: None,
+            message: "maintenance".to_string(),
+        };
+        let delay = retry_delay_ms(&unavailable, 500, 1, 1_000).expect("503 should retry");
+        assert!((900..=1_000).contains(&delay));
+
+        assert_eq!(
+            retry_delay_ms(
+                &BackendError::Provider("request timed out".to_string()),
+                500,
+                0,
+                1_000
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn shorten_body_caps_ascii_at_byte_limit() {
+        assert_eq!(shorten_body("  short\nbody  "), "short body");
+
+        let shortened = shorten_body(&"a".repeat(500));
+        assert_eq!(shortened.len(), SHORTEN_BODY_BYTE_LIMIT + 3);
+        assert!(shortened.ends_with("..."));
+    }
+
+    #[test]
+    fn shorten_body_cuts_multi_byte_text_on_char_boundary() {
+        // Three bytes per char: 400 is not a boundary, so 399 bytes are kept.
+        let shortened = shorten_body(&"\u{6f22}".repeat(200));
+        assert_eq!(shortened.len(), 399 + 3);
+        assert_eq!(shortened.trim_end_matches("...").chars().count(), 133);
+    }
+
+    #[test]
+    fn shorten_body_handles_mixed_content() {
+        let body = format!("{}\u{00e9}\u{6f22}{}", "x".repeat(398), "y".repeat(100));
+        // 398 ASCII bytes + 2-byte char lands exactly on the limit.
+        assert_eq!(
+            shorten_body(&body),
+            format!("{}\u{00e9}...", "x".repeat(398))
+        );
+    }
 }
@@ -24,6 +24,7 @@ pub const COOKIE_AUTH_STRATEGIES: &[AuthStrategyContract] = &[COOKIE_AUTH_STRATE
 
 pub const OPEN_SETTINGS: SettingsContract = SettingsContract {
     required_keys: &[],
+    optional_keys: &[],
     allow_additional_keys: true,
 };
 
@@ -34,6 +35,9 @@ pub struct ProviderContract {
     pub default_auth_strategy_id: &'static str,
     pub auth_strategies: &'static [AuthStrategyContract],
     pub settings: SettingsContract,
+    pub documentation_url: Option<&'static str>,
+    /// Where users check their plan, billing or usage on the provider's site.
+    pub dashboard_url: Option<&'static str>,
 }
 
 impl ProviderContract {
@@ -50,6 +54,22 @@ impl ProviderContract {
                     label: strategy.label,
                 })
                 .collect(),
+            documentation_url: self.documentation_url,
+            dashboard_url: self.dashboard_url,
+        }
+    }
+
+    pub const fn with_documentation_url(self, url: &'static str) -> Self {
+        Self {
+            documentation_url: Some(url),
+            ..self
+        }
+    }
+
+    pub const fn with_dashboard_url(self, url: &'static str) -> Self {
+        Self {
+            dashboard_url: Some(url),
+            ..self
         }
     }
 
@@ -81,6 +101,7 @@ pub enum AuthStrategyKind {
 #[derive(Debug, Clone, Copy)]
 pub struct SettingsContract {
     pub required_keys: &'static [&'static str],
+    pub optional_keys: &'static [&'static str],
     pub allow_additional_keys: bool,
 }
 
@@ -97,6 +118,8 @@ pub const fn provider_contract(
         default_auth_strategy_id,
         auth_strategies,
         settings,
+        documentation_url: None,
+        dashboard_url: None,
     }
 }
 
@@ -1,5 +1,6 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::sync::OnceLock;
 use std::time::Duration;
 
 use reqwest::Client;
@@ -7,7 +8,10 @@ use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 
 use crate::error::{BackendError, Result};
-use crate::providers::common::format_http_error;
+use crate::providers::common::{
+    format_http_error_with_context, http_status_error, parse_retry_after, send_with_retry,
+    ProviderHttpClient,
+};
 use crate::utils::now_unix_ms;
 
 const CLIENT_ID: &str = "Iv1.b507a08c87ecfe98";
@@ -20,6 +24,11 @@ const EDITOR_VERSION: &str = "vscode/1.96.2";
 const EDITOR_PLUGIN_VERSION: &str = "copilot-chat/0.26.7";
 const API_VERSION: &str = "2025-04-01";
 
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(USER_AGENT).into_client())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CopilotCredentials {
     #[serde(rename = "type", default)]
@@ -41,6 +50,24 @@ impl CopilotCredentials {
     }
 }
 
+/// GitHub personal access token with `read:user` scope, used instead of the
+/// device flow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopilotPatCredentials {
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    pub token: String,
+}
+
+impl CopilotPatCredentials {
+    pub fn new(token: &str) -> Self {
+        Self {
+            kind: Some("pat".to_string()),
+            token: token.trim().to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CopilotDeviceCodeResponse {
     #[serde(rename = "device_code")]
@@ -73,24 +100,26 @@ struct DeviceTokenResponse {
 }
 
 pub async fn request_device_code() -> Result<CopilotDeviceCodeResponse> {
-    let client = Client::new();
-    let response = client
+    let client = shared_client();
+    let request = client
         .post(DEVICE_CODE_URL)
         .header("accept", "application/json")
         .header("content-type", "application/x-www-form-urlencoded")
-        .header("user-agent", USER_AGENT)
-        .form(&[("client_id", CLIENT_ID), ("scope", SCOPE)])
-        .send()
-        .await
-        .map_err(|err| {
-            BackendError::Provider(format!("Copilot OAuth device request failed: {err}"))
-        })?;
+        .form(&[("client_id", CLIENT_ID), ("scope", SCOPE)]);
+    let response = send_with_retry(request, "Copilot OAuth device request failed").await?;
 
     let status = response.status();
     if !status.is_success() {
+        let retry_after = parse_retry_after(response.headers());
         let body = response.text().await.unwrap_or_else(|_| "".to_string());
-        let message = format_http_error("Copilot OAuth device request failed", status, &body);
-        return Err(BackendError::Provider(message));
+        let message = format_http_error_with_context(
+            "Copilot OAuth device request failed",
+            status,
+            &body,
+            "POST",
+            DEVICE_CODE_URL,
+        );
+        return Err(http_status_error(status, retry_after, message));
     }
 
     response
@@ -99,13 +128,17 @@ pub async fn request_device_code() -> Result<CopilotDeviceCodeResponse> {
         .map_err(|err| BackendError::Provider(format!("Copilot OAuth device decode failed: {err}")))
 }
 
+/// Polls until the user approves the device code; `on_poll` is called with
+/// the 1-based attempt number before each token request.
 pub async fn poll_for_token(
     device_code: &str,
     interval_seconds: u64,
     cancel_flag: Option<&Arc<AtomicBool>>,
+    mut on_poll: impl FnMut(u32),
 ) -> Result<CopilotCredentials> {
-    let client = Client::new();
+    let client = shared_client();
     let mut interval_seconds = interval_seconds.max(1);
+    let mut attempt: u32 = 0;
 
     loop {
         if is_cancelled(cancel_flag) {
@@ -117,27 +150,31 @@ pub async fn poll_for_token(
             return Err(BackendError::Provider("OAuth cancelled".to_string()));
         }
 
-        let response = client
+        attempt = attempt.saturating_add(1);
+        on_poll(attempt);
+        let request = client
             .post(ACCESS_TOKEN_URL)
             .header("accept", "application/json")
             .header("content-type", "application/x-www-form-urlencoded")
-            .header("user-agent", USER_AGENT)
             .form(&[
                 ("client_id", CLIENT_ID),
                 ("device_code", device_code),
                 ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
-            ])
-            .send()
-            .await
-            .map_err(|err| {
-                BackendError::Provider(format!("Copilot OAuth token request failed: {err}"))
-            })?;
+            ]);
+        let response = send_with_retry(request, "Copilot OAuth token request failed").await?;
 
         let status = response.status();
+        let retry_after = parse_retry_after(response.headers());
         let body = response.text().await.unwrap_or_else(|_| "".to_string());
         if !status.is_success() {
-            let message = format_http_error("Copilot OAuth token request failed", status, &body);
-            return Err(BackendError::Provider(message));
+            let message = format_http_error_with_context(
+                "Copilot OAuth token request failed",
+                status,
+                &body,
+                "POST",
+                ACCESS_TOKEN_URL,
+            );
+            return Err(http_status_error(status, retry_after, message));
         }
 
         let token = serde_json::from_str::<DeviceTokenResponse>(&body).map_err(|err| {
@@ -202,6 +239,17 @@ pub struct CopilotUsageResponse {
         default
     )]
     pub limited_user_reset_date: Option<String>,
+    /// Seat usage for Business and Enterprise organizations.
+    #[serde(default)]
+    pub seats: Option<CopilotSeatsStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopilotSeatsStatus {
+    #[serde(default)]
+    pub total: Option<u64>,
+    #[serde(default)]
+    pub used: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -231,18 +279,15 @@ pub struct CopilotLimitedQuotas {
 }
 
 pub async fn fetch_usage(access_token: &str) -> Result<CopilotUsageResponse> {
-    let client = Client::new();
-    let response = client
+    let client = shared_client();
+    let request = client
         .get(USAGE_URL)
         .header("authorization", format!("token {access_token}"))
         .header("accept", "application/json")
         .header("editor-version", EDITOR_VERSION)
         .header("editor-plugin-version", EDITOR_PLUGIN_VERSION)
-        .header("user-agent", USER_AGENT)
-        .header("x-github-api-version", API_VERSION)
-        .send()
-        .await
-        .map_err(|err| BackendError::Provider(format!("Copilot usage request failed: {err}")))?;
+        .header("x-github-api-version", API_VERSION);
+    let response = send_with_retry(request, "Copilot usage request failed").await?;
 
     let status = response.status();
     if status.is_success() {
@@ -252,9 +297,16 @@ pub async fn fetch_usage(access_token: &str) -> Result<CopilotUsageResponse> {
             .map_err(|err| BackendError::Provider(format!("Copilot usage decode failed: {err}")));
     }
 
+    let retry_after = parse_retry_after(response.headers());
     let body = response.text().await.unwrap_or_else(|_| "".to_string());
-    let message = format_http_error("Copilot usage request failed", status, &body);
-    Err(BackendError::Provider(message))
+    let message = format_http_error_with_context(
+        "Copilot usage request failed",
+        status,
+        &body,
+        "GET",
+        USAGE_URL,
+    );
+    Err(http_status_error(status, retry_after, message))
 }
 
 fn is_cancelled(cancel_flag: Option<&Arc<AtomicBool>>) -> bool {
@@ -3,12 +3,31 @@ pub mod probe;
 
 use crate::models::AccountRecord;
 
-use super::contract::{oauth_provider_contract, ProviderContract};
+use super::contract::{
+    provider_contract, AuthStrategyContract, AuthStrategyKind, ProviderContract,
+    OAUTH_AUTH_STRATEGY, OPEN_SETTINGS,
+};
 use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
 
-pub const CONTRACT: ProviderContract = oauth_provider_contract("copilot", "Copilot");
+pub const PAT_AUTH_STRATEGY: AuthStrategyContract = AuthStrategyContract {
+    id: "pat",
+    label: "Personal Access Token",
+    kind: AuthStrategyKind::ApiKey,
+};
 
-const LINES: [ManifestLineSpec; 3] = [
+const AUTH_STRATEGIES: &[AuthStrategyContract] = &[OAUTH_AUTH_STRATEGY, PAT_AUTH_STRATEGY];
+
+pub const CONTRACT: ProviderContract = provider_contract(
+    "copilot",
+    "Copilot",
+    "oauth",
+    AUTH_STRATEGIES,
+    OPEN_SETTINGS,
+)
+.with_documentation_url("https://docs.github.com/en/copilot")
+.with_dashboard_url("https://github.com/settings/billing/copilot");
+
+const LINES: [ManifestLineSpec; 4] = [
     ManifestLineSpec {
         line_type: "progress",
         label: "Premium",
@@ -24,6 +43,11 @@ const LINES: [ManifestLineSpec; 3] = [
         label: "Completions",
         scope: "overview",
     },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Seats",
+        scope: "detail",
+    },
 ];
 
 const PRIMARY_CANDIDATES: [&str; 2] = ["Premium", "Chat"];
@@ -58,6 +82,18 @@ impl ProviderRuntime for CopilotRuntime {
         &PRIMARY_CANDIDATES
     }
 
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    fn dashboard_url(&self) -> Option<&'static str> {
+        CONTRACT.dashboard_url
+    }
+
+    fn colors_usage(&self) -> bool {
+        true
+    }
+
     fn probe<'a>(
         &self,
         account: &'a AccountRecord,
@@ -2,8 +2,8 @@ use crate::error::{BackendError, Result};
 use crate::models::AccountRecord;
 use crate::providers::common::normalize_percent;
 use crate::providers::usage::{
-    normalize_resets_at, plan_label, progress_percent_line, status_line, MetricLine, ProbeSuccess,
-    PERIOD_30_DAYS_MS,
+    normalize_metric_line, normalize_resets_at, plan_label, progress_percent_line, status_line,
+    usage_color, MetricLine, ProbeSuccess, ProgressFormat, PERIOD_30_DAYS_MS,
 };
 
 use super::client as copilot;
@@ -12,6 +12,18 @@ pub async fn probe(
     _account: &AccountRecord,
     credentials: serde_json::Value,
 ) -> Result<ProbeSuccess> {
+    if credentials.get("type").and_then(|value| value.as_str()) == Some("pat") {
+        let credentials = serde_json::from_value::<copilot::CopilotPatCredentials>(credentials)
+            .map_err(|err| BackendError::Provider(format!("Invalid Copilot credentials: {err}")))?;
+        if credentials.token.trim().is_empty() {
+            return Err(BackendError::Provider(
+                "Copilot personal access token is missing".to_string(),
+            ));
+        }
+        let usage = copilot::fetch_usage(credentials.token.trim()).await?;
+        return Ok(build_probe_success(usage, None));
+    }
+
     let mut credentials = serde_json::from_value::<copilot::CopilotCredentials>(credentials)
         .map_err(|err| BackendError::Provider(format!("Invalid Copilot credentials: {err}")))?;
 
@@ -32,15 +44,22 @@ pub async fn probe(
     }
 
     let usage = copilot::fetch_usage(&credentials.access_token).await?;
+    Ok(build_probe_success(usage, updated_credentials))
+}
+
+fn build_probe_success(
+    usage: copilot::CopilotUsageResponse,
+    updated_credentials: Option<serde_json::Value>,
+) -> ProbeSuccess {
     let mut lines = Vec::new();
 
     if let Some(snapshots) = usage.quota_snapshots.as_ref() {
         if let Some(line) = build_copilot_quota_line(
             "Premium",
-            snapshots
-                .premium_interactions
-                .as_ref()
-                .and_then(|snapshot| snapshot.percent_remaining),
+            snapshot_percent_remaining(
+                "premium_interactions",
+                snapshots.premium_interactions.as_ref(),
+            ),
             usage.quota_reset_date.clone(),
         ) {
             lines.push(line);
@@ -48,10 +67,7 @@ pub async fn probe(
 
         if let Some(line) = build_copilot_quota_line(
             "Chat",
-            snapshots
-                .chat
-                .as_ref()
-                .and_then(|snapshot| snapshot.percent_remaining),
+            snapshot_percent_remaining("chat", snapshots.chat.as_ref()),
             usage.quota_reset_date.clone(),
         ) {
             lines.push(line);
@@ -81,6 +97,10 @@ pub async fn probe(
         }
     }
 
+    if let Some(line) = usage.seats.as_ref().and_then(build_copilot_seats_line) {
+        lines.push(line);
+    }
+
     if lines.is_empty() {
         lines.push(status_line("No usage data"));
     }
@@ -91,11 +111,26 @@ pub async fn probe(
         .map(plan_label)
         .filter(|value| !value.is_empty());
 
-    Ok(ProbeSuccess {
+    ProbeSuccess {
         plan,
-        lines,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
         updated_credentials,
-    })
+        updated_settings: None,
+    }
+}
+
+fn snapshot_percent_remaining(
+    category: &str,
+    snapshot: Option<&copilot::CopilotQuotaSnapshot>,
+) -> Option<f64> {
+    let snapshot = snapshot?;
+    if snapshot.percent_remaining.is_none() {
+        log::warn!(
+            "[copilot] {} present but percent_remaining is null — plan may not expose quota data",
+            category
+        );
+    }
+    snapshot.percent_remaining
 }
 
 fn build_copilot_quota_line(
@@ -133,3 +168,59 @@ fn build_copilot_limited_line(
         Some(PERIOD_30_DAYS_MS),
     ))
 }
+
+fn build_copilot_seats_line(seats: &copilot::CopilotSeatsStatus) -> Option<MetricLine> {
+    let total = seats.total.filter(|value| *value > 0)? as f64;
+    let used = seats.used.unwrap_or(0) as f64;
+    Some(MetricLine::Progress {
+        label: "Seats".to_string(),
+        used,
+        limit: total,
+        format: ProgressFormat::Count {
+            suffix: "seats".to_string(),
+        },
+        resets_at: None,
+        period_duration_ms: None,
+        color: usage_color(used, total),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usage_response_reads_optional_seats() {
+        let usage = serde_json::from_value::<copilot::CopilotUsageResponse>(serde_json::json!({
+            "copilotPlan": "business",
+            "seats": { "total": 25, "used": 18 }
+        }))
+        .expect("usage should deserialize");
+        let line = usage
+            .seats
+            .as_ref()
+            .and_then(build_copilot_seats_line)
+            .expect("seats line should be built");
+        match line {
+            MetricLine::Progress {
+                label, used, limit, ..
+            } => {
+                assert_eq!(label, "Seats");
+                assert_eq!(used, 18.0);
+                assert_eq!(limit, 25.0);
+            }
+            other => panic!("unexpected line: {other:?}"),
+        }
+
+        let single_seat = serde_json::from_value::<copilot::CopilotUsageResponse>(
+            serde_json::json!({ "copilotPlan": "individual" }),
+        )
+        .expect("usage should deserialize");
+        assert!(single_seat.seats.is_none());
+        assert!(build_copilot_seats_line(&copilot::CopilotSeatsStatus {
+            total: Some(0),
+            used: Some(0),
+        })
+        .is_none());
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::OnceLock;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BackendError, Result};
+use crate::providers::common::{
+    format_http_error_with_context, http_status_error, parse_retry_after, ProviderHttpClient,
+    DEFAULT_USER_AGENT,
+};
+
+const BALANCE_URL: &str = "https://api.deepseek.com/user/balance";
+
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(DEFAULT_USER_AGENT).into_client())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeepSeekCredentials {
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    #[serde(
+        rename = "apiKey",
+        alias = "api_key",
+        alias = "token",
+        alias = "access_token",
+        alias = "deepseekApiKey"
+    )]
+    pub api_key: String,
+}
+
+impl DeepSeekCredentials {
+    pub fn with_kind(mut self) -> Self {
+        self.kind = Some("apiKey".to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeepSeekBalance {
+    #[serde(default)]
+    pub is_available: bool,
+    #[serde(default)]
+    pub balance_infos: Vec<DeepSeekBalanceInfo>,
+}
+
+/// Balances are returned as decimal strings (e.g. `"110.00"`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeepSeekBalanceInfo {
+    pub currency: String,
+    #[serde(default)]
+    pub total_balance: Option<String>,
+    #[serde(default)]
+    pub granted_balance: Option<String>,
+    #[serde(default)]
+    pub topped_up_balance: Option<String>,
+}
+
+pub async fn fetch_balance(api_key: &str) -> Result<DeepSeekBalance> {
+    if api_key.trim().is_empty() {
+        return Err(BackendError::Provider(
+            "Missing DeepSeek API key".to_string(),
+        ));
+    }
+
+    let response = shared_client()
+        .get(BALANCE_URL)
+        .bearer_auth(api_key.trim())
+        .header("accept", "application/json")
+        .send()
+        .await
+        .map_err(|err| BackendError::Provider(format!("DeepSeek balance request failed: {err}")))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return response.json::<DeepSeekBalance>().await.map_err(|err| {
+            BackendError::Provider(format!("DeepSeek balance decode failed: {err}"))
+        });
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    let message = format_http_error_with_context(
+        "DeepSeek balance request failed",
+        status,
+        &body,
+        "GET",
+        BALANCE_URL,
+    );
+    Err(http_status_error(status, retry_after, message))
+}
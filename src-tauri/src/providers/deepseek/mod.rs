@@ -0,0 +1,73 @@
+pub mod client;
+pub mod probe;
+
+use crate::models::AccountRecord;
+
+use super::contract::{api_key_provider_contract, ProviderContract};
+use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
+
+pub const CONTRACT: ProviderContract = api_key_provider_contract("deepseek", "DeepSeek")
+    .with_documentation_url("https://api-docs.deepseek.com")
+    .with_dashboard_url("https://platform.deepseek.com/usage");
+
+const LINES: [ManifestLineSpec; 2] = [
+    ManifestLineSpec {
+        line_type: "text",
+        label: "Balance",
+        scope: "overview",
+    },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Credits",
+        scope: "overview",
+    },
+];
+
+const PRIMARY_CANDIDATES: [&str; 1] = ["Credits"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct DeepSeekRuntime;
+
+pub const RUNTIME: DeepSeekRuntime = DeepSeekRuntime;
+
+impl ProviderRuntime for DeepSeekRuntime {
+    fn id(&self) -> &'static str {
+        CONTRACT.id
+    }
+
+    fn name(&self) -> &'static str {
+        CONTRACT.name
+    }
+
+    fn icon_url(&self) -> &'static str {
+        "/providers/deepseek.svg"
+    }
+
+    fn brand_color(&self) -> Option<&'static str> {
+        Some("#4D6BFE")
+    }
+
+    fn lines(&self) -> &'static [ManifestLineSpec] {
+        &LINES
+    }
+
+    fn primary_candidates(&self) -> &'static [&'static str] {
+        &PRIMARY_CANDIDATES
+    }
+
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    fn dashboard_url(&self) -> Option<&'static str> {
+        CONTRACT.dashboard_url
+    }
+
+    fn probe<'a>(
+        &self,
+        account: &'a AccountRecord,
+        credentials: serde_json::Value,
+    ) -> ProbeFuture<'a> {
+        Box::pin(probe::probe(account, credentials))
+    }
+}
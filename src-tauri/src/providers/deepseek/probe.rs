@@ -0,0 +1,187 @@
+use crate::error::{BackendError, Result};
+use crate::models::AccountRecord;
+use crate::providers::usage::{
+    normalize_metric_line, parse_number, read_json_string, status_line, MetricLine, ProbeSuccess,
+    ProgressFormat,
+};
+
+use super::client as deepseek;
+
+const CREDIT_LIMIT_SETTING: &str = "creditLimit";
+const DEFAULT_CREDIT_LIMIT: f64 = 100.0;
+
+pub async fn probe(
+    account: &AccountRecord,
+    credentials: serde_json::Value,
+) -> Result<ProbeSuccess> {
+    let mut credentials = serde_json::from_value::<deepseek::DeepSeekCredentials>(credentials)
+        .map_err(|err| BackendError::Provider(format!("Invalid DeepSeek credentials: {err}")))?;
+
+    let mut updated = false;
+    if credentials.kind.as_deref() != Some("apiKey") {
+        credentials.kind = Some("apiKey".to_string());
+        updated = true;
+    }
+
+    if credentials.api_key.trim().is_empty() {
+        if let Some(value) = read_json_string(
+            &account.settings,
+            &[
+                "apiKey",
+                "api_key",
+                "token",
+                "access_token",
+                "deepseekApiKey",
+            ],
+        ) {
+            credentials.api_key = value;
+            updated = true;
+        }
+    }
+
+    let balance = deepseek::fetch_balance(&credentials.api_key).await?;
+    let mut lines = Vec::new();
+    let has_multiple_currencies = balance.balance_infos.len() > 1;
+
+    for info in &balance.balance_infos {
+        let currency = info.currency.trim();
+        let Some(total) = info.total_balance.as_deref().map(str::trim) else {
+            continue;
+        };
+        let label = if has_multiple_currencies {
+            format!("Balance ({currency})")
+        } else {
+            "Balance".to_string()
+        };
+        lines.push(MetricLine::Text {
+            label,
+            value: format!("{total} {currency}"),
+            color: None,
+            subtitle: None,
+        });
+    }
+
+    if let Some(credits_line) =
+        build_credits_line(&balance.balance_infos, credit_limit(&account.settings))
+    {
+        lines.push(credits_line);
+    }
+
+    if !balance.is_available {
+        lines.push(MetricLine::Badge {
+            label: "Status".to_string(),
+            text: "Insufficient balance".to_string(),
+            color: Some("#ef4444".to_string()),
+            subtitle: None,
+        });
+    }
+
+    if lines.is_empty() {
+        lines.push(status_line("No usage data"));
+    }
+
+    let updated_credentials = if updated {
+        Some(
+            serde_json::to_value(credentials.with_kind()).map_err(|err| {
+                BackendError::Provider(format!("Invalid DeepSeek credentials: {err}"))
+            })?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ProbeSuccess {
+        plan: None,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
+        updated_credentials,
+        updated_settings: None,
+    })
+}
+
+fn credit_limit(settings: &serde_json::Value) -> f64 {
+    settings
+        .get(CREDIT_LIMIT_SETTING)
+        .and_then(|value| match value {
+            serde_json::Value::Number(number) => number.as_f64(),
+            serde_json::Value::String(text) => parse_number(text),
+            _ => None,
+        })
+        .filter(|value| *value > 0.0)
+        .unwrap_or(DEFAULT_CREDIT_LIMIT)
+}
+
+/// Spend against the soft credit limit, in USD when the account has a USD
+/// balance and otherwise in the first reported currency.
+fn build_credits_line(
+    balance_infos: &[deepseek::DeepSeekBalanceInfo],
+    limit: f64,
+) -> Option<MetricLine> {
+    let info = balance_infos
+        .iter()
+        .find(|info| info.currency.eq_ignore_ascii_case("USD"))
+        .or_else(|| balance_infos.first())?;
+    let remaining = info.total_balance.as_deref().and_then(parse_number)?;
+    Some(MetricLine::Progress {
+        label: "Credits".to_string(),
+        used: (limit - remaining).clamp(0.0, limit),
+        limit,
+        format: ProgressFormat::currency(Some(&info.currency)),
+        resets_at: None,
+        period_duration_ms: None,
+        color: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance_info(currency: &str, total: &str) -> deepseek::DeepSeekBalanceInfo {
+        deepseek::DeepSeekBalanceInfo {
+            currency: currency.to_string(),
+            total_balance: Some(total.to_string()),
+            granted_balance: None,
+            topped_up_balance: None,
+        }
+    }
+
+    #[test]
+    fn credits_line_prefers_usd_and_reports_spend() {
+        let infos = vec![balance_info("CNY", "500.00"), balance_info("USD", "30.00")];
+        match build_credits_line(&infos, 100.0) {
+            Some(MetricLine::Progress {
+                used,
+                limit,
+                format: ProgressFormat::Currency { symbol, .. },
+                ..
+            }) => {
+                assert_eq!(used, 70.0);
+                assert_eq!(limit, 100.0);
+                assert_eq!(symbol, "$");
+            }
+            other => panic!("expected credits line, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn credits_line_uses_the_reported_currency_without_usd() {
+        let infos = vec![balance_info("CNY", "20.00")];
+        match build_credits_line(&infos, 100.0) {
+            Some(MetricLine::Progress {
+                used,
+                format: ProgressFormat::Currency { symbol, .. },
+                ..
+            }) => {
+                assert_eq!(used, 80.0);
+                assert_eq!(symbol, "CN\u{a5}");
+            }
+            other => panic!("expected credits line, got {other:?}"),
+        }
+
+        let over_limit = vec![balance_info("CNY", "250.00")];
+        match build_credits_line(&over_limit, 100.0) {
+            Some(MetricLine::Progress { used, .. }) => assert_eq!(used, 0.0),
+            other => panic!("expected credits line, got {other:?}"),
+        }
+    }
+}
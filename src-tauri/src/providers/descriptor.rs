@@ -14,4 +14,6 @@ pub struct ProviderDescriptor {
     pub name: &'static str,
     pub default_auth_strategy_id: &'static str,
     pub auth_strategies: Vec<AuthStrategyDescriptor>,
+    pub documentation_url: Option<&'static str>,
+    pub dashboard_url: Option<&'static str>,
 }
@@ -0,0 +1,211 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BackendError, Result};
+use crate::providers::common::{
+    format_http_error_with_context, http_status_error, parse_retry_after, request_error,
+    shorten_body, ProviderHttpClient, DEFAULT_USER_AGENT,
+};
+use crate::utils::now_instant;
+
+const MODELS_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models";
+
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(DEFAULT_USER_AGENT).into_client())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiCredentials {
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    #[serde(
+        rename = "apiKey",
+        alias = "api_key",
+        alias = "token",
+        alias = "access_token",
+        alias = "geminiApiKey"
+    )]
+    pub api_key: String,
+    #[serde(
+        rename = "projectId",
+        alias = "project_id",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub project_id: Option<String>,
+}
+
+impl GeminiCredentials {
+    pub fn with_kind(mut self) -> Self {
+        self.kind = Some("apiKey".to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiModelsResponse {
+    #[serde(default)]
+    pub models: Vec<GeminiModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiModel {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub quota_status: Option<GeminiQuotaStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiQuotaStatus {
+    #[serde(default)]
+    pub requests_per_minute: Option<GeminiQuotaWindow>,
+    #[serde(default)]
+    pub requests_per_day: Option<GeminiQuotaWindow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeminiQuotaWindow {
+    #[serde(default)]
+    pub used: Option<f64>,
+    #[serde(default)]
+    pub limit: Option<f64>,
+}
+
+/// The models endpoint answers 403 instead of a usage payload once the key's
+/// quota is exhausted, so that case is surfaced separately from other errors.
+/// Other 403s (invalid key, disabled API) stay errors.
+#[derive(Debug, Clone)]
+pub enum GeminiModelsResult {
+    Models(GeminiModelsResponse),
+    QuotaExhausted(String),
+}
+
+pub async fn fetch_models(api_key: &str, project_id: Option<&str>) -> Result<GeminiModelsResult> {
+    if api_key.trim().is_empty() {
+        return Err(BackendError::Provider(
+            "Missing Google AI Studio API key".to_string(),
+        ));
+    }
+
+    let mut request = shared_client()
+        .get(MODELS_URL)
+        .header("x-goog-api-key", api_key.trim())
+        .header("accept", "application/json");
+    if let Some(project_id) = project_id.map(str::trim).filter(|value| !value.is_empty()) {
+        request = request.header("x-goog-user-project", project_id);
+    }
+
+    let started = now_instant();
+    let response = request
+        .send()
+        .await
+        .map_err(|err| request_error("Google AI Studio models request failed", started, err))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return response
+            .json::<GeminiModelsResponse>()
+            .await
+            .map(GeminiModelsResult::Models)
+            .map_err(|err| {
+                BackendError::Provider(format!("Google AI Studio models decode failed: {err}"))
+            });
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    error_response(status, retry_after, &body)
+}
+
+fn error_response(
+    status: StatusCode,
+    retry_after: Option<Duration>,
+    body: &str,
+) -> Result<GeminiModelsResult> {
+    if status == StatusCode::FORBIDDEN && is_quota_exhausted(body) {
+        return Ok(GeminiModelsResult::QuotaExhausted(shorten_body(body)));
+    }
+
+    let message = format_http_error_with_context(
+        "Google AI Studio models request failed",
+        status,
+        body,
+        "GET",
+        MODELS_URL,
+    );
+    Err(http_status_error(status, retry_after, message))
+}
+
+/// Google API errors carry `error.status` and `error.details[].reason`; only
+/// `RESOURCE_EXHAUSTED` or a quota reason means the key ran out of quota.
+fn is_quota_exhausted(body: &str) -> bool {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return false;
+    };
+    let Some(error) = value.get("error") else {
+        return false;
+    };
+    let status_exhausted = error
+        .get("status")
+        .and_then(serde_json::Value::as_str)
+        .is_some_and(|status| status.eq_ignore_ascii_case("RESOURCE_EXHAUSTED"));
+    let quota_reason = error
+        .get("details")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|detail| detail.get("reason").and_then(serde_json::Value::as_str))
+        .any(|reason| reason.to_ascii_lowercase().contains("quota"));
+    status_exhausted || quota_reason
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn google_error(status: &str, reason: &str) -> String {
+        serde_json::json!({
+            "error": {
+                "code": 403,
+                "message": "Request denied",
+                "status": status,
+                "details": [{ "reason": reason }]
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn quota_forbidden_is_reported_as_exhausted() {
+        for body in [
+            google_error("RESOURCE_EXHAUSTED", "RATE_LIMIT_EXCEEDED"),
+            google_error("PERMISSION_DENIED", "QUOTA_EXCEEDED"),
+        ] {
+            assert!(matches!(
+                error_response(StatusCode::FORBIDDEN, None, &body),
+                Ok(GeminiModelsResult::QuotaExhausted(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn other_forbidden_responses_are_auth_errors() {
+        for body in [
+            google_error("PERMISSION_DENIED", "API_KEY_INVALID"),
+            "Forbidden".to_string(),
+        ] {
+            match error_response(StatusCode::FORBIDDEN, None, &body) {
+                Err(BackendError::AuthExpired { .. }) => {}
+                other => panic!("expected auth error, got {other:?}"),
+            }
+        }
+    }
+}
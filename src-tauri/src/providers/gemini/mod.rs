@@ -0,0 +1,83 @@
+pub mod client;
+pub mod probe;
+
+use crate::models::AccountRecord;
+
+use super::contract::{api_key_provider_contract, ProviderContract};
+use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
+
+pub const CONTRACT: ProviderContract = api_key_provider_contract("gemini", "Google AI Studio")
+    .with_documentation_url("https://ai.google.dev/gemini-api/docs")
+    .with_dashboard_url("https://aistudio.google.com/usage");
+
+const LINES: [ManifestLineSpec; 4] = [
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Gemini 2.0 Flash RPM",
+        scope: "overview",
+    },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Gemini 2.0 Flash RPD",
+        scope: "overview",
+    },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Gemini 1.5 Pro RPM",
+        scope: "detail",
+    },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Gemini 1.5 Pro RPD",
+        scope: "detail",
+    },
+];
+
+const PRIMARY_CANDIDATES: [&str; 2] = ["Gemini 2.0 Flash RPD", "Gemini 1.5 Pro RPD"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct GeminiRuntime;
+
+pub const RUNTIME: GeminiRuntime = GeminiRuntime;
+
+impl ProviderRuntime for GeminiRuntime {
+    fn id(&self) -> &'static str {
+        CONTRACT.id
+    }
+
+    fn name(&self) -> &'static str {
+        CONTRACT.name
+    }
+
+    fn icon_url(&self) -> &'static str {
+        "/providers/gemini.svg"
+    }
+
+    fn brand_color(&self) -> Option<&'static str> {
+        Some("#4285F4")
+    }
+
+    fn lines(&self) -> &'static [ManifestLineSpec] {
+        &LINES
+    }
+
+    fn primary_candidates(&self) -> &'static [&'static str] {
+        &PRIMARY_CANDIDATES
+    }
+
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    fn dashboard_url(&self) -> Option<&'static str> {
+        CONTRACT.dashboard_url
+    }
+
+    fn probe<'a>(
+        &self,
+        account: &'a AccountRecord,
+        credentials: serde_json::Value,
+    ) -> ProbeFuture<'a> {
+        Box::pin(probe::probe(account, credentials))
+    }
+}
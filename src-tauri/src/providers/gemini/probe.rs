@@ -0,0 +1,137 @@
+use crate::error::{BackendError, Result};
+use crate::models::AccountRecord;
+use crate::providers::usage::{
+    normalize_metric_line, read_json_string, status_line, MetricLine, ProbeSuccess, ProgressFormat,
+};
+
+use super::client as gemini;
+
+const PERIOD_1_MINUTE_MS: u64 = 60 * 1000;
+const PERIOD_1_DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+const PRIMARY_MODELS: [(&str, &str); 2] = [
+    ("models/gemini-2.0-flash", "Gemini 2.0 Flash"),
+    ("models/gemini-1.5-pro", "Gemini 1.5 Pro"),
+];
+
+pub async fn probe(
+    account: &AccountRecord,
+    credentials: serde_json::Value,
+) -> Result<ProbeSuccess> {
+    let mut credentials = serde_json::from_value::<gemini::GeminiCredentials>(credentials)
+        .map_err(|err| {
+            BackendError::Provider(format!("Invalid Google AI Studio credentials: {err}"))
+        })?;
+
+    let mut updated = false;
+    if credentials.kind.as_deref() != Some("apiKey") {
+        credentials.kind = Some("apiKey".to_string());
+        updated = true;
+    }
+
+    if credentials.api_key.trim().is_empty() {
+        if let Some(value) = read_json_string(
+            &account.settings,
+            &["apiKey", "api_key", "token", "access_token", "geminiApiKey"],
+        ) {
+            credentials.api_key = value;
+            updated = true;
+        }
+    }
+
+    if credentials
+        .project_id
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .is_empty()
+    {
+        if let Some(value) = read_json_string(&account.settings, &["projectId", "project_id"]) {
+            credentials.project_id = Some(value);
+            updated = true;
+        }
+    }
+
+    let result =
+        gemini::fetch_models(&credentials.api_key, credentials.project_id.as_deref()).await?;
+
+    let mut lines = match result {
+        gemini::GeminiModelsResult::Models(response) => build_quota_lines(&response.models),
+        gemini::GeminiModelsResult::QuotaExhausted(detail) => vec![MetricLine::Badge {
+            label: "Status".to_string(),
+            text: "Quota exhausted".to_string(),
+            color: Some("#ef4444".to_string()),
+            subtitle: Some(detail).filter(|value| !value.is_empty()),
+        }],
+    };
+
+    if lines.is_empty() {
+        lines.push(status_line("No usage data"));
+    }
+
+    let updated_credentials = if updated {
+        Some(
+            serde_json::to_value(credentials.with_kind()).map_err(|err| {
+                BackendError::Provider(format!("Invalid Google AI Studio credentials: {err}"))
+            })?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ProbeSuccess {
+        plan: None,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
+        updated_credentials,
+        updated_settings: None,
+    })
+}
+
+fn build_quota_lines(models: &[gemini::GeminiModel]) -> Vec<MetricLine> {
+    let mut lines = Vec::new();
+    for (model_name, label) in PRIMARY_MODELS {
+        let Some(quota) = models
+            .iter()
+            .find(|model| model.name == model_name)
+            .and_then(|model| model.quota_status.as_ref())
+        else {
+            continue;
+        };
+
+        if let Some(line) = build_request_line(
+            &format!("{label} RPM"),
+            quota.requests_per_minute.as_ref(),
+            PERIOD_1_MINUTE_MS,
+        ) {
+            lines.push(line);
+        }
+        if let Some(line) = build_request_line(
+            &format!("{label} RPD"),
+            quota.requests_per_day.as_ref(),
+            PERIOD_1_DAY_MS,
+        ) {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+fn build_request_line(
+    label: &str,
+    window: Option<&gemini::GeminiQuotaWindow>,
+    period_duration_ms: u64,
+) -> Option<MetricLine> {
+    let window = window?;
+    let limit = window.limit.filter(|value| *value > 0.0)?;
+    Some(MetricLine::Progress {
+        label: label.to_string(),
+        used: window.used.unwrap_or(0.0).max(0.0),
+        limit,
+        format: ProgressFormat::Count {
+            suffix: "requests".to_string(),
+        },
+        resets_at: None,
+        period_duration_ms: Some(period_duration_ms),
+        color: None,
+    })
+}
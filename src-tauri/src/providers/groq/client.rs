@@ -0,0 +1,101 @@
+use std::sync::OnceLock;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BackendError, Result};
+use crate::providers::common::{
+    format_http_error_with_context, http_status_error, parse_retry_after, request_error,
+    ProviderHttpClient, DEFAULT_USER_AGENT,
+};
+use crate::utils::now_instant;
+
+const API_BASE: &str = "https://api.groq.com/openai/v1";
+// Resolves to the organization that owns the API key when no org id is known yet.
+const CURRENT_ORG_ID: &str = "me";
+
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(DEFAULT_USER_AGENT).into_client())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroqCredentials {
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    #[serde(
+        rename = "apiKey",
+        alias = "api_key",
+        alias = "token",
+        alias = "access_token",
+        alias = "groqApiKey"
+    )]
+    pub api_key: String,
+    #[serde(
+        rename = "orgId",
+        alias = "org_id",
+        alias = "organizationId",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub org_id: Option<String>,
+}
+
+impl GroqCredentials {
+    pub fn with_kind(mut self) -> Self {
+        self.kind = Some("apiKey".to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroqUsage {
+    #[serde(default, alias = "organizationId", alias = "org_id")]
+    pub organization_id: Option<String>,
+    #[serde(default, alias = "data")]
+    pub models: Vec<GroqModelUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroqModelUsage {
+    #[serde(alias = "model_id", alias = "id")]
+    pub model: String,
+    #[serde(default, alias = "tokensUsed", alias = "total_tokens")]
+    pub tokens_used: Option<f64>,
+    #[serde(default, alias = "tokensPerDay", alias = "tokens_per_day")]
+    pub daily_token_limit: Option<f64>,
+}
+
+pub async fn fetch_usage(api_key: &str, org_id: Option<&str>) -> Result<GroqUsage> {
+    if api_key.trim().is_empty() {
+        return Err(BackendError::Provider("Missing Groq API key".to_string()));
+    }
+
+    let org_id = org_id
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(CURRENT_ORG_ID);
+    let url = format!("{API_BASE}/organizations/{org_id}/usage");
+    let started = now_instant();
+    let response = shared_client()
+        .get(&url)
+        .bearer_auth(api_key.trim())
+        .header("accept", "application/json")
+        .send()
+        .await
+        .map_err(|err| request_error("Groq usage request failed", started, err))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return response
+            .json::<GroqUsage>()
+            .await
+            .map_err(|err| BackendError::Provider(format!("Groq usage decode failed: {err}")));
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    let message =
+        format_http_error_with_context("Groq usage request failed", status, &body, "GET", &url);
+    Err(http_status_error(status, retry_after, message))
+}
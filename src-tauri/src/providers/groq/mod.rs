@@ -0,0 +1,85 @@
+pub mod client;
+pub mod probe;
+
+use crate::models::AccountRecord;
+
+use super::contract::{api_key_provider_contract, ProviderContract};
+use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
+
+pub const CONTRACT: ProviderContract = api_key_provider_contract("groq", "Groq")
+    .with_documentation_url("https://console.groq.com/docs")
+    .with_dashboard_url("https://console.groq.com/dashboard/usage");
+
+// Lines are built per model at probe time; these are representative entries
+// for the most common models rather than a fixed list.
+const LINES: [ManifestLineSpec; 4] = [
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "llama-3.3-70b-versatile",
+        scope: "overview",
+    },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "llama-3.1-8b-instant",
+        scope: "overview",
+    },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "openai/gpt-oss-120b",
+        scope: "overview",
+    },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "qwen/qwen3-32b",
+        scope: "overview",
+    },
+];
+
+const PRIMARY_CANDIDATES: [&str; 2] = ["llama-3.3-70b-versatile", "openai/gpt-oss-120b"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct GroqRuntime;
+
+pub const RUNTIME: GroqRuntime = GroqRuntime;
+
+impl ProviderRuntime for GroqRuntime {
+    fn id(&self) -> &'static str {
+        CONTRACT.id
+    }
+
+    fn name(&self) -> &'static str {
+        CONTRACT.name
+    }
+
+    fn icon_url(&self) -> &'static str {
+        "/providers/groq.svg"
+    }
+
+    fn brand_color(&self) -> Option<&'static str> {
+        Some("#F55036")
+    }
+
+    fn lines(&self) -> &'static [ManifestLineSpec] {
+        &LINES
+    }
+
+    fn primary_candidates(&self) -> &'static [&'static str] {
+        &PRIMARY_CANDIDATES
+    }
+
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    fn dashboard_url(&self) -> Option<&'static str> {
+        CONTRACT.dashboard_url
+    }
+
+    fn probe<'a>(
+        &self,
+        account: &'a AccountRecord,
+        credentials: serde_json::Value,
+    ) -> ProbeFuture<'a> {
+        Box::pin(probe::probe(account, credentials))
+    }
+}
@@ -0,0 +1,137 @@
+use crate::error::{BackendError, Result};
+use crate::models::AccountRecord;
+use crate::providers::usage::{
+    normalize_metric_line, read_json_string, status_line, MetricLine, ProbeSuccess, ProgressFormat,
+};
+
+use super::client as groq;
+
+const PERIOD_1_DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+pub async fn probe(
+    account: &AccountRecord,
+    credentials: serde_json::Value,
+) -> Result<ProbeSuccess> {
+    let mut credentials = serde_json::from_value::<groq::GroqCredentials>(credentials)
+        .map_err(|err| BackendError::Provider(format!("Invalid Groq credentials: {err}")))?;
+
+    let mut updated = false;
+    if credentials.kind.as_deref() != Some("apiKey") {
+        credentials.kind = Some("apiKey".to_string());
+        updated = true;
+    }
+
+    if credentials.api_key.trim().is_empty() {
+        if let Some(value) = read_json_string(
+            &account.settings,
+            &["apiKey", "api_key", "token", "access_token", "groqApiKey"],
+        ) {
+            credentials.api_key = value;
+            updated = true;
+        }
+    }
+
+    let usage = groq::fetch_usage(&credentials.api_key, credentials.org_id.as_deref()).await?;
+
+    if let Some(org_id) = usage
+        .organization_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        if credentials.org_id.as_deref() != Some(org_id) {
+            credentials.org_id = Some(org_id.to_string());
+            updated = true;
+        }
+    }
+
+    let mut lines = build_model_lines(&usage.models);
+    if lines.is_empty() {
+        lines.push(status_line("No usage data"));
+    }
+
+    let updated_credentials = if updated {
+        Some(
+            serde_json::to_value(credentials.with_kind()).map_err(|err| {
+                BackendError::Provider(format!("Invalid Groq credentials: {err}"))
+            })?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ProbeSuccess {
+        plan: None,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
+        updated_credentials,
+        updated_settings: None,
+    })
+}
+
+fn build_model_lines(models: &[groq::GroqModelUsage]) -> Vec<MetricLine> {
+    let mut models = models
+        .iter()
+        .filter_map(|model| {
+            let name = model.model.trim();
+            let limit = model.daily_token_limit.filter(|value| *value > 0.0)?;
+            if name.is_empty() {
+                return None;
+            }
+            Some((name, model.tokens_used.unwrap_or(0.0).max(0.0), limit))
+        })
+        .collect::<Vec<_>>();
+    models.sort_by(|left, right| right.1.total_cmp(&left.1).then_with(|| left.0.cmp(right.0)));
+
+    models
+        .into_iter()
+        .map(|(name, used, limit)| MetricLine::Progress {
+            label: name.to_string(),
+            used,
+            limit,
+            format: ProgressFormat::Count {
+                suffix: "tokens".to_string(),
+            },
+            resets_at: None,
+            period_duration_ms: Some(PERIOD_1_DAY_MS),
+            color: None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_model_list_produces_no_lines() {
+        let usage = serde_json::from_value::<groq::GroqUsage>(serde_json::json!({
+            "organization_id": "org_123",
+            "models": []
+        }))
+        .expect("usage should parse");
+
+        assert!(build_model_lines(&usage.models).is_empty());
+        assert_eq!(usage.organization_id.as_deref(), Some("org_123"));
+    }
+
+    #[test]
+    fn model_lines_are_sorted_by_most_used() {
+        let usage = serde_json::from_value::<groq::GroqUsage>(serde_json::json!({
+            "models": [
+                { "model": "llama-3.1-8b-instant", "tokens_used": 100, "daily_token_limit": 500000 },
+                { "model": "llama-3.3-70b-versatile", "tokens_used": 9000, "daily_token_limit": 100000 }
+            ]
+        }))
+        .expect("usage should parse");
+
+        let labels = build_model_lines(&usage.models)
+            .iter()
+            .map(|line| line.label().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            labels,
+            vec!["llama-3.3-70b-versatile", "llama-3.1-8b-instant"]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BackendError, Result};
+use crate::providers::common::{
+    format_http_error_with_context, http_status_error, parse_retry_after, request_error,
+    ProviderHttpClient, DEFAULT_USER_AGENT,
+};
+use crate::utils::now_instant;
+
+const WHOAMI_URL: &str = "https://huggingface.co/api/whoami-v2";
+const BILLING_USAGE_URL: &str = "https://huggingface.co/api/v2/billing/usage";
+
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(DEFAULT_USER_AGENT).into_client())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HuggingFaceCredentials {
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    #[serde(
+        rename = "apiKey",
+        alias = "api_key",
+        alias = "token",
+        alias = "access_token",
+        alias = "hfToken"
+    )]
+    pub api_key: String,
+}
+
+impl HuggingFaceCredentials {
+    pub fn with_kind(mut self) -> Self {
+        self.kind = Some("apiKey".to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HuggingFaceWhoAmI {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(rename = "type", default)]
+    pub account_type: Option<String>,
+    #[serde(rename = "isPro", default)]
+    pub is_pro: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HuggingFaceBillingUsage {
+    #[serde(default)]
+    pub total_cost_usd: Option<f64>,
+    #[serde(default)]
+    pub models: HashMap<String, HuggingFaceModelUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HuggingFaceModelUsage {
+    #[serde(default)]
+    pub calls: Option<f64>,
+    #[serde(default)]
+    pub tokens: Option<f64>,
+}
+
+pub async fn fetch_whoami(api_key: &str) -> Result<HuggingFaceWhoAmI> {
+    if api_key.trim().is_empty() {
+        return Err(BackendError::Provider(
+            "Missing Hugging Face API key".to_string(),
+        ));
+    }
+
+    let started = now_instant();
+    let response = authorized_get(WHOAMI_URL, api_key)
+        .await
+        .map_err(|err| request_error("Hugging Face whoami failed", started, err))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return response.json::<HuggingFaceWhoAmI>().await.map_err(|err| {
+            BackendError::Provider(format!("Hugging Face whoami decode failed: {err}"))
+        });
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    let message = format_http_error_with_context(
+        "Hugging Face whoami failed",
+        status,
+        &body,
+        "GET",
+        WHOAMI_URL,
+    );
+    Err(http_status_error(status, retry_after, message))
+}
+
+pub async fn fetch_billing_usage(api_key: &str) -> Result<HuggingFaceBillingUsage> {
+    let started = now_instant();
+    let response = authorized_get(BILLING_USAGE_URL, api_key)
+        .await
+        .map_err(|err| request_error("Hugging Face usage request failed", started, err))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return response
+            .json::<HuggingFaceBillingUsage>()
+            .await
+            .map_err(|err| {
+                BackendError::Provider(format!("Hugging Face usage decode failed: {err}"))
+            });
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    let message = format_http_error_with_context(
+        "Hugging Face usage request failed",
+        status,
+        &body,
+        "GET",
+        BILLING_USAGE_URL,
+    );
+    Err(http_status_error(status, retry_after, message))
+}
+
+async fn authorized_get(url: &str, api_key: &str) -> reqwest::Result<reqwest::Response> {
+    shared_client()
+        .get(url)
+        .bearer_auth(api_key.trim())
+        .header("accept", "application/json")
+        .send()
+        .await
+}
@@ -0,0 +1,152 @@
+use crate::error::{BackendError, Result};
+use crate::models::AccountRecord;
+use crate::providers::usage::{
+    normalize_metric_line, read_json_string, status_line, MetricLine, ProbeSuccess,
+};
+
+use super::client as huggingface;
+
+pub async fn probe(
+    account: &AccountRecord,
+    credentials: serde_json::Value,
+) -> Result<ProbeSuccess> {
+    let mut credentials = serde_json::from_value::<huggingface::HuggingFaceCredentials>(
+        credentials,
+    )
+    .map_err(|err| BackendError::Provider(format!("Invalid Hugging Face credentials: {err}")))?;
+
+    let mut updated = false;
+    if credentials.kind.as_deref() != Some("apiKey") {
+        credentials.kind = Some("apiKey".to_string());
+        updated = true;
+    }
+
+    if credentials.api_key.trim().is_empty() {
+        if let Some(value) = read_json_string(
+            &account.settings,
+            &["apiKey", "api_key", "token", "access_token", "hfToken"],
+        ) {
+            credentials.api_key = value;
+            updated = true;
+        }
+    }
+
+    let whoami = huggingface::fetch_whoami(&credentials.api_key).await?;
+    let usage = huggingface::fetch_billing_usage(&credentials.api_key).await?;
+    let mut lines = build_usage_lines(&usage);
+    if lines.is_empty() {
+        lines.push(status_line("No usage data"));
+    }
+
+    let plan = match whoami.is_pro {
+        Some(true) => Some("Pro".to_string()),
+        _ => None,
+    };
+
+    let updated_credentials = if updated {
+        Some(
+            serde_json::to_value(credentials.with_kind()).map_err(|err| {
+                BackendError::Provider(format!("Invalid Hugging Face credentials: {err}"))
+            })?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ProbeSuccess {
+        plan,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
+        updated_credentials,
+        updated_settings: None,
+    })
+}
+
+/// Monthly spend plus one call count per model, busiest first. The billing
+/// API reports no per-model quota, so the counts are plain text lines.
+fn build_usage_lines(usage: &huggingface::HuggingFaceBillingUsage) -> Vec<MetricLine> {
+    let mut lines = Vec::new();
+
+    if let Some(total_cost_usd) = usage.total_cost_usd {
+        lines.push(MetricLine::Text {
+            label: "Monthly Spend".to_string(),
+            value: format!("${:.2}", total_cost_usd.max(0.0)),
+            color: None,
+            subtitle: None,
+        });
+    }
+
+    let mut models = usage
+        .models
+        .iter()
+        .filter_map(|(name, model)| {
+            let calls = model.calls?.max(0.0);
+            let name = name.trim();
+            if name.is_empty() {
+                None
+            } else {
+                Some((name.to_string(), calls))
+            }
+        })
+        .collect::<Vec<_>>();
+    models.sort_by(|left, right| {
+        right.1.total_cmp(&left.1).then_with(|| {
+            left.0
+                .to_ascii_lowercase()
+                .cmp(&right.0.to_ascii_lowercase())
+        })
+    });
+
+    lines.extend(models.into_iter().map(|(name, calls)| MetricLine::Text {
+        label: name,
+        value: format!("{} calls", calls.round() as u64),
+        color: None,
+        subtitle: None,
+    }));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usage_response_becomes_spend_and_call_count_lines() {
+        let usage =
+            serde_json::from_value::<huggingface::HuggingFaceBillingUsage>(serde_json::json!({
+                "total_cost_usd": 3.456,
+                "models": {
+                    "meta-llama/Llama-3.1-8B-Instruct": { "calls": 12, "tokens": 4000 },
+                    "Qwen/Qwen2.5-72B-Instruct": { "calls": 340 },
+                    "  ": { "calls": 5 },
+                    "mistralai/Mistral-7B": { "tokens": 10 }
+                }
+            }))
+            .expect("usage should parse");
+
+        let lines = build_usage_lines(&usage)
+            .into_iter()
+            .map(|line| match line {
+                MetricLine::Text { label, value, .. } => format!("{label}={value}"),
+                other => panic!("unexpected line: {other:?}"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            lines,
+            vec![
+                "Monthly Spend=$3.46",
+                "Qwen/Qwen2.5-72B-Instruct=340 calls",
+                "meta-llama/Llama-3.1-8B-Instruct=12 calls",
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_usage_response_produces_no_lines() {
+        let usage =
+            serde_json::from_value::<huggingface::HuggingFaceBillingUsage>(serde_json::json!({}))
+                .expect("usage should parse");
+
+        assert!(build_usage_lines(&usage).is_empty());
+    }
+}
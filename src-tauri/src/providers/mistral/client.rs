@@ -0,0 +1,141 @@
+use std::sync::OnceLock;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BackendError, Result};
+use crate::providers::common::{
+    format_http_error_with_context, http_status_error, parse_retry_after, request_error,
+    ProviderHttpClient, DEFAULT_USER_AGENT,
+};
+use crate::utils::now_instant;
+
+const DEFAULT_API_BASE: &str = "https://api.mistral.ai";
+const ACCOUNT_PATH: &str = "/v1/account";
+const USAGE_PATH: &str = "/v1/account/usage";
+
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(DEFAULT_USER_AGENT).into_client())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MistralCredentials {
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    #[serde(
+        rename = "apiKey",
+        alias = "api_key",
+        alias = "token",
+        alias = "access_token",
+        alias = "mistralApiKey"
+    )]
+    pub api_key: String,
+    #[serde(
+        rename = "apiBase",
+        alias = "api_base",
+        alias = "baseUrl",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub api_base: Option<String>,
+}
+
+impl MistralCredentials {
+    pub fn with_kind(mut self) -> Self {
+        self.kind = Some("apiKey".to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MistralAccount {
+    #[serde(default, alias = "plan_name", alias = "planName")]
+    pub plan: Option<String>,
+    #[serde(default, alias = "inputTokenLimit")]
+    pub input_token_limit: Option<f64>,
+    #[serde(default, alias = "outputTokenLimit")]
+    pub output_token_limit: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MistralUsage {
+    #[serde(default, alias = "inputTokens", alias = "prompt_tokens")]
+    pub input_tokens: Option<f64>,
+    #[serde(default, alias = "outputTokens", alias = "completion_tokens")]
+    pub output_tokens: Option<f64>,
+    #[serde(default, alias = "periodEnd")]
+    pub period_end: Option<i64>,
+}
+
+pub fn resolve_api_base(api_base: Option<&str>) -> String {
+    api_base
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(DEFAULT_API_BASE)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+pub async fn fetch_account(api_key: &str, api_base: &str) -> Result<MistralAccount> {
+    if api_key.trim().is_empty() {
+        return Err(BackendError::Provider(
+            "Missing Mistral API key".to_string(),
+        ));
+    }
+
+    let url = format!("{api_base}{ACCOUNT_PATH}");
+    let started = now_instant();
+    let response = authorized_get(&url, api_key)
+        .await
+        .map_err(|err| request_error("Mistral account request failed", started, err))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return response.json::<MistralAccount>().await.map_err(|err| {
+            BackendError::Provider(format!("Mistral account decode failed: {err}"))
+        });
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    let message = format_http_error_with_context(
+        "Mistral account request failed",
+        status,
+        &body,
+        "GET",
+        &url,
+    );
+    Err(http_status_error(status, retry_after, message))
+}
+
+pub async fn fetch_usage(api_key: &str, api_base: &str) -> Result<MistralUsage> {
+    let url = format!("{api_base}{USAGE_PATH}");
+    let started = now_instant();
+    let response = authorized_get(&url, api_key)
+        .await
+        .map_err(|err| request_error("Mistral usage request failed", started, err))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return response
+            .json::<MistralUsage>()
+            .await
+            .map_err(|err| BackendError::Provider(format!("Mistral usage decode failed: {err}")));
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    let message =
+        format_http_error_with_context("Mistral usage request failed", status, &body, "GET", &url);
+    Err(http_status_error(status, retry_after, message))
+}
+
+async fn authorized_get(url: &str, api_key: &str) -> reqwest::Result<reqwest::Response> {
+    shared_client()
+        .get(url)
+        .bearer_auth(api_key.trim())
+        .header("accept", "application/json")
+        .send()
+        .await
+}
@@ -0,0 +1,73 @@
+pub mod client;
+pub mod probe;
+
+use crate::models::AccountRecord;
+
+use super::contract::{api_key_provider_contract, ProviderContract};
+use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
+
+pub const CONTRACT: ProviderContract = api_key_provider_contract("mistral", "Mistral AI")
+    .with_documentation_url("https://docs.mistral.ai")
+    .with_dashboard_url("https://console.mistral.ai/usage");
+
+const LINES: [ManifestLineSpec; 2] = [
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Input Tokens",
+        scope: "overview",
+    },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Output Tokens",
+        scope: "overview",
+    },
+];
+
+const PRIMARY_CANDIDATES: [&str; 2] = ["Input Tokens", "Output Tokens"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct MistralRuntime;
+
+pub const RUNTIME: MistralRuntime = MistralRuntime;
+
+impl ProviderRuntime for MistralRuntime {
+    fn id(&self) -> &'static str {
+        CONTRACT.id
+    }
+
+    fn name(&self) -> &'static str {
+        CONTRACT.name
+    }
+
+    fn icon_url(&self) -> &'static str {
+        "/providers/mistral.svg"
+    }
+
+    fn brand_color(&self) -> Option<&'static str> {
+        Some("#FF7000")
+    }
+
+    fn lines(&self) -> &'static [ManifestLineSpec] {
+        &LINES
+    }
+
+    fn primary_candidates(&self) -> &'static [&'static str] {
+        &PRIMARY_CANDIDATES
+    }
+
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    fn dashboard_url(&self) -> Option<&'static str> {
+        CONTRACT.dashboard_url
+    }
+
+    fn probe<'a>(
+        &self,
+        account: &'a AccountRecord,
+        credentials: serde_json::Value,
+    ) -> ProbeFuture<'a> {
+        Box::pin(probe::probe(account, credentials))
+    }
+}
@@ -0,0 +1,131 @@
+use crate::error::{BackendError, Result};
+use crate::models::AccountRecord;
+use crate::providers::usage::{
+    normalize_metric_line, plan_label, read_json_string, status_line, unix_to_rfc3339, MetricLine,
+    ProbeSuccess, ProgressFormat, PERIOD_30_DAYS_MS,
+};
+
+use super::client as mistral;
+
+pub async fn probe(
+    account: &AccountRecord,
+    credentials: serde_json::Value,
+) -> Result<ProbeSuccess> {
+    let mut credentials = serde_json::from_value::<mistral::MistralCredentials>(credentials)
+        .map_err(|err| BackendError::Provider(format!("Invalid Mistral credentials: {err}")))?;
+
+    let mut updated = false;
+    if credentials.kind.as_deref() != Some("apiKey") {
+        credentials.kind = Some("apiKey".to_string());
+        updated = true;
+    }
+
+    if credentials.api_key.trim().is_empty() {
+        if let Some(value) = read_json_string(
+            &account.settings,
+            &[
+                "apiKey",
+                "api_key",
+                "token",
+                "access_token",
+                "mistralApiKey",
+            ],
+        ) {
+            credentials.api_key = value;
+            updated = true;
+        }
+    }
+
+    if credentials
+        .api_base
+        .as_deref()
+        .unwrap_or("")
+        .trim()
+        .is_empty()
+    {
+        if let Some(value) =
+            read_json_string(&account.settings, &["apiBase", "api_base", "baseUrl"])
+        {
+            credentials.api_base = Some(value);
+            updated = true;
+        }
+    }
+
+    let api_base = mistral::resolve_api_base(credentials.api_base.as_deref());
+    let account_info = mistral::fetch_account(&credentials.api_key, &api_base).await?;
+    let usage = mistral::fetch_usage(&credentials.api_key, &api_base).await?;
+
+    let resets_at = usage.period_end.and_then(unix_to_rfc3339);
+    let mut lines = Vec::new();
+    if let Some(line) = build_token_line(
+        "Input Tokens",
+        usage.input_tokens,
+        account_info.input_token_limit,
+        resets_at.clone(),
+    ) {
+        lines.push(line);
+    }
+    if let Some(line) = build_token_line(
+        "Output Tokens",
+        usage.output_tokens,
+        account_info.output_token_limit,
+        resets_at,
+    ) {
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        lines.push(status_line("No usage data"));
+    }
+
+    let plan = account_info
+        .plan
+        .as_deref()
+        .map(plan_label)
+        .filter(|value| !value.is_empty());
+
+    let updated_credentials = if updated {
+        Some(
+            serde_json::to_value(credentials.with_kind()).map_err(|err| {
+                BackendError::Provider(format!("Invalid Mistral credentials: {err}"))
+            })?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ProbeSuccess {
+        plan,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
+        updated_credentials,
+        updated_settings: None,
+    })
+}
+
+fn build_token_line(
+    label: &str,
+    used: Option<f64>,
+    limit: Option<f64>,
+    resets_at: Option<String>,
+) -> Option<MetricLine> {
+    let used = used?.max(0.0);
+    let Some(limit) = limit.filter(|value| *value > 0.0) else {
+        return Some(MetricLine::Text {
+            label: label.to_string(),
+            value: format!("{} tokens", used.round() as u64),
+            color: None,
+            subtitle: None,
+        });
+    };
+    Some(MetricLine::Progress {
+        label: label.to_string(),
+        used,
+        limit,
+        format: ProgressFormat::Count {
+            suffix: "tokens".to_string(),
+        },
+        resets_at,
+        period_duration_ms: Some(PERIOD_30_DAYS_MS),
+        color: None,
+    })
+}
@@ -4,10 +4,18 @@ mod codex;
 pub mod common;
 mod contract;
 mod copilot;
+mod deepseek;
 mod descriptor;
+mod gemini;
+mod groq;
+mod huggingface;
+mod mistral;
+mod openai;
 mod opencode;
+mod perplexity;
 mod registry;
 mod runtime;
+mod together;
 pub mod usage;
 mod validation;
 mod zai;
@@ -18,13 +26,23 @@ pub mod clients {
     pub use super::claude::client as claude;
     pub use super::codex::client as codex;
     pub use super::copilot::client as copilot;
+    pub use super::deepseek::client as deepseek;
+    pub use super::gemini::client as gemini;
+    pub use super::groq::client as groq;
+    pub use super::huggingface::client as huggingface;
+    pub use super::mistral::client as mistral;
+    pub use super::openai::client as openai;
     pub use super::opencode::client as opencode;
+    pub use super::perplexity::client as perplexity;
+    pub use super::together::client as together;
     pub use super::zai::client as zai;
 }
 
 pub use descriptor::ProviderDescriptor;
-pub use registry::{all_provider_descriptors, find_provider_contract};
-pub use runtime::{all_provider_ids, all_provider_meta, find_provider_runtime, ProviderMeta};
+pub use registry::{all_provider_descriptors, find_provider_contract, list_provider_aliases};
+pub use runtime::{
+    all_provider_ids, all_provider_meta, find_provider_runtime, ProviderMeta, ProviderRuntime,
+};
 pub use usage::{MetricLine, ProbeSuccess};
 pub use validation::{validate_auth_strategy_for_provider, validate_provider_settings};
 
@@ -40,6 +58,25 @@ mod tests {
         assert!(find_provider_contract("unknown").is_none());
     }
 
+    #[test]
+    fn provider_aliases_resolve_to_registered_providers() {
+        let provider = find_provider_contract(" Anthropic ").expect("alias should resolve");
+        assert_eq!(provider.id, "claude");
+        assert_eq!(
+            find_provider_contract("gpt").map(|provider| provider.id),
+            Some("codex")
+        );
+        assert!(find_provider_contract("openrouter").is_none());
+        assert!(find_provider_contract("github copilot").is_none());
+
+        for (alias, provider_id) in list_provider_aliases() {
+            assert_eq!(
+                find_provider_contract(&alias).map(|provider| provider.id),
+                Some(provider_id.as_str())
+            );
+        }
+    }
+
     #[test]
     fn descriptors_are_exposed() {
         let providers = all_provider_descriptors();
@@ -49,7 +86,17 @@ mod tests {
         assert!(providers.iter().any(|provider| provider.id == "codex"));
         assert!(providers.iter().any(|provider| provider.id == "copilot"));
         assert!(providers.iter().any(|provider| provider.id == "claude"));
+        assert!(providers.iter().any(|provider| provider.id == "deepseek"));
+        assert!(providers.iter().any(|provider| provider.id == "gemini"));
+        assert!(providers.iter().any(|provider| provider.id == "groq"));
+        assert!(providers
+            .iter()
+            .any(|provider| provider.id == "huggingface"));
+        assert!(providers.iter().any(|provider| provider.id == "mistral"));
+        assert!(providers.iter().any(|provider| provider.id == "openai"));
         assert!(providers.iter().any(|provider| provider.id == "opencode"));
+        assert!(providers.iter().any(|provider| provider.id == "perplexity"));
+        assert!(providers.iter().any(|provider| provider.id == "together"));
         assert!(providers.iter().any(|provider| provider.id == "zai"));
     }
 
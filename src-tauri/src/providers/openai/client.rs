@@ -0,0 +1,133 @@
+use std::sync::OnceLock;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BackendError, Result};
+use crate::providers::common::{
+    format_http_error_with_context, http_status_error, parse_retry_after, ProviderHttpClient,
+    DEFAULT_USER_AGENT,
+};
+
+const SUBSCRIPTION_URL: &str = "https://api.openai.com/v1/dashboard/billing/subscription";
+const USAGE_URL: &str = "https://api.openai.com/v1/usage";
+
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(DEFAULT_USER_AGENT).into_client())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAICredentials {
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    #[serde(
+        rename = "apiKey",
+        alias = "api_key",
+        alias = "token",
+        alias = "access_token",
+        alias = "openaiApiKey"
+    )]
+    pub api_key: String,
+}
+
+impl OpenAICredentials {
+    pub fn with_kind(mut self) -> Self {
+        self.kind = Some("apiKey".to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAISubscription {
+    #[serde(default)]
+    pub hard_limit_usd: Option<f64>,
+    #[serde(default)]
+    pub soft_limit_usd: Option<f64>,
+    #[serde(default)]
+    pub system_hard_limit_usd: Option<f64>,
+    #[serde(default)]
+    pub access_until: Option<i64>,
+    #[serde(default)]
+    pub plan: Option<OpenAIPlan>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAIPlan {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub id: Option<String>,
+}
+
+/// Spend for the requested window; `total_usage` is reported in cents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAIUsage {
+    #[serde(default)]
+    pub total_usage: Option<f64>,
+}
+
+pub async fn fetch_subscription(api_key: &str) -> Result<OpenAISubscription> {
+    if api_key.trim().is_empty() {
+        return Err(BackendError::Provider("Missing OpenAI API key".to_string()));
+    }
+
+    let response = authorized_get(SUBSCRIPTION_URL, api_key)
+        .await
+        .map_err(|err| {
+            BackendError::Provider(format!("OpenAI subscription request failed: {err}"))
+        })?;
+
+    let status = response.status();
+    if status.is_success() {
+        return response.json::<OpenAISubscription>().await.map_err(|err| {
+            BackendError::Provider(format!("OpenAI subscription decode failed: {err}"))
+        });
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    let message = format_http_error_with_context(
+        "OpenAI subscription request failed",
+        status,
+        &body,
+        "GET",
+        SUBSCRIPTION_URL,
+    );
+    Err(http_status_error(status, retry_after, message))
+}
+
+pub async fn fetch_usage(api_key: &str, start_date: &str, end_date: &str) -> Result<OpenAIUsage> {
+    let url = format!("{USAGE_URL}?start_date={start_date}&end_date={end_date}");
+    let response = authorized_get(&url, api_key)
+        .await
+        .map_err(|err| BackendError::Provider(format!("OpenAI usage request failed: {err}")))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return response
+            .json::<OpenAIUsage>()
+            .await
+            .map_err(|err| BackendError::Provider(format!("OpenAI usage decode failed: {err}")));
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    let message = format_http_error_with_context(
+        "OpenAI usage request failed",
+        status,
+        &body,
+        "GET",
+        USAGE_URL,
+    );
+    Err(http_status_error(status, retry_after, message))
+}
+
+async fn authorized_get(url: &str, api_key: &str) -> reqwest::Result<reqwest::Response> {
+    shared_client()
+        .get(url)
+        .bearer_auth(api_key.trim())
+        .header("accept", "application/json")
+        .send()
+        .await
+}
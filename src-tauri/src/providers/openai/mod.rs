@@ -0,0 +1,66 @@
+pub mod client;
+pub mod probe;
+
+use crate::models::AccountRecord;
+
+use super::contract::{api_key_provider_contract, ProviderContract};
+use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
+
+pub const CONTRACT: ProviderContract = api_key_provider_contract("openai", "OpenAI")
+    .with_documentation_url("https://platform.openai.com/docs")
+    .with_dashboard_url("https://platform.openai.com/usage");
+
+const LINES: [ManifestLineSpec; 1] = [ManifestLineSpec {
+    line_type: "progress",
+    label: "Monthly Spend",
+    scope: "overview",
+}];
+
+const PRIMARY_CANDIDATES: [&str; 1] = ["Monthly Spend"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct OpenAIRuntime;
+
+pub const RUNTIME: OpenAIRuntime = OpenAIRuntime;
+
+impl ProviderRuntime for OpenAIRuntime {
+    fn id(&self) -> &'static str {
+        CONTRACT.id
+    }
+
+    fn name(&self) -> &'static str {
+        CONTRACT.name
+    }
+
+    fn icon_url(&self) -> &'static str {
+        "/providers/openai.svg"
+    }
+
+    fn brand_color(&self) -> Option<&'static str> {
+        Some("#10A37F")
+    }
+
+    fn lines(&self) -> &'static [ManifestLineSpec] {
+        &LINES
+    }
+
+    fn primary_candidates(&self) -> &'static [&'static str] {
+        &PRIMARY_CANDIDATES
+    }
+
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    fn dashboard_url(&self) -> Option<&'static str> {
+        CONTRACT.dashboard_url
+    }
+
+    fn probe<'a>(
+        &self,
+        account: &'a AccountRecord,
+        credentials: serde_json::Value,
+    ) -> ProbeFuture<'a> {
+        Box::pin(probe::probe(account, credentials))
+    }
+}
@@ -0,0 +1,187 @@
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+use crate::error::{BackendError, Result};
+use crate::models::AccountRecord;
+use crate::providers::usage::{
+    dollars_from_cents, normalize_metric_line, plan_label, read_json_string, status_line,
+    MetricLine, ProbeSuccess, ProgressFormat, PERIOD_30_DAYS_MS,
+};
+
+use super::client as openai;
+
+pub async fn probe(
+    account: &AccountRecord,
+    credentials: serde_json::Value,
+) -> Result<ProbeSuccess> {
+    let mut credentials = serde_json::from_value::<openai::OpenAICredentials>(credentials)
+        .map_err(|err| BackendError::Provider(format!("Invalid OpenAI credentials: {err}")))?;
+
+    let mut updated = false;
+    if credentials.kind.as_deref() != Some("apiKey") {
+        credentials.kind = Some("apiKey".to_string());
+        updated = true;
+    }
+
+    if credentials.api_key.trim().is_empty() {
+        if let Some(value) = read_json_string(
+            &account.settings,
+            &["apiKey", "api_key", "token", "access_token", "openaiApiKey"],
+        ) {
+            credentials.api_key = value;
+            updated = true;
+        }
+    }
+
+    let today = OffsetDateTime::now_utc().date();
+    let period_start = today.replace_day(1).unwrap_or(today);
+    let period_end = today.next_day().unwrap_or(today);
+
+    let subscription = openai::fetch_subscription(&credentials.api_key).await?;
+    let usage = openai::fetch_usage(
+        &credentials.api_key,
+        &period_start.to_string(),
+        &period_end.to_string(),
+    )
+    .await?;
+
+    let mut lines = Vec::new();
+    if let Some(line) = build_spend_line(&subscription, &usage, next_month_start(today)) {
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        lines.push(status_line("No usage data"));
+    }
+
+    let plan = subscription
+        .plan
+        .as_ref()
+        .and_then(|plan| plan.title.as_deref().or(plan.id.as_deref()))
+        .map(plan_label)
+        .filter(|value| !value.is_empty());
+
+    let updated_credentials = if updated {
+        Some(
+            serde_json::to_value(credentials.with_kind()).map_err(|err| {
+                BackendError::Provider(format!("Invalid OpenAI credentials: {err}"))
+            })?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ProbeSuccess {
+        plan,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
+        updated_credentials,
+        updated_settings: None,
+    })
+}
+
+fn build_spend_line(
+    subscription: &openai::OpenAISubscription,
+    usage: &openai::OpenAIUsage,
+    resets_at: Option<String>,
+) -> Option<MetricLine> {
+    let spent = dollars_from_cents(usage.total_usage?.max(0.0));
+    let hard_limit = subscription
+        .hard_limit_usd
+        .or(subscription.system_hard_limit_usd)
+        .filter(|value| *value > 0.0);
+
+    let Some(limit) = hard_limit else {
+        return Some(MetricLine::Text {
+            label: "Monthly Spend".to_string(),
+            value: format!("${spent:.2}"),
+            color: None,
+            subtitle: None,
+        });
+    };
+
+    Some(MetricLine::Progress {
+        label: "Monthly Spend".to_string(),
+        used: spent,
+        limit,
+        format: ProgressFormat::dollars(),
+        resets_at,
+        period_duration_ms: Some(PERIOD_30_DAYS_MS),
+        color: None,
+    })
+}
+
+fn next_month_start(today: Date) -> Option<String> {
+    let (year, month) = match today.month().next() {
+        time::Month::January => (today.year() + 1, time::Month::January),
+        month => (today.year(), month),
+    };
+    let date = Date::from_calendar_date(year, month, 1).ok()?;
+    PrimitiveDateTime::new(date, Time::MIDNIGHT)
+        .assume_utc()
+        .format(&Rfc3339)
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscription_and_usage_build_dollar_progress_line() {
+        let subscription =
+            serde_json::from_value::<openai::OpenAISubscription>(serde_json::json!({
+                "object": "billing_subscription",
+                "has_payment_method": true,
+                "soft_limit_usd": 80.0,
+                "hard_limit_usd": 120.0,
+                "system_hard_limit_usd": 120.0,
+                "access_until": 1767225600,
+                "plan": { "title": "Pay-as-you-go", "id": "payg" }
+            }))
+            .expect("subscription should parse");
+        let usage = serde_json::from_value::<openai::OpenAIUsage>(serde_json::json!({
+            "object": "list",
+            "daily_costs": [],
+            "total_usage": 4250.0
+        }))
+        .expect("usage should parse");
+
+        let line = build_spend_line(&subscription, &usage, None).expect("line should be built");
+
+        match line {
+            MetricLine::Progress {
+                used,
+                limit,
+                format: ProgressFormat::Currency { symbol, decimals },
+                ..
+            } => {
+                assert_eq!(symbol, "$");
+                assert_eq!(decimals, 2);
+                assert_eq!(used, 42.5);
+                assert_eq!(limit, 120.0);
+            }
+            other => panic!("unexpected line: {other:?}"),
+        }
+        assert_eq!(
+            subscription.plan.and_then(|plan| plan.title).as_deref(),
+            Some("Pay-as-you-go")
+        );
+    }
+
+    #[test]
+    fn missing_hard_limit_falls_back_to_text_spend() {
+        let subscription =
+            serde_json::from_value::<openai::OpenAISubscription>(serde_json::json!({}))
+                .expect("empty subscription should parse");
+        let usage =
+            serde_json::from_value::<openai::OpenAIUsage>(serde_json::json!({"total_usage": 199}))
+                .expect("usage should parse");
+
+        let line = build_spend_line(&subscription, &usage, None).expect("line should be built");
+
+        match line {
+            MetricLine::Text { value, .. } => assert_eq!(value, "$1.99"),
+            other => panic!("unexpected line: {other:?}"),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::sync::OnceLock;
+use std::time::UNIX_EPOCH;
 
 use regex::Regex;
 use reqwest::Client;
@@ -8,20 +9,45 @@ use url::Url;
 use uuid::Uuid;
 
 use crate::error::{BackendError, Result};
-use crate::providers::common::{format_http_error, shorten_body};
+use crate::providers::common::{
+    format_http_error, http_status_error, parse_retry_after, request_error, shorten_body,
+    ProviderHttpClient,
+};
+use crate::utils::{mask_workspace_id, now_instant, now_unix_ms};
 
 const BASE_URL: &str = "https://opencode.ai";
 const SERVER_URL: &str = "https://opencode.ai/_server";
+const WORKSPACES_URL: &str = "https://opencode.ai/api/workspaces";
 const USAGE_SERVER_ID: &str = "bbb1284bc5442ffc92d7d2ef43d0bae818b6a859d848d631e9fa8d26cf77b56c";
 const USER_AGENT: &str =
     "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/143.0.0.0 Safari/537.36";
 
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(USER_AGENT).into_client())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OpenCodeCredentials {
     #[serde(rename = "type", default)]
     pub kind: Option<String>,
     #[serde(rename = "cookieHeader", alias = "cookie_header", alias = "cookie")]
     pub cookie_header: String,
+    #[serde(
+        rename = "workspaceId",
+        alias = "workspace_id",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub workspace_id: Option<String>,
+    /// Unix milliseconds at which the auth cookie expires, when known.
+    #[serde(
+        rename = "expiresAt",
+        alias = "expires_at",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expires_at: Option<i64>,
 }
 
 impl OpenCodeCredentials {
@@ -31,9 +57,13 @@ impl OpenCodeCredentials {
     }
 }
 
+/// Builds the `Cookie` header from captured cookie pairs. `cookie_attributes`
+/// holds the auth cookie's `Set-Cookie` attributes (`Max-Age=...; Expires=...`)
+/// and yields the expiry in unix milliseconds when present.
 pub fn cookie_header_from_pairs<'a>(
     pairs: impl IntoIterator<Item = (&'a str, &'a str)>,
-) -> Option<String> {
+    cookie_attributes: &str,
+) -> Option<(String, Option<i64>)> {
     let mut collected = Vec::new();
     let mut has_auth = false;
 
@@ -55,7 +85,36 @@ pub fn cookie_header_from_pairs<'a>(
         return None;
     }
 
-    Some(collected.join("; "))
+    Some((
+        collected.join("; "),
+        cookie_expires_at(cookie_attributes, now_unix_ms()),
+    ))
+}
+
+/// `Max-Age` wins over `Expires`, as in RFC 6265.
+fn cookie_expires_at(cookie_attributes: &str, now_ms: i64) -> Option<i64> {
+    let mut expires = None;
+    for attribute in cookie_attributes.split(';') {
+        let Some((name, value)) = attribute.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "max-age" => {
+                if let Ok(seconds) = value.parse::<i64>() {
+                    return Some(now_ms.saturating_add(seconds.saturating_mul(1000)));
+                }
+            }
+            "expires" => {
+                expires = httpdate::parse_http_date(value)
+                    .ok()
+                    .and_then(|deadline| deadline.duration_since(UNIX_EPOCH).ok())
+                    .and_then(|elapsed| i64::try_from(elapsed.as_millis()).ok());
+            }
+            _ => {}
+        }
+    }
+    expires
 }
 
 #[derive(Debug, Clone)]
@@ -104,32 +163,107 @@ pub async fn fetch_usage(
         has_auth_cookie
     );
 
-    let client = Client::new();
-    let payload = fetch_usage_text(&client, &workspace_id, cookie_header).await?;
+    let client = shared_client();
+    let payload = fetch_usage_text(client, &workspace_id, cookie_header).await?;
     parse_usage_text(&payload, &workspace_id)
 }
 
+pub async fn discover_workspace_id(cookie_header: &str) -> Result<String> {
+    let cookie_header = cookie_header.trim();
+    if cookie_header.is_empty() {
+        return Err(BackendError::Provider(
+            "OpenCode session cookie is invalid or expired.".to_string(),
+        ));
+    }
+
+    let started = now_instant();
+    let response = shared_client()
+        .get(WORKSPACES_URL)
+        .header("Cookie", cookie_header)
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|err| request_error("OpenCode network error", started, err))?;
+
+    let status = response.status();
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    if !status.is_success() {
+        log::warn!(
+            "[opencode] workspace discovery failed status={} body_hint={}",
+            status,
+            body_hint(&body)
+        );
+        return Err(http_status_error(
+            status,
+            retry_after,
+            format_http_error("OpenCode workspace discovery failed", status, &body),
+        ));
+    }
+
+    let payload = serde_json::from_str::<serde_json::Value>(&body).map_err(|err| {
+        BackendError::Provider(format!("OpenCode workspace discovery decode failed: {err}"))
+    })?;
+    let workspace_id = first_workspace_id(&payload).ok_or_else(|| {
+        BackendError::Provider("OpenCode workspace discovery returned no workspaces".to_string())
+    })?;
+
+    log::info!(
+        "[opencode] discovered workspace workspace_id={}",
+        mask_workspace_id(&workspace_id)
+    );
+    Ok(workspace_id)
+}
+
+fn first_workspace_id(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(text) => normalize_workspace_id(Some(text)),
+        serde_json::Value::Array(items) => items.iter().find_map(first_workspace_id),
+        serde_json::Value::Object(object) => ["id", "workspaceId", "workspace_id"]
+            .iter()
+            .filter_map(|key| object.get(*key))
+            .find_map(first_workspace_id)
+            .or_else(|| {
+                ["workspaces", "data", "items"]
+                    .iter()
+                    .filter_map(|key| object.get(*key))
+                    .find_map(first_workspace_id)
+            }),
+        _ => None,
+    }
+}
+
 pub fn normalize_workspace_id(raw: Option<&str>) -> Option<String> {
     let raw = raw?.trim();
     if raw.is_empty() {
         return None;
     }
 
-    if raw.starts_with("wrk_") && raw.len() > 4 {
+    if is_workspace_id(raw) {
         return Some(raw.to_string());
     }
 
     if let Ok(url) = Url::parse(raw) {
-        let mut parts = url.path_segments()?.collect::<Vec<_>>();
-        for idx in 0..parts.len() {
-            if parts[idx] != "workspace" {
-                continue;
-            }
+        let parts = url
+            .path_segments()
+            .map(|segments| segments.collect::<Vec<_>>())
+            .unwrap_or_default();
+        let from_path = parts
+            .windows(2)
+            .filter(|pair| pair[0] == "workspace")
+            .map(|pair| pair[1].trim())
+            .find(|candidate| is_workspace_id(candidate));
+        if let Some(candidate) = from_path {
+            return Some(candidate.to_string());
+        }
 
-            let candidate = parts.get_mut(idx + 1).map(|value| value.trim())?;
-            if candidate.starts_with("wrk_") && candidate.len() > 4 {
-                return Some(candidate.to_string());
-            }
+        let from_query = url.query_pairs().find_map(|(key, value)| {
+            let value = value.trim();
+            (matches!(key.as_ref(), "workspace" | "workspaceId" | "w") && is_workspace_id(value))
+                .then(|| value.to_string())
+        });
+        if from_query.is_some() {
+            return from_query;
         }
     }
 
@@ -138,6 +272,52 @@ pub fn normalize_workspace_id(raw: Option<&str>) -> Option<String> {
         .map(|value| value.as_str().to_string())
 }
 
+fn is_workspace_id(value: &str) -> bool {
+    value.starts_with("wrk_") && value.len() > 4
+}
+
+/// Workspaces remembered in account settings: `workspaceIds` plus the active
+/// `workspaceId` for accounts saved before the list existed.
+pub fn workspace_ids(settings: &serde_json::Value) -> Vec<String> {
+    let mut ids = settings
+        .get("workspaceIds")
+        .and_then(|value| value.as_array())
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|value| normalize_workspace_id(value.as_str()))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if let Some(active) =
+        normalize_workspace_id(settings.get("workspaceId").and_then(|value| value.as_str()))
+    {
+        ids.push(active);
+    }
+    let mut seen = std::collections::HashSet::new();
+    ids.retain(|id| seen.insert(id.clone()));
+    ids
+}
+
+/// Makes `workspace_id` the active workspace and appends it to `workspaceIds`.
+/// Returns whether the settings changed.
+pub fn remember_workspace(
+    settings: &mut serde_json::Map<String, serde_json::Value>,
+    workspace_id: &str,
+) -> bool {
+    let mut ids = workspace_ids(&serde_json::Value::Object(settings.clone()));
+    if !ids.iter().any(|id| id == workspace_id) {
+        ids.push(workspace_id.to_string());
+    }
+    let ids = serde_json::json!(ids);
+    let active = serde_json::Value::String(workspace_id.to_string());
+    let changed =
+        settings.get("workspaceIds") != Some(&ids) || settings.get("workspaceId") != Some(&active);
+    settings.insert("workspaceIds".to_string(), ids);
+    settings.insert("workspaceId".to_string(), active);
+    changed
+}
+
 async fn fetch_usage_text(
     client: &Client,
     workspace_id: &str,
@@ -255,12 +435,12 @@ async fn fetch_server_text(
         .server_instance
         .unwrap_or_else(|| format!("server-fn:{}", Uuid::new_v4()));
 
+    let started = now_instant();
     let response = client
         .post(SERVER_URL)
         .header("Cookie", cookie_header)
         .header("X-Server-Id", request.server_id)
         .header("X-Server-Instance", server_instance)
-        .header("User-Agent", USER_AGENT)
         .header("Origin", BASE_URL)
         .header("Referer", request.referer)
         .header(
@@ -271,7 +451,7 @@ async fn fetch_server_text(
         .json(&request.args)
         .send()
         .await
-        .map_err(|err| BackendError::Provider(format!("OpenCode network error: {err}")))?;
+        .map_err(|err| request_error("OpenCode network error", started, err))?;
 
     let status = response.status();
     let content_type = response
@@ -280,6 +460,7 @@ async fn fetch_server_text(
         .and_then(|value| value.to_str().ok())
         .unwrap_or("unknown")
         .to_string();
+    let retry_after = parse_retry_after(response.headers());
     let body = response.text().await.unwrap_or_else(|_| "".to_string());
 
     if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
@@ -290,9 +471,10 @@ async fn fetch_server_text(
             content_type,
             body_hint(&body)
         );
-        return Err(BackendError::Provider(
-            "OpenCode session cookie is invalid or expired.".to_string(),
-        ));
+        return Err(BackendError::AuthExpired {
+            account_id: None,
+            message: "OpenCode session cookie is invalid or expired.".to_string(),
+        });
     }
 
     if !status.is_success() {
@@ -313,15 +495,15 @@ async fn fetch_server_text(
 
         if let Some(message) = extract_server_error_message(&body) {
             return Err(BackendError::Provider(format!(
-                "OpenCode API error: HTTP {status} - {message}"
+                "OpenCode API error: HTTP {status} — {message}"
             )));
         }
 
-        return Err(BackendError::Provider(format_http_error(
-            "OpenCode API error",
+        return Err(http_status_error(
             status,
-            &body,
-        )));
+            retry_after,
+            format_http_error("OpenCode API error", status, &body),
+        ));
     }
 
     log::info!(
@@ -344,7 +526,7 @@ async fn fetch_server_text(
 
 fn workspace_id_regex() -> &'static Regex {
     static REGEX: OnceLock<Regex> = OnceLock::new();
-    REGEX.get_or_init(|| Regex::new(r"wrk_[A-Za-z0-9]+$").expect("workspace regex should compile"))
+    REGEX.get_or_init(|| Regex::new(r"wrk_[A-Za-z0-9_-]+").expect("workspace regex should compile"))
 }
 
 fn rolling_usage_percent_regex() -> &'static Regex {
@@ -547,16 +729,6 @@ fn body_hint(text: &str) -> &'static str {
     }
 }
 
-fn mask_workspace_id(workspace_id: &str) -> String {
-    let visible_tail_len = 6;
-    if workspace_id.len() <= visible_tail_len {
-        return workspace_id.to_string();
-    }
-
-    let tail = &workspace_id[workspace_id.len() - visible_tail_len..];
-    format!("***{tail}")
-}
-
 fn log_parse_summary(text: &str) {
     if let Ok(value) = serde_json::from_str::<serde_json::Value>(text) {
         let summary = summarize_json(&value, 0);
@@ -624,3 +796,106 @@ fn scalar_type_description(value: &serde_json::Value) -> &'static str {
         _ => "value",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_client_reuses_one_pool_across_calls() {
+        assert!(std::ptr::eq(shared_client(), shared_client()));
+    }
+
+    #[test]
+    fn normalize_workspace_id_accepts_bare_id() {
+        assert_eq!(
+            normalize_workspace_id(Some("wrk_abc123")).as_deref(),
+            Some("wrk_abc123")
+        );
+    }
+
+    #[test]
+    fn normalize_workspace_id_extracts_from_workspace_url() {
+        assert_eq!(
+            normalize_workspace_id(Some("https://opencode.ai/workspace/wrk_abc-def")).as_deref(),
+            Some("wrk_abc-def")
+        );
+    }
+
+    #[test]
+    fn normalize_workspace_id_extracts_from_hash_route() {
+        assert_eq!(
+            normalize_workspace_id(Some("https://opencode.ai/#/workspace/wrk_xyz_789")).as_deref(),
+            Some("wrk_xyz_789")
+        );
+    }
+
+    #[test]
+    fn cookie_expiry_prefers_max_age_over_expires() {
+        let now = 1_800_000_000_000;
+        assert_eq!(
+            cookie_expires_at(
+                "Path=/; Expires=Wed, 21 Oct 2015 07:28:00 GMT; Max-Age=60",
+                now
+            ),
+            Some(now + 60_000)
+        );
+        assert_eq!(
+            cookie_expires_at("Expires=Wed, 21 Oct 2015 07:28:00 GMT", now),
+            Some(1_445_412_480_000)
+        );
+        assert_eq!(cookie_expires_at("Path=/; HttpOnly", now), None);
+    }
+
+    #[test]
+    fn remember_workspace_appends_without_duplicates() {
+        let mut settings = serde_json::json!({ "workspaceId": "wrk_first" })
+            .as_object()
+            .cloned()
+            .expect("settings should be an object");
+
+        assert!(remember_workspace(&mut settings, "wrk_second"));
+        assert!(remember_workspace(&mut settings, "wrk_first"));
+        assert!(!remember_workspace(&mut settings, "wrk_first"));
+
+        let settings = serde_json::Value::Object(settings);
+        assert_eq!(workspace_ids(&settings), vec!["wrk_first", "wrk_second"]);
+        assert_eq!(settings["workspaceId"], "wrk_first");
+    }
+
+    #[test]
+    fn normalize_workspace_id_extracts_from_query_parameter() {
+        assert_eq!(
+            normalize_workspace_id(Some("https://opencode.ai/auth?workspace=wrk_abc123"))
+                .as_deref(),
+            Some("wrk_abc123")
+        );
+        assert_eq!(
+            normalize_workspace_id(Some("https://opencode.ai/auth?next=%2F&w=wrk_q1")).as_deref(),
+            Some("wrk_q1")
+        );
+    }
+
+    #[test]
+    fn normalize_workspace_id_prefers_path_over_query() {
+        assert_eq!(
+            normalize_workspace_id(Some(
+                "https://opencode.ai/workspace/wrk_path?workspaceId=wrk_query"
+            ))
+            .as_deref(),
+            Some("wrk_path")
+        );
+    }
+
+    #[test]
+    fn normalize_workspace_id_rejects_empty_input() {
+        assert_eq!(normalize_workspace_id(Some("")), None);
+        assert_eq!(normalize_workspace_id(Some("   ")), None);
+        assert_eq!(normalize_workspace_id(None), None);
+    }
+
+    #[test]
+    fn normalize_workspace_id_rejects_unrelated_text() {
+        assert_eq!(normalize_workspace_id(Some("not-a-workspace-id")), None);
+    }
+}
@@ -6,7 +6,8 @@ use crate::models::AccountRecord;
 use super::contract::{cookie_provider_contract, ProviderContract};
 use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
 
-pub const CONTRACT: ProviderContract = cookie_provider_contract("opencode", "OpenCode");
+pub const CONTRACT: ProviderContract = cookie_provider_contract("opencode", "OpenCode")
+    .with_documentation_url("https://opencode.ai/docs");
 
 const LINES: [ManifestLineSpec; 4] = [
     ManifestLineSpec {
@@ -63,6 +64,20 @@ impl ProviderRuntime for OpencodeRuntime {
         &PRIMARY_CANDIDATES
     }
 
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    // The login window and session cookie are shared, so a second account
+    // would only mirror the first.
+    fn supports_multiple_accounts(&self) -> bool {
+        false
+    }
+
+    fn colors_usage(&self) -> bool {
+        true
+    }
+
     fn probe<'a>(
         &self,
         account: &'a AccountRecord,
@@ -1,8 +1,8 @@
 use crate::error::{BackendError, Result};
 use crate::models::AccountRecord;
 use crate::providers::usage::{
-    plan_label, read_json_string, status_line, unix_to_rfc3339, MetricLine, ProbeSuccess,
-    PERIOD_5_HOURS_MS, PERIOD_7_DAYS_MS,
+    normalize_metric_line, plan_label, read_json_string, status_line, unix_to_rfc3339, MetricLine,
+    ProbeSuccess, PERIOD_5_HOURS_MS, PERIOD_7_DAYS_MS,
 };
 use crate::utils::now_unix_ms;
 
@@ -39,16 +39,41 @@ pub async fn probe(
         ));
     }
 
-    let workspace_override = read_json_string(
+    if credentials
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= now_unix_ms())
+    {
+        return Err(BackendError::AuthExpired {
+            account_id: Some(account.id.clone()),
+            message: "OpenCode session cookie has expired. Reconnect OpenCode.".to_string(),
+        });
+    }
+
+    let workspace_from_settings = read_json_string(
         &account.settings,
         &["workspaceId", "workspace_id", "workspace"],
     )
-    .and_then(|value| opencode::normalize_workspace_id(Some(&value)))
-    .ok_or_else(|| {
-        BackendError::Provider(
-            "OpenCode workspaceId is missing in account settings. Reconnect OpenCode.".to_string(),
-        )
-    })?;
+    .and_then(|value| opencode::normalize_workspace_id(Some(&value)));
+
+    let workspace_override = match workspace_from_settings
+        .or_else(|| opencode::normalize_workspace_id(credentials.workspace_id.as_deref()))
+    {
+        Some(workspace_id) => workspace_id,
+        None => match opencode::discover_workspace_id(&credentials.cookie_header).await {
+            Ok(workspace_id) => {
+                credentials.workspace_id = Some(workspace_id.clone());
+                updated = true;
+                workspace_id
+            }
+            Err(err) => {
+                log::warn!("[opencode] workspace fallback discovery failed: {err}");
+                return Err(BackendError::Provider(
+                    "OpenCode workspaceId is missing in account settings. Reconnect OpenCode."
+                        .to_string(),
+                ));
+            }
+        },
+    };
 
     let snapshot =
         opencode::fetch_usage(&credentials.cookie_header, Some(&workspace_override)).await?;
@@ -130,7 +155,8 @@ pub async fn probe(
 
     Ok(ProbeSuccess {
         plan,
-        lines,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
         updated_credentials,
+        updated_settings: None,
     })
 }
@@ -0,0 +1,94 @@
+use std::sync::OnceLock;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BackendError, Result};
+use crate::providers::common::{
+    format_http_error_with_context, http_status_error, parse_retry_after, request_error,
+    ProviderHttpClient, DEFAULT_USER_AGENT,
+};
+use crate::utils::now_instant;
+
+const USAGE_URL: &str = "https://api.perplexity.ai/usage";
+
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(DEFAULT_USER_AGENT).into_client())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerplexityCredentials {
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    #[serde(
+        rename = "apiKey",
+        alias = "api_key",
+        alias = "token",
+        alias = "access_token",
+        alias = "perplexityApiKey"
+    )]
+    pub api_key: String,
+}
+
+impl PerplexityCredentials {
+    pub fn with_kind(mut self) -> Self {
+        self.kind = Some("apiKey".to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerplexityUsage {
+    #[serde(default)]
+    pub plan: Option<String>,
+    #[serde(default, alias = "periodEnd")]
+    pub period_end: Option<i64>,
+    #[serde(default, alias = "searchRequests")]
+    pub search_requests: Option<PerplexityUsageCounter>,
+    #[serde(default, alias = "reasoningTokens")]
+    pub reasoning_tokens: Option<PerplexityUsageCounter>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerplexityUsageCounter {
+    #[serde(default)]
+    pub used: Option<f64>,
+    #[serde(default)]
+    pub limit: Option<f64>,
+}
+
+pub async fn fetch_usage(api_key: &str) -> Result<PerplexityUsage> {
+    if api_key.trim().is_empty() {
+        return Err(BackendError::Provider(
+            "Missing Perplexity API key".to_string(),
+        ));
+    }
+
+    let started = now_instant();
+    let response = shared_client()
+        .get(USAGE_URL)
+        .bearer_auth(api_key.trim())
+        .header("accept", "application/json")
+        .send()
+        .await
+        .map_err(|err| request_error("Perplexity usage request failed", started, err))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return response.json::<PerplexityUsage>().await.map_err(|err| {
+            BackendError::Provider(format!("Perplexity usage decode failed: {err}"))
+        });
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    let message = format_http_error_with_context(
+        "Perplexity usage request failed",
+        status,
+        &body,
+        "GET",
+        USAGE_URL,
+    );
+    Err(http_status_error(status, retry_after, message))
+}
@@ -0,0 +1,73 @@
+pub mod client;
+pub mod probe;
+
+use crate::models::AccountRecord;
+
+use super::contract::{api_key_provider_contract, ProviderContract};
+use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
+
+pub const CONTRACT: ProviderContract = api_key_provider_contract("perplexity", "Perplexity")
+    .with_documentation_url("https://docs.perplexity.ai")
+    .with_dashboard_url("https://www.perplexity.ai/account/api/billing");
+
+const LINES: [ManifestLineSpec; 2] = [
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Search requests",
+        scope: "overview",
+    },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Reasoning tokens",
+        scope: "overview",
+    },
+];
+
+const PRIMARY_CANDIDATES: [&str; 1] = ["Search requests"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct PerplexityRuntime;
+
+pub const RUNTIME: PerplexityRuntime = PerplexityRuntime;
+
+impl ProviderRuntime for PerplexityRuntime {
+    fn id(&self) -> &'static str {
+        CONTRACT.id
+    }
+
+    fn name(&self) -> &'static str {
+        CONTRACT.name
+    }
+
+    fn icon_url(&self) -> &'static str {
+        "/providers/perplexity.svg"
+    }
+
+    fn brand_color(&self) -> Option<&'static str> {
+        Some("#20808D")
+    }
+
+    fn lines(&self) -> &'static [ManifestLineSpec] {
+        &LINES
+    }
+
+    fn primary_candidates(&self) -> &'static [&'static str] {
+        &PRIMARY_CANDIDATES
+    }
+
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    fn dashboard_url(&self) -> Option<&'static str> {
+        CONTRACT.dashboard_url
+    }
+
+    fn probe<'a>(
+        &self,
+        account: &'a AccountRecord,
+        credentials: serde_json::Value,
+    ) -> ProbeFuture<'a> {
+        Box::pin(probe::probe(account, credentials))
+    }
+}
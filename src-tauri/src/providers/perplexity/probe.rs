@@ -0,0 +1,107 @@
+use crate::error::{BackendError, Result};
+use crate::models::AccountRecord;
+use crate::providers::usage::{
+    normalize_metric_line, plan_label, read_json_string, status_line, unix_to_rfc3339, MetricLine,
+    ProbeSuccess, ProgressFormat, PERIOD_30_DAYS_MS,
+};
+
+use super::client as perplexity;
+
+pub async fn probe(
+    account: &AccountRecord,
+    credentials: serde_json::Value,
+) -> Result<ProbeSuccess> {
+    let mut credentials = serde_json::from_value::<perplexity::PerplexityCredentials>(credentials)
+        .map_err(|err| BackendError::Provider(format!("Invalid Perplexity credentials: {err}")))?;
+
+    let mut updated = false;
+    if credentials.kind.as_deref() != Some("apiKey") {
+        credentials.kind = Some("apiKey".to_string());
+        updated = true;
+    }
+
+    if credentials.api_key.trim().is_empty() {
+        if let Some(value) = read_json_string(
+            &account.settings,
+            &[
+                "apiKey",
+                "api_key",
+                "token",
+                "access_token",
+                "perplexityApiKey",
+            ],
+        ) {
+            credentials.api_key = value;
+            updated = true;
+        }
+    }
+
+    let usage = perplexity::fetch_usage(&credentials.api_key).await?;
+    let resets_at = usage.period_end.and_then(unix_to_rfc3339);
+    let mut lines = Vec::new();
+
+    if let Some(line) = build_counter_line(
+        "Search requests",
+        "requests",
+        usage.search_requests.as_ref(),
+        resets_at.clone(),
+    ) {
+        lines.push(line);
+    }
+    if let Some(line) = build_counter_line(
+        "Reasoning tokens",
+        "tokens",
+        usage.reasoning_tokens.as_ref(),
+        resets_at,
+    ) {
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        lines.push(status_line("No usage data"));
+    }
+
+    let plan = usage
+        .plan
+        .as_deref()
+        .map(plan_label)
+        .filter(|value| !value.is_empty());
+
+    let updated_credentials = if updated {
+        Some(
+            serde_json::to_value(credentials.with_kind()).map_err(|err| {
+                BackendError::Provider(format!("Invalid Perplexity credentials: {err}"))
+            })?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ProbeSuccess {
+        plan,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
+        updated_credentials,
+        updated_settings: None,
+    })
+}
+
+fn build_counter_line(
+    label: &str,
+    suffix: &str,
+    counter: Option<&perplexity::PerplexityUsageCounter>,
+    resets_at: Option<String>,
+) -> Option<MetricLine> {
+    let counter = counter?;
+    let limit = counter.limit.filter(|value| *value > 0.0)?;
+    Some(MetricLine::Progress {
+        label: label.to_string(),
+        used: counter.used.unwrap_or(0.0).max(0.0),
+        limit,
+        format: ProgressFormat::Count {
+            suffix: suffix.to_string(),
+        },
+        resets_at,
+        period_duration_ms: Some(PERIOD_30_DAYS_MS),
+        color: None,
+    })
+}
@@ -1,21 +1,64 @@
 use super::contract::ProviderContract;
 use super::descriptor::ProviderDescriptor;
-use super::{antigravity, claude, codex, copilot, opencode, zai};
+use super::{
+    antigravity, claude, codex, copilot, deepseek, gemini, groq, huggingface, mistral, openai,
+    opencode, perplexity, together, zai,
+};
 
-const PROVIDERS: [ProviderContract; 6] = [
+const PROVIDERS: [ProviderContract; 14] = [
     antigravity::CONTRACT,
     codex::CONTRACT,
     copilot::CONTRACT,
     claude::CONTRACT,
+    deepseek::CONTRACT,
+    gemini::CONTRACT,
+    groq::CONTRACT,
+    huggingface::CONTRACT,
+    mistral::CONTRACT,
+    openai::CONTRACT,
     opencode::CONTRACT,
+    perplexity::CONTRACT,
+    together::CONTRACT,
     zai::CONTRACT,
 ];
 
+/// Alternate names accepted by `find_provider_contract`, as `(alias, providerId)`.
+pub const PROVIDER_ALIASES: &[(&str, &str)] = &[
+    ("gpt", "codex"),
+    ("anthropic", "claude"),
+    ("github-copilot", "copilot"),
+    ("google", "antigravity"),
+];
+
 pub fn all_provider_descriptors() -> Vec<ProviderDescriptor> {
     PROVIDERS.iter().map(ProviderContract::descriptor).collect()
 }
 
 pub fn find_provider_contract(provider_id: &str) -> Option<&'static ProviderContract> {
-    let provider_id = provider_id.trim().to_ascii_lowercase();
-    PROVIDERS.iter().find(|provider| provider.id == provider_id)
+    let provider_id = normalize_provider_lookup(provider_id);
+    PROVIDERS
+        .iter()
+        .find(|provider| provider.id == provider_id)
+        .or_else(|| {
+            let (_, target) = PROVIDER_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == provider_id)?;
+            PROVIDERS.iter().find(|provider| provider.id == *target)
+        })
+}
+
+pub fn list_provider_aliases() -> Vec<(String, String)> {
+    PROVIDER_ALIASES
+        .iter()
+        .map(|(alias, provider_id)| (alias.to_string(), provider_id.to_string()))
+        .collect()
+}
+
+/// Trims, lowercases and collapses interior whitespace runs to one space.
+fn normalize_provider_lookup(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_ascii_lowercase()
 }
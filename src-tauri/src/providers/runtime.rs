@@ -3,13 +3,25 @@ use std::pin::Pin;
 
 use serde::Serialize;
 
-use crate::error::Result;
+use crate::error::{BackendError, Result};
 use crate::models::AccountRecord;
 
-use super::usage::ProbeSuccess;
-use super::{antigravity, claude, codex, copilot, opencode, zai};
+use super::usage::{MetricLine, ProbeSuccess};
+use super::{
+    antigravity, claude, codex, copilot, deepseek, gemini, groq, huggingface, mistral, openai,
+    opencode, perplexity, together, zai,
+};
 
 pub type ProbeFuture<'a> = Pin<Box<dyn Future<Output = Result<ProbeSuccess>> + Send + 'a>>;
+pub type RefreshFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<RefreshedCredentials>> + Send + 'a>>;
+
+#[derive(Debug, Clone)]
+pub struct RefreshedCredentials {
+    pub credentials: serde_json::Value,
+    pub expires_at: i64,
+    pub updated_settings: Option<serde_json::Value>,
+}
 
 #[derive(Debug, Clone, Copy)]
 pub struct ManifestLineSpec {
@@ -36,6 +48,8 @@ pub struct ProviderMeta {
     pub brand_color: Option<String>,
     pub lines: Vec<ManifestLineDto>,
     pub primary_candidates: Vec<String>,
+    pub documentation_url: Option<String>,
+    pub dashboard_url: Option<String>,
 }
 
 pub trait ProviderRuntime: Sync {
@@ -45,19 +59,67 @@ pub trait ProviderRuntime: Sync {
     fn brand_color(&self) -> Option<&'static str>;
     fn lines(&self) -> &'static [ManifestLineSpec];
     fn primary_candidates(&self) -> &'static [&'static str];
+
+    fn documentation_url(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn dashboard_url(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// `false` limits the provider to a single account.
+    fn supports_multiple_accounts(&self) -> bool {
+        true
+    }
+
+    /// Upper bound on accounts for providers that allow several; `None` is
+    /// unlimited.
+    fn max_accounts(&self) -> Option<usize> {
+        None
+    }
+
+    /// `true` when progress lines are colored by the usage warn/critical
+    /// thresholds.
+    fn colors_usage(&self) -> bool {
+        false
+    }
+
     fn probe<'a>(
         &self,
         account: &'a AccountRecord,
         credentials: serde_json::Value,
     ) -> ProbeFuture<'a>;
+
+    /// Forces an OAuth token refresh. Providers without refreshable credentials
+    /// keep this default.
+    fn refresh_credentials<'a>(
+        &self,
+        _account: &'a AccountRecord,
+        _credentials: serde_json::Value,
+    ) -> RefreshFuture<'a> {
+        Box::pin(async {
+            Err(BackendError::Provider(
+                "provider does not support credential refresh".to_string(),
+            ))
+        })
+    }
 }
 
-const RUNTIMES: [&dyn ProviderRuntime; 6] = [
+const RUNTIMES: [&dyn ProviderRuntime; 14] = [
     &antigravity::RUNTIME,
     &codex::RUNTIME,
     &copilot::RUNTIME,
     &claude::RUNTIME,
+    &deepseek::RUNTIME,
+    &gemini::RUNTIME,
+    &groq::RUNTIME,
+    &huggingface::RUNTIME,
+    &mistral::RUNTIME,
+    &openai::RUNTIME,
     &opencode::RUNTIME,
+    &perplexity::RUNTIME,
+    &together::RUNTIME,
     &zai::RUNTIME,
 ];
 
@@ -83,6 +145,8 @@ pub fn all_provider_meta() -> Vec<ProviderMeta> {
                 .iter()
                 .map(|label| label.to_string())
                 .collect(),
+            documentation_url: runtime.documentation_url().map(str::to_string),
+            dashboard_url: runtime.dashboard_url().map(str::to_string),
         })
         .collect()
 }
@@ -101,3 +165,86 @@ pub fn find_provider_runtime(provider_id: &str) -> Option<&'static dyn ProviderR
         .copied()
         .find(|runtime| runtime.id() == provider_id.as_str())
 }
+
+pub fn find_primary_line<'a>(
+    lines: &'a [MetricLine],
+    candidates: &[&str],
+) -> Option<&'a MetricLine> {
+    candidates.iter().find_map(|candidate| {
+        let candidate = candidate.trim();
+        lines
+            .iter()
+            .find(|line| line.label().trim().eq_ignore_ascii_case(candidate))
+    })
+}
+
+/// Moves every line matching a candidate to the front, in candidate order; the
+/// remaining lines keep their relative order.
+pub fn prioritize_primary_lines(lines: Vec<MetricLine>, candidates: &[&str]) -> Vec<MetricLine> {
+    let candidate_rank = |line: &MetricLine| {
+        candidates
+            .iter()
+            .position(|candidate| line.label().trim().eq_ignore_ascii_case(candidate.trim()))
+    };
+    let (mut primary, rest): (Vec<_>, Vec<_>) = lines
+        .into_iter()
+        .partition(|line| candidate_rank(line).is_some());
+    primary.sort_by_key(|line| candidate_rank(line));
+    primary.extend(rest);
+    primary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::find_provider_contract;
+
+    #[test]
+    fn provider_meta_links_match_contracts() {
+        for meta in all_provider_meta() {
+            let contract =
+                find_provider_contract(&meta.id).expect("runtime should have a contract");
+            assert_eq!(
+                meta.documentation_url.as_deref(),
+                contract.documentation_url
+            );
+            assert_eq!(meta.dashboard_url.as_deref(), contract.dashboard_url);
+        }
+
+        let codex = find_provider_runtime("codex").expect("codex should be registered");
+        assert_eq!(codex.dashboard_url(), Some("https://chatgpt.com/settings"));
+    }
+
+    #[test]
+    fn prioritize_primary_lines_moves_all_matches_in_candidate_order() {
+        let line = |label: &str| MetricLine::Text {
+            label: label.to_string(),
+            value: String::new(),
+            color: None,
+            subtitle: None,
+        };
+        let lines = vec![
+            line("Claude Sonnet 4.5"),
+            line("Other"),
+            line("gemini 3 flash"),
+            line("Prompt Credits"),
+            line("Gemini 3 Pro"),
+        ];
+
+        let ordered = prioritize_primary_lines(
+            lines,
+            &["Gemini 3 Pro", "Gemini 3 Flash", "Claude Sonnet 4.5"],
+        );
+        let labels = ordered.iter().map(MetricLine::label).collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            vec![
+                "Gemini 3 Pro",
+                "gemini 3 flash",
+                "Claude Sonnet 4.5",
+                "Other",
+                "Prompt Credits"
+            ]
+        );
+    }
+}
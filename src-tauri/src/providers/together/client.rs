@@ -0,0 +1,146 @@
+use std::sync::OnceLock;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{BackendError, Result};
+use crate::providers::common::{
+    format_http_error_with_context, http_status_error, parse_retry_after, request_error,
+    ProviderHttpClient, DEFAULT_USER_AGENT,
+};
+use crate::utils::now_instant;
+
+const API_BASE: &str = "https://api.together.xyz/v1";
+const CREDITS_URL: &str = "https://api.together.xyz/v1/billing/credits";
+
+fn shared_client() -> &'static Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT.get_or_init(|| ProviderHttpClient::new(DEFAULT_USER_AGENT).into_client())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TogetherCredentials {
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    #[serde(
+        rename = "apiKey",
+        alias = "api_key",
+        alias = "token",
+        alias = "access_token",
+        alias = "togetherApiKey"
+    )]
+    pub api_key: String,
+    #[serde(
+        rename = "orgId",
+        alias = "org_id",
+        alias = "organizationId",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub org_id: Option<String>,
+}
+
+impl TogetherCredentials {
+    pub fn with_kind(mut self) -> Self {
+        self.kind = Some("apiKey".to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TogetherCredits {
+    #[serde(default, alias = "organizationId", alias = "org_id")]
+    pub organization_id: Option<String>,
+    #[serde(default, alias = "creditsUsed", alias = "used")]
+    pub credits_used: Option<f64>,
+    #[serde(default, alias = "creditsAllocated", alias = "total")]
+    pub credits_allocated: Option<f64>,
+    #[serde(default, alias = "creditsRemaining", alias = "remaining")]
+    pub credits_remaining: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TogetherRateLimits {
+    #[serde(default, alias = "rateLimits", alias = "data")]
+    pub rate_limits: Vec<TogetherRateLimit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TogetherRateLimit {
+    #[serde(alias = "modelFamily", alias = "family")]
+    pub model_family: String,
+    #[serde(default)]
+    pub limit: f64,
+    #[serde(default)]
+    pub remaining: f64,
+    #[serde(default)]
+    pub used: f64,
+    #[serde(default, alias = "resetAt", alias = "reset_time")]
+    pub reset_at: Option<i64>,
+}
+
+pub async fn fetch_credits(api_key: &str) -> Result<TogetherCredits> {
+    if api_key.trim().is_empty() {
+        return Err(BackendError::Provider(
+            "Missing Together AI API key".to_string(),
+        ));
+    }
+
+    let started = now_instant();
+    let response = authorized_get(CREDITS_URL, api_key)
+        .await
+        .map_err(|err| request_error("Together AI credits request failed", started, err))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return response.json::<TogetherCredits>().await.map_err(|err| {
+            BackendError::Provider(format!("Together AI credits decode failed: {err}"))
+        });
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    let message = format_http_error_with_context(
+        "Together AI credits request failed",
+        status,
+        &body,
+        "GET",
+        CREDITS_URL,
+    );
+    Err(http_status_error(status, retry_after, message))
+}
+
+pub async fn fetch_rate_limits(api_key: &str, org_id: &str) -> Result<TogetherRateLimits> {
+    let url = format!("{API_BASE}/organizations/{}/rate_limits", org_id.trim());
+    let started = now_instant();
+    let response = authorized_get(&url, api_key)
+        .await
+        .map_err(|err| request_error("Together AI rate limits request failed", started, err))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return response.json::<TogetherRateLimits>().await.map_err(|err| {
+            BackendError::Provider(format!("Together AI rate limits decode failed: {err}"))
+        });
+    }
+
+    let retry_after = parse_retry_after(response.headers());
+    let body = response.text().await.unwrap_or_else(|_| "".to_string());
+    let message = format_http_error_with_context(
+        "Together AI rate limits request failed",
+        status,
+        &body,
+        "GET",
+        &url,
+    );
+    Err(http_status_error(status, retry_after, message))
+}
+
+async fn authorized_get(url: &str, api_key: &str) -> reqwest::Result<reqwest::Response> {
+    shared_client()
+        .get(url)
+        .bearer_auth(api_key.trim())
+        .header("accept", "application/json")
+        .send()
+        .await
+}
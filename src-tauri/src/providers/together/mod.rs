@@ -0,0 +1,78 @@
+pub mod client;
+pub mod probe;
+
+use crate::models::AccountRecord;
+
+use super::contract::{api_key_provider_contract, ProviderContract};
+use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
+
+pub const CONTRACT: ProviderContract = api_key_provider_contract("together", "Together AI")
+    .with_documentation_url("https://docs.together.ai")
+    .with_dashboard_url("https://api.together.ai/settings/billing");
+
+const LINES: [ManifestLineSpec; 3] = [
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Credits",
+        scope: "overview",
+    },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Llama",
+        scope: "detail",
+    },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Mixtral",
+        scope: "detail",
+    },
+];
+
+const PRIMARY_CANDIDATES: [&str; 1] = ["Credits"];
+
+#[derive(Debug, Clone, Copy)]
+pub struct TogetherRuntime;
+
+pub const RUNTIME: TogetherRuntime = TogetherRuntime;
+
+impl ProviderRuntime for TogetherRuntime {
+    fn id(&self) -> &'static str {
+        CONTRACT.id
+    }
+
+    fn name(&self) -> &'static str {
+        CONTRACT.name
+    }
+
+    fn icon_url(&self) -> &'static str {
+        "/providers/together.svg"
+    }
+
+    fn brand_color(&self) -> Option<&'static str> {
+        Some("#0F6FFF")
+    }
+
+    fn lines(&self) -> &'static [ManifestLineSpec] {
+        &LINES
+    }
+
+    fn primary_candidates(&self) -> &'static [&'static str] {
+        &PRIMARY_CANDIDATES
+    }
+
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    fn dashboard_url(&self) -> Option<&'static str> {
+        CONTRACT.dashboard_url
+    }
+
+    fn probe<'a>(
+        &self,
+        account: &'a AccountRecord,
+        credentials: serde_json::Value,
+    ) -> ProbeFuture<'a> {
+        Box::pin(probe::probe(account, credentials))
+    }
+}
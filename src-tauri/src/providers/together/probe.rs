@@ -0,0 +1,136 @@
+use crate::error::{BackendError, Result};
+use crate::models::AccountRecord;
+use crate::providers::usage::{
+    limit_used_percent, normalize_metric_line, read_json_string, status_line, unix_to_rfc3339,
+    MetricLine, ProbeSuccess, ProgressFormat,
+};
+
+use super::client as together;
+
+pub async fn probe(
+    account: &AccountRecord,
+    credentials: serde_json::Value,
+) -> Result<ProbeSuccess> {
+    let mut credentials = serde_json::from_value::<together::TogetherCredentials>(credentials)
+        .map_err(|err| BackendError::Provider(format!("Invalid Together AI credentials: {err}")))?;
+
+    let mut updated = false;
+    if credentials.kind.as_deref() != Some("apiKey") {
+        credentials.kind = Some("apiKey".to_string());
+        updated = true;
+    }
+
+    if credentials.api_key.trim().is_empty() {
+        if let Some(value) = read_json_string(
+            &account.settings,
+            &[
+                "apiKey",
+                "api_key",
+                "token",
+                "access_token",
+                "togetherApiKey",
+            ],
+        ) {
+            credentials.api_key = value;
+            updated = true;
+        }
+    }
+
+    let credits = together::fetch_credits(&credentials.api_key).await?;
+
+    let has_org_id = credentials
+        .org_id
+        .as_deref()
+        .is_some_and(|value| !value.trim().is_empty());
+    if !has_org_id {
+        if let Some(org_id) = credits
+            .organization_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            credentials.org_id = Some(org_id.to_string());
+            updated = true;
+        }
+    }
+
+    let mut lines = Vec::new();
+    if let Some(line) = build_credits_line(&credits) {
+        lines.push(line);
+    }
+
+    // Rate limits are supplementary; a failure there keeps the credits line.
+    let rate_limits = match credentials.org_id.as_deref() {
+        Some(org_id) => match together::fetch_rate_limits(&credentials.api_key, org_id).await {
+            Ok(rate_limits) => Some(rate_limits),
+            Err(err) => {
+                log::warn!(
+                    "[together] rate limit fetch failed account={}: {err}",
+                    account.id
+                );
+                None
+            }
+        },
+        None => None,
+    };
+    if let Some(rate_limits) = rate_limits {
+        for limit in &rate_limits.rate_limits {
+            let label = limit.model_family.trim();
+            if label.is_empty() || limit.limit <= 0.0 {
+                continue;
+            }
+            lines.push(MetricLine::Progress {
+                label: label.to_string(),
+                used: limit_used_percent(limit.limit, limit.remaining, limit.used, 0.0)
+                    .clamp(0.0, 100.0),
+                limit: 100.0,
+                format: ProgressFormat::Percent,
+                resets_at: limit.reset_at.and_then(unix_to_rfc3339),
+                period_duration_ms: None,
+                color: None,
+            });
+        }
+    }
+
+    if lines.is_empty() {
+        lines.push(status_line("No usage data"));
+    }
+
+    let updated_credentials = if updated {
+        Some(
+            serde_json::to_value(credentials.with_kind()).map_err(|err| {
+                BackendError::Provider(format!("Invalid Together AI credentials: {err}"))
+            })?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ProbeSuccess {
+        plan: None,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
+        updated_credentials,
+        updated_settings: None,
+    })
+}
+
+fn build_credits_line(credits: &together::TogetherCredits) -> Option<MetricLine> {
+    let allocated = credits.credits_allocated.filter(|value| *value > 0.0)?;
+    let used = credits
+        .credits_used
+        .or_else(|| {
+            credits
+                .credits_remaining
+                .map(|remaining| allocated - remaining)
+        })?
+        .clamp(0.0, allocated);
+    Some(MetricLine::Progress {
+        label: "Credits".to_string(),
+        used,
+        limit: allocated,
+        format: ProgressFormat::dollars(),
+        resets_at: None,
+        period_duration_ms: None,
+        color: None,
+    })
+}
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
@@ -6,15 +6,157 @@ pub const PERIOD_5_HOURS_MS: u64 = 5 * 60 * 60 * 1000;
 pub const PERIOD_7_DAYS_MS: u64 = 7 * 24 * 60 * 60 * 1000;
 pub const PERIOD_30_DAYS_MS: u64 = 30 * 24 * 60 * 60 * 1000;
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(tag = "kind", rename_all = "camelCase")]
+pub const DEFAULT_USAGE_WARN_THRESHOLD: f64 = 0.7;
+pub const DEFAULT_USAGE_CRITICAL_THRESHOLD: f64 = 0.9;
+const USAGE_WARN_COLOR: &str = "#f59e0b";
+const USAGE_CRITICAL_COLOR: &str = "#ef4444";
+
+/// Fractions of a progress line's `limit` from the `usageWarnThreshold` and
+/// `usageCriticalThreshold` settings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UsageThresholds {
+    pub warn: f64,
+    pub critical: f64,
+}
+
+impl Default for UsageThresholds {
+    fn default() -> Self {
+        Self {
+            warn: DEFAULT_USAGE_WARN_THRESHOLD,
+            critical: DEFAULT_USAGE_CRITICAL_THRESHOLD,
+        }
+    }
+}
+
+impl UsageThresholds {
+    /// Amber from the warn threshold, red above the critical threshold.
+    pub fn color(&self, used: f64, limit: f64) -> Option<String> {
+        if limit <= 0.0 || !used.is_finite() {
+            return None;
+        }
+        let ratio = used / limit;
+        if ratio > self.critical {
+            Some(USAGE_CRITICAL_COLOR.to_string())
+        } else if ratio >= self.warn {
+            Some(USAGE_WARN_COLOR.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Recolors a progress line; other lines are returned unchanged.
+    pub fn apply(&self, line: MetricLine) -> MetricLine {
+        match line {
+            MetricLine::Progress {
+                label,
+                used,
+                limit,
+                format,
+                resets_at,
+                period_duration_ms,
+                ..
+            } => MetricLine::Progress {
+                color: self.color(used, limit),
+                label,
+                used,
+                limit,
+                format,
+                resets_at,
+                period_duration_ms,
+            },
+            other => other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", from = "ProgressFormatWire")]
 pub enum ProgressFormat {
+    Percent,
+    Currency { symbol: String, decimals: u8 },
+    Count { suffix: String },
+    Time { unit: TimeUnit },
+}
+
+/// Deserialization shape of [`ProgressFormat`] that still accepts the
+/// retired `{"kind":"dollars"}` written by older builds and caches.
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum ProgressFormatWire {
     Percent,
     Dollars,
+    Currency { symbol: String, decimals: u8 },
     Count { suffix: String },
+    Time { unit: TimeUnit },
+}
+
+impl From<ProgressFormatWire> for ProgressFormat {
+    fn from(wire: ProgressFormatWire) -> Self {
+        match wire {
+            ProgressFormatWire::Percent => Self::Percent,
+            ProgressFormatWire::Dollars => Self::dollars(),
+            ProgressFormatWire::Currency { symbol, decimals } => {
+                Self::Currency { symbol, decimals }
+            }
+            ProgressFormatWire::Count { suffix } => Self::Count { suffix },
+            ProgressFormatWire::Time { unit } => Self::Time { unit },
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl ProgressFormat {
+    /// US dollars with cents; replaces the former `Dollars` format.
+    pub fn dollars() -> Self {
+        Self::Currency {
+            symbol: "$".to_string(),
+            decimals: 2,
+        }
+    }
+
+    /// Currency format for an ISO 4217 code, defaulting to dollars.
+    pub fn currency(code: Option<&str>) -> Self {
+        let Some(code) = code.map(str::trim).filter(|code| !code.is_empty()) else {
+            return Self::dollars();
+        };
+        let code = code.to_ascii_uppercase();
+        let decimals = if matches!(code.as_str(), "JPY" | "KRW") {
+            0
+        } else {
+            2
+        };
+        Self::Currency {
+            symbol: currency_symbol(&code).to_string(),
+            decimals,
+        }
+    }
+}
+
+/// Symbol for common ISO 4217 codes; unknown codes are shown as-is.
+pub fn currency_symbol(code: &str) -> &str {
+    match code {
+        "USD" => "$",
+        "EUR" => "\u{20ac}",
+        "GBP" => "\u{a3}",
+        "JPY" => "\u{a5}",
+        "INR" => "\u{20b9}",
+        "KRW" => "\u{20a9}",
+        "CAD" => "CA$",
+        "AUD" => "A$",
+        "BRL" => "R$",
+        "CNY" => "CN\u{a5}",
+        _ => code,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimeUnit {
+    Minutes,
+    Hours,
+    Days,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum MetricLine {
     Text {
@@ -40,6 +182,31 @@ pub enum MetricLine {
         color: Option<String>,
         subtitle: Option<String>,
     },
+    Table {
+        label: String,
+        columns: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    /// The same metric at two points in time, `previous` being the older one.
+    Trend {
+        label: String,
+        current: f64,
+        previous: f64,
+        format: ProgressFormat,
+        color: Option<String>,
+    },
+}
+
+impl MetricLine {
+    pub fn label(&self) -> &str {
+        match self {
+            MetricLine::Text { label, .. }
+            | MetricLine::Progress { label, .. }
+            | MetricLine::Badge { label, .. }
+            | MetricLine::Table { label, .. }
+            | MetricLine::Trend { label, .. } => label,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -47,6 +214,7 @@ pub struct ProbeSuccess {
     pub plan: Option<String>,
     pub lines: Vec<MetricLine>,
     pub updated_credentials: Option<serde_json::Value>,
+    pub updated_settings: Option<serde_json::Value>,
 }
 
 pub fn progress_percent_line(
@@ -62,10 +230,62 @@ pub fn progress_percent_line(
         format: ProgressFormat::Percent,
         resets_at,
         period_duration_ms,
+        color: usage_color(used, 100.0),
+    }
+}
+
+pub fn trend_line(label: &str, current: f64, previous: f64, format: ProgressFormat) -> MetricLine {
+    MetricLine::Trend {
+        label: label.to_string(),
+        current,
+        previous,
+        format,
         color: None,
     }
 }
 
+/// Color under the default thresholds. The probe batch recolors these lines
+/// with the configured thresholds.
+pub fn usage_color(used: f64, limit: f64) -> Option<String> {
+    UsageThresholds::default().color(used, limit)
+}
+
+pub fn normalize_metric_line(line: MetricLine) -> MetricLine {
+    match line {
+        MetricLine::Progress {
+            label,
+            used,
+            limit,
+            format: ProgressFormat::Percent,
+            resets_at,
+            period_duration_ms,
+            color,
+        } => {
+            let clamped = if used > limit {
+                log::debug!(
+                    "[usage] clamped progress line label={} used={} limit={}",
+                    label,
+                    used,
+                    limit
+                );
+                limit
+            } else {
+                used
+            };
+            MetricLine::Progress {
+                label,
+                used: clamped,
+                limit,
+                format: ProgressFormat::Percent,
+                resets_at,
+                period_duration_ms,
+                color,
+            }
+        }
+        other => other,
+    }
+}
+
 pub fn status_line(text: &str) -> MetricLine {
     MetricLine::Badge {
         label: "Status".to_string(),
@@ -144,6 +364,18 @@ pub fn normalize_resets_at(value: Option<String>) -> Option<String> {
         .filter(|text| !text.is_empty())
 }
 
+/// Percent of `total` consumed, taking the larger of the remaining-derived and
+/// reported current usage. Falls back to `fallback_percent` when no total is known.
+pub fn limit_used_percent(total: f64, remaining: f64, current: f64, fallback_percent: f64) -> f64 {
+    let total = total.max(0.0);
+    if total > 0.0 {
+        let used_from_remaining = (total - remaining.max(0.0)).max(0.0);
+        let used = used_from_remaining.max(current.max(0.0)).min(total);
+        return used / total * 100.0;
+    }
+    fallback_percent
+}
+
 pub fn dollars_from_cents(value: f64) -> f64 {
     (value / 100.0 * 100.0).round() / 100.0
 }
@@ -162,3 +394,65 @@ pub fn read_json_string(settings: &serde_json::Value, keys: &[&str]) -> Option<S
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn color(used: f64, limit: f64) -> Option<String> {
+        UsageThresholds::default().color(used, limit)
+    }
+
+    #[test]
+    fn currency_format_maps_iso_codes() {
+        match ProgressFormat::currency(Some("eur")) {
+            ProgressFormat::Currency { symbol, decimals } => {
+                assert_eq!(symbol, "\u{20ac}");
+                assert_eq!(decimals, 2);
+            }
+            other => panic!("unexpected format: {other:?}"),
+        }
+        assert_eq!(currency_symbol("CHF"), "CHF");
+        assert_eq!(
+            serde_json::to_value(ProgressFormat::currency(None)).expect("format should serialize"),
+            serde_json::json!({ "kind": "currency", "symbol": "$", "decimals": 2 })
+        );
+    }
+
+    #[test]
+    fn legacy_dollars_format_deserializes_as_currency() {
+        let format: ProgressFormat =
+            serde_json::from_value(serde_json::json!({ "kind": "dollars" }))
+                .expect("legacy format should deserialize");
+        assert_eq!(
+            serde_json::to_value(format).expect("format should serialize"),
+            serde_json::json!({ "kind": "currency", "symbol": "$", "decimals": 2 })
+        );
+    }
+
+    #[test]
+    fn usage_color_applies_threshold_boundaries() {
+        assert_eq!(color(69.9, 100.0), None);
+        assert_eq!(color(70.0, 100.0), Some(USAGE_WARN_COLOR.to_string()));
+        assert_eq!(color(90.0, 100.0), Some(USAGE_WARN_COLOR.to_string()));
+        assert_eq!(color(90.1, 100.0), Some(USAGE_CRITICAL_COLOR.to_string()));
+        assert_eq!(color(100.0, 100.0), Some(USAGE_CRITICAL_COLOR.to_string()));
+        assert_eq!(color(700.0, 1000.0), Some(USAGE_WARN_COLOR.to_string()));
+        assert_eq!(color(5.0, 0.0), None);
+
+        let thresholds = UsageThresholds {
+            warn: 0.5,
+            critical: 0.8,
+        };
+        assert_eq!(
+            thresholds.color(50.0, 100.0),
+            Some(USAGE_WARN_COLOR.to_string())
+        );
+        match thresholds.apply(progress_percent_line("Session", 85.0, None, None)) {
+            MetricLine::Progress { color, .. } => {
+                assert_eq!(color, Some(USAGE_CRITICAL_COLOR.to_string()))
+            }
+            other => panic!("unexpected line: {other:?}"),
+        }
+    }
+}
@@ -41,7 +41,8 @@ pub fn validate_provider_settings(
                 .settings
                 .required_keys
                 .iter()
-                .any(|required_key| required_key == &key.as_str())
+                .chain(provider.settings.optional_keys.iter())
+                .any(|known_key| known_key == &key.as_str())
             {
                 return Err(format!(
                     "settings.{} is not allowed for providerId '{}'",
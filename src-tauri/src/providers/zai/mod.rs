@@ -6,9 +6,11 @@ use crate::models::AccountRecord;
 use super::contract::{api_key_provider_contract, ProviderContract};
 use super::runtime::{ManifestLineSpec, ProbeFuture, ProviderRuntime};
 
-pub const CONTRACT: ProviderContract = api_key_provider_contract("zai", "Z.ai");
+pub const CONTRACT: ProviderContract = api_key_provider_contract("zai", "Z.ai")
+    .with_documentation_url("https://docs.z.ai")
+    .with_dashboard_url("https://z.ai/manage-apikey/subscription");
 
-const LINES: [ManifestLineSpec; 2] = [
+const LINES: [ManifestLineSpec; 3] = [
     ManifestLineSpec {
         line_type: "progress",
         label: "Token Usage",
@@ -19,6 +21,11 @@ const LINES: [ManifestLineSpec; 2] = [
         label: "Utility Usage",
         scope: "overview",
     },
+    ManifestLineSpec {
+        line_type: "progress",
+        label: "Request Usage",
+        scope: "detail",
+    },
 ];
 
 const PRIMARY_CANDIDATES: [&str; 2] = ["Token Usage", "Utility Usage"];
@@ -53,6 +60,18 @@ impl ProviderRuntime for ZaiRuntime {
         &PRIMARY_CANDIDATES
     }
 
+    fn documentation_url(&self) -> Option<&'static str> {
+        CONTRACT.documentation_url
+    }
+
+    fn dashboard_url(&self) -> Option<&'static str> {
+        CONTRACT.dashboard_url
+    }
+
+    fn colors_usage(&self) -> bool {
+        true
+    }
+
     fn probe<'a>(
         &self,
         account: &'a AccountRecord,
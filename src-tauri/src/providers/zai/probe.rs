@@ -1,8 +1,8 @@
 use crate::error::{BackendError, Result};
 use crate::models::AccountRecord;
 use crate::providers::usage::{
-    plan_label, read_json_string, status_line, unix_to_rfc3339, MetricLine, ProbeSuccess,
-    ProgressFormat,
+    limit_used_percent, normalize_metric_line, plan_label, read_json_string, status_line,
+    unix_to_rfc3339, usage_color, MetricLine, ProbeSuccess, ProgressFormat, TimeUnit,
 };
 
 use super::client as zai;
@@ -70,47 +70,7 @@ pub async fn probe(
     }
 
     let usage = zai::fetch_usage(&credentials).await?;
-    let mut lines = Vec::new();
-
-    if let Some(data) = usage.data.as_ref() {
-        let mut token_line = None;
-        let mut utility_line = None;
-
-        for limit in &data.limits {
-            match limit.limit_type.as_str() {
-                "TOKENS_LIMIT" => {
-                    token_line = Some(MetricLine::Progress {
-                        label: "Token Usage".to_string(),
-                        used: zai_limit_used_percent(limit).clamp(0.0, 100.0),
-                        limit: 100.0,
-                        format: ProgressFormat::Percent,
-                        resets_at: limit.next_reset_time.and_then(unix_to_rfc3339),
-                        period_duration_ms: zai_limit_period_ms(limit),
-                        color: None,
-                    })
-                }
-                "TIME_LIMIT" => {
-                    utility_line = Some(MetricLine::Progress {
-                        label: "Utility Usage".to_string(),
-                        used: zai_limit_used_percent(limit).clamp(0.0, 100.0),
-                        limit: 100.0,
-                        format: ProgressFormat::Percent,
-                        resets_at: limit.next_reset_time.and_then(unix_to_rfc3339),
-                        period_duration_ms: zai_limit_period_ms(limit),
-                        color: None,
-                    })
-                }
-                _ => {}
-            }
-        }
-
-        if let Some(line) = token_line {
-            lines.push(line);
-        }
-        if let Some(line) = utility_line {
-            lines.push(line);
-        }
-    }
+    let mut lines = usage.data.as_ref().map(usage_lines).unwrap_or_default();
 
     if lines.is_empty() {
         lines.push(status_line("No usage data"));
@@ -141,21 +101,127 @@ pub async fn probe(
 
     Ok(ProbeSuccess {
         plan,
-        lines,
+        lines: lines.into_iter().map(normalize_metric_line).collect(),
         updated_credentials,
+        updated_settings: None,
     })
 }
 
-fn zai_limit_used_percent(limit: &zai::ZaiLimitRaw) -> f64 {
-    let total = limit.usage.max(0);
-    if total > 0 {
-        let remaining = limit.remaining.max(0);
-        let current = limit.current_value.max(0);
-        let used_from_remaining = total.saturating_sub(remaining);
-        let used = used_from_remaining.max(current).min(total);
-        return used as f64 / total as f64 * 100.0;
+fn usage_lines(data: &zai::ZaiQuotaLimitData) -> Vec<MetricLine> {
+    let mut lines = Vec::new();
+
+    let mut token_line = None;
+    let mut utility_line = None;
+    let mut request_line = None;
+
+    for limit in &data.limits {
+        match limit.limit_type.as_str() {
+            "TOKENS_LIMIT" => {
+                let (used, total) = zai_limit_used(limit);
+                let used = (used * 100.0 / total).clamp(0.0, 100.0);
+                token_line = Some(MetricLine::Progress {
+                    label: "Token Usage".to_string(),
+                    used,
+                    limit: 100.0,
+                    format: ProgressFormat::Percent,
+                    resets_at: limit.next_reset_time.and_then(unix_to_rfc3339),
+                    period_duration_ms: zai_limit_period_ms(limit),
+                    color: usage_color(used, 100.0),
+                })
+            }
+            "TIME_LIMIT" => {
+                let (used, total) = zai_limit_used(limit);
+                let (used, total, format) = match zai_time_unit(limit.unit) {
+                    Some(unit) if limit.usage > 0 => (used, total, ProgressFormat::Time { unit }),
+                    _ => (
+                        (used * 100.0 / total).clamp(0.0, 100.0),
+                        100.0,
+                        ProgressFormat::Percent,
+                    ),
+                };
+                utility_line = Some(MetricLine::Progress {
+                    label: "Utility Usage".to_string(),
+                    used,
+                    limit: total,
+                    format,
+                    resets_at: limit.next_reset_time.and_then(unix_to_rfc3339),
+                    period_duration_ms: zai_limit_period_ms(limit),
+                    color: usage_color(used, total),
+                })
+            }
+            "REQUESTS_LIMIT" => {
+                let (used, total) = zai_limit_used_absolute(limit);
+                if total > 0.0 {
+                    request_line = Some(MetricLine::Progress {
+                        label: "Request Usage".to_string(),
+                        used,
+                        limit: total,
+                        format: ProgressFormat::Count {
+                            suffix: "requests".to_string(),
+                        },
+                        resets_at: limit.next_reset_time.and_then(unix_to_rfc3339),
+                        period_duration_ms: zai_limit_period_ms(limit),
+                        color: usage_color(used, total),
+                    })
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(line) = token_line {
+        lines.push(line);
+    }
+    if let Some(line) = utility_line {
+        lines.push(line);
+    }
+    if let Some(line) = request_line {
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// `(used, limit)` in the quota's own units, or against a limit of 100 when
+/// the quota only reports a percentage.
+fn zai_limit_used(limit: &zai::ZaiLimitRaw) -> (f64, f64) {
+    let total = (limit.usage as f64).max(0.0);
+    let percent = limit_used_percent(
+        total,
+        limit.remaining as f64,
+        limit.current_value as f64,
+        limit.percentage,
+    );
+    if total > 0.0 {
+        (percent * total / 100.0, total)
+    } else {
+        (percent, 100.0)
+    }
+}
+
+/// `(used_count, total_count)` for count quotas; `(0, 0)` when no total is known.
+fn zai_limit_used_absolute(limit: &zai::ZaiLimitRaw) -> (f64, f64) {
+    let total = (limit.usage as f64).max(0.0);
+    if total <= 0.0 {
+        return (0.0, 0.0);
+    }
+    let used = if limit.current_value > 0 {
+        limit.current_value as f64
+    } else if limit.remaining > 0 {
+        total - limit.remaining as f64
+    } else {
+        limit.percentage * total / 100.0
+    };
+    (used.clamp(0.0, total), total)
+}
+
+fn zai_time_unit(unit: i64) -> Option<TimeUnit> {
+    match unit {
+        5 => Some(TimeUnit::Minutes),
+        3 => Some(TimeUnit::Hours),
+        1 => Some(TimeUnit::Days),
+        _ => None,
     }
-    limit.percentage
 }
 
 fn zai_limit_period_ms(limit: &zai::ZaiLimitRaw) -> Option<u64> {
@@ -163,10 +229,13 @@ fn zai_limit_period_ms(limit: &zai::ZaiLimitRaw) -> Option<u64> {
         return None;
     }
 
-    let unit_seconds = match limit.unit {
+    let unit_seconds: u64 = match limit.unit {
         5 => 60,
         3 => 60 * 60,
         1 => 24 * 60 * 60,
+        2 => 7 * 24 * 60 * 60,
+        // Months, as used by the BigModel CN API; approximated as 30 days.
+        4 => 30 * 24 * 60 * 60,
         _ => return None,
     };
 
@@ -176,3 +245,70 @@ fn zai_limit_period_ms(limit: &zai::ZaiLimitRaw) -> Option<u64> {
             .saturating_mul(1000),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit(unit: i64, number: i64) -> zai::ZaiLimitRaw {
+        serde_json::from_value(serde_json::json!({
+            "type": "TOKENS_LIMIT",
+            "unit": unit,
+            "number": number,
+        }))
+        .expect("limit should deserialize")
+    }
+
+    #[test]
+    fn each_limit_type_produces_its_line() {
+        let data = serde_json::from_value::<zai::ZaiQuotaLimitData>(serde_json::json!({
+            "limits": [
+                { "type": "REQUESTS_LIMIT", "unit": 3, "number": 5, "usage": 200, "currentValue": 50 },
+                { "type": "TIME_LIMIT", "unit": 5, "number": 1, "usage": 0, "percentage": 40.0 },
+                { "type": "TOKENS_LIMIT", "unit": 3, "number": 5, "percentage": 25.0 },
+            ],
+        }))
+        .expect("limits should deserialize");
+
+        let lines = usage_lines(&data);
+        let summary: Vec<(&str, &ProgressFormat, f64, f64)> = lines
+            .iter()
+            .map(|line| match line {
+                MetricLine::Progress {
+                    label,
+                    format,
+                    used,
+                    limit,
+                    ..
+                } => (label.as_str(), format, *used, *limit),
+                other => panic!("unexpected line: {other:?}"),
+            })
+            .collect();
+
+        assert_eq!(summary.len(), 3);
+        assert_eq!(summary[0].0, "Token Usage");
+        assert!(matches!(summary[0].1, ProgressFormat::Percent));
+        assert_eq!((summary[0].2, summary[0].3), (25.0, 100.0));
+        assert_eq!(summary[1].0, "Utility Usage");
+        assert!(matches!(summary[1].1, ProgressFormat::Percent));
+        assert_eq!(summary[2].0, "Request Usage");
+        assert!(matches!(summary[2].1, ProgressFormat::Count { suffix } if suffix == "requests"));
+        assert_eq!((summary[2].2, summary[2].3), (50.0, 200.0));
+    }
+
+    #[test]
+    fn limit_period_covers_all_unit_codes() {
+        const MINUTE_MS: u64 = 60 * 1000;
+        const DAY_MS: u64 = 24 * 60 * MINUTE_MS;
+
+        assert_eq!(zai_limit_period_ms(&limit(5, 30)), Some(30 * MINUTE_MS));
+        assert_eq!(zai_limit_period_ms(&limit(3, 5)), Some(5 * 60 * MINUTE_MS));
+        assert_eq!(zai_limit_period_ms(&limit(1, 1)), Some(DAY_MS));
+        assert_eq!(zai_limit_period_ms(&limit(2, 1)), Some(7 * DAY_MS));
+        assert_eq!(zai_limit_period_ms(&limit(4, 1)), Some(30 * DAY_MS));
+
+        assert_eq!(zai_limit_period_ms(&limit(6, 1)), None);
+        assert_eq!(zai_limit_period_ms(&limit(0, 1)), None);
+        assert_eq!(zai_limit_period_ms(&limit(2, 0)), None);
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Mutex, OnceLock};
 
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
@@ -6,11 +7,13 @@ use base64::Engine;
 use chacha20poly1305::aead::{Aead, KeyInit, Payload};
 use chacha20poly1305::{ChaCha20Poly1305, Nonce, XChaCha20Poly1305, XNonce};
 use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
 use rand::rngs::OsRng;
 use rand::RngCore;
 use sha2::Sha256;
 use tauri::{AppHandle, Runtime};
 use tauri_plugin_keyring::KeyringExt;
+use zeroize::Zeroizing;
 
 use crate::account_store::AccountStore;
 use crate::error::{BackendError, Result};
@@ -18,21 +21,116 @@ use crate::models::{AccountRecord, EncryptedCredentials};
 
 const SERVICE_NAME: &str = "openburn";
 const MASTER_KEY_PREFIX: &str = "master-key-v";
-const KEY_VERSION: u32 = 1;
+const BASE_KEY_VERSION: u32 = 1;
+// Reserved for blobs sealed with the passphrase-derived key. Keyring versions
+// start at `BASE_KEY_VERSION`, so the two never share a version number.
+const PASSPHRASE_KEY_VERSION: u32 = 0;
 const ALGORITHM: &str = "xchacha20poly1305";
 const HKDF_SALT: &[u8] = b"openburn-credentials-v1";
+const HMAC_INFO: &[u8] = b"openburn-hmac-v1";
 
+// SAFETY: cached master keys are plain arrays and are not zeroized while the
+// process runs. Re-reading the keychain on every encrypt/decrypt is slow and can
+// prompt the user, so the cache trades that exposure for usability; it is
+// cleared on key rotation and via `clear_master_key_cache`.
 static MASTER_KEY_CACHE: OnceLock<Mutex<HashMap<u32, [u8; 32]>>> = OnceLock::new();
+// Master key version used for new ciphertexts. Older versions stay in the
+// keyring so existing blobs remain readable.
+static KEY_VERSION: AtomicU32 = AtomicU32::new(BASE_KEY_VERSION);
 
-fn credential_id(account: &AccountRecord) -> String {
-    format!("{}:{}", account.provider_id, account.id)
+#[cfg(feature = "passphrase-key")]
+const SALT_FILE_NAME: &str = "master-key-salt";
+#[cfg(feature = "passphrase-key")]
+const VERIFIER_FILE_NAME: &str = "master-key-verifier";
+#[cfg(feature = "passphrase-key")]
+const PASSPHRASE_CHECK: &[u8] = b"openburn-passphrase-check-v1";
+#[cfg(feature = "passphrase-key")]
+const PASSPHRASE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+// Master key derived from the user's passphrase when the keyring is unavailable.
+// Like `MASTER_KEY_CACHE`, it lives in memory for the rest of the process.
+#[cfg(feature = "passphrase-key")]
+static PASSPHRASE_KEY: Mutex<Option<[u8; 32]>> = Mutex::new(None);
+#[cfg(feature = "passphrase-key")]
+static PENDING_PASSPHRASE: Mutex<Option<tokio::sync::oneshot::Sender<Zeroizing<String>>>> =
+    Mutex::new(None);
+
+fn current_key_version() -> u32 {
+    KEY_VERSION.load(Ordering::SeqCst)
+}
+
+/// Restores the active key version after a restart from the newest version
+/// referenced by any stored credential blob.
+pub fn init_key_version(store: &AccountStore) -> Result<()> {
+    let newest = all_accounts(store)?
+        .iter()
+        .filter_map(|account| account.credentials.as_ref())
+        .map(|encrypted| encrypted.key_version)
+        .max()
+        .unwrap_or(BASE_KEY_VERSION)
+        .max(BASE_KEY_VERSION);
+    KEY_VERSION.store(newest, Ordering::SeqCst);
+    Ok(())
+}
+
+fn all_accounts(store: &AccountStore) -> Result<Vec<AccountRecord>> {
+    let mut accounts = store.list_accounts()?;
+    accounts.extend(store.list_deleted_accounts()?);
+    Ok(accounts)
+}
+
+// The credential id feeds both the HKDF info and the AEAD associated data, so
+// it must stay stable for the lifetime of the ciphertext. Changing an account's
+// `provider_id` (e.g. in a store migration) makes existing credentials
+// undecryptable unless they are re-encrypted under the new id first.
+fn credential_id(account: &AccountRecord) -> Result<String> {
+    validate_credential_id_stable(account)?;
+    Ok(format!("{}:{}", account.provider_id, account.id))
+}
+
+fn validate_credential_id_stable(account: &AccountRecord) -> Result<()> {
+    if account.id.trim().is_empty() {
+        return Err(BackendError::Crypto(
+            "cannot derive credential key: account id is empty".to_string(),
+        ));
+    }
+    if account.provider_id.trim().is_empty() {
+        return Err(BackendError::Crypto(format!(
+            "cannot derive credential key: provider id is empty for account {}",
+            account.id
+        )));
+    }
+    Ok(())
 }
 
 fn master_key_name(version: u32) -> String {
     format!("{MASTER_KEY_PREFIX}{version}")
 }
 
-fn read_master_key<R: Runtime>(app: &AppHandle<R>, version: u32) -> Result<Option<[u8; 32]>> {
+pub fn clear_master_key_cache() {
+    if let Some(cache) = MASTER_KEY_CACHE.get() {
+        let mut cache = cache.lock().expect("master key cache mutex poisoned");
+        for key in cache.values_mut() {
+            zeroize::Zeroize::zeroize(key);
+        }
+        cache.clear();
+    }
+}
+
+fn read_master_key<R: Runtime>(
+    app: &AppHandle<R>,
+    version: u32,
+) -> Result<Option<Zeroizing<[u8; 32]>>> {
+    if version == PASSPHRASE_KEY_VERSION {
+        #[cfg(feature = "passphrase-key")]
+        return passphrase_master_key(app, "credentials are sealed with the passphrase key")
+            .map(Some);
+        #[cfg(not(feature = "passphrase-key"))]
+        return Err(BackendError::Crypto(
+            "credentials are sealed with a passphrase key, which this build does not support"
+                .to_string(),
+        ));
+    }
+
     let cache = MASTER_KEY_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
     if let Some(key) = cache
         .lock()
@@ -40,7 +138,7 @@ fn read_master_key<R: Runtime>(app: &AppHandle<R>, version: u32) -> Result<Optio
         .get(&version)
         .copied()
     {
-        return Ok(Some(key));
+        return Ok(Some(Zeroizing::new(key)));
     }
 
     let key_name = master_key_name(version);
@@ -49,42 +147,225 @@ fn read_master_key<R: Runtime>(app: &AppHandle<R>, version: u32) -> Result<Optio
         .get_secret(SERVICE_NAME, &key_name)
         .map_err(|err| BackendError::Keyring(err.to_string()))?;
     let payload = match payload {
-        Some(payload) => payload,
+        Some(payload) => Zeroizing::new(payload),
         None => return Ok(None),
     };
+    if payload.len() != 32 {
+        return Err(BackendError::Crypto(
+            "master key length invalid".to_string(),
+        ));
+    }
 
-    let key: [u8; 32] = payload
-        .try_into()
-        .map_err(|_| BackendError::Crypto("master key length invalid".to_string()))?;
+    let mut key = Zeroizing::new([0u8; 32]);
+    key.copy_from_slice(&payload);
     let mut cache = cache.lock().expect("master key cache mutex poisoned");
-    cache.insert(version, key);
+    cache.insert(version, *key);
     Ok(Some(key))
 }
 
-fn get_or_create_master_key<R: Runtime>(app: &AppHandle<R>, version: u32) -> Result<[u8; 32]> {
+fn get_or_create_master_key<R: Runtime>(
+    app: &AppHandle<R>,
+    version: u32,
+) -> Result<Zeroizing<[u8; 32]>> {
     if let Some(key) = read_master_key(app, version)? {
         return Ok(key);
     }
 
-    let mut key = [0u8; 32];
-    OsRng.fill_bytes(&mut key);
+    let mut key = Zeroizing::new([0u8; 32]);
+    OsRng.fill_bytes(key.as_mut());
     let key_name = master_key_name(version);
     app.keyring()
-        .set_secret(SERVICE_NAME, &key_name, &key)
+        .set_secret(SERVICE_NAME, &key_name, &key[..])
         .map_err(|err| BackendError::Keyring(err.to_string()))?;
 
     let cache = MASTER_KEY_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
     cache
         .lock()
         .expect("master key cache mutex poisoned")
-        .insert(version, key);
+        .insert(version, *key);
+    Ok(key)
+}
+
+#[cfg(feature = "passphrase-key")]
+pub fn derive_master_key_from_passphrase(passphrase: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let params = argon2::Params::new(65536, 3, 1, Some(32))
+        .map_err(|err| BackendError::Crypto(format!("invalid argon2 parameters: {err}")))?;
+    let argon2 = argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+    let mut key = [0u8; 32];
+    argon2
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| BackendError::Crypto(format!("passphrase key derivation failed: {err}")))?;
     Ok(key)
 }
 
-fn derive_key(master_key: &[u8; 32], credential_id: &str) -> Result<[u8; 32]> {
+/// Returns the passphrase-derived key, or asks the frontend for a passphrase in
+/// the background and fails this call.
+#[cfg(feature = "passphrase-key")]
+fn passphrase_master_key<R: Runtime>(
+    app: &AppHandle<R>,
+    reason: &str,
+) -> Result<Zeroizing<[u8; 32]>> {
+    let cached = *PASSPHRASE_KEY
+        .lock()
+        .expect("passphrase key mutex poisoned");
+    if let Some(key) = cached {
+        return Ok(Zeroizing::new(key));
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(err) = request_passphrase_key(&app).await {
+            log::warn!("[secrets] passphrase unlock failed: {}", err);
+        }
+    });
+    Err(BackendError::Keyring(format!(
+        "{reason}; waiting for master key passphrase"
+    )))
+}
+
+/// Prompts until a passphrase matches the stored verifier (the first accepted
+/// passphrase creates it), then caches the derived key.
+#[cfg(feature = "passphrase-key")]
+async fn request_passphrase_key<R: Runtime>(app: &AppHandle<R>) -> Result<()> {
+    let data_dir = passphrase_data_dir(app)?;
+    let salt = load_or_create_salt(&data_dir)?;
+    let mut rejected = false;
+    loop {
+        let Some(passphrase) = wait_for_passphrase(app, rejected).await? else {
+            return Ok(());
+        };
+        let key = Zeroizing::new(derive_master_key_from_passphrase(&passphrase, &salt)?);
+        match verify_or_store_passphrase(&data_dir.join(VERIFIER_FILE_NAME), &key) {
+            Ok(()) => break,
+            Err(BackendError::Validation(message)) => {
+                log::warn!("[secrets] {}", message);
+                rejected = true;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    log::info!("[secrets] master key unlocked from passphrase");
+    Ok(())
+}
+
+/// Emits `auth:request-passphrase` and waits for the frontend's answer. Returns
+/// `None` when another request is already waiting.
+#[cfg(feature = "passphrase-key")]
+async fn wait_for_passphrase<R: Runtime>(
+    app: &AppHandle<R>,
+    rejected: bool,
+) -> Result<Option<Zeroizing<String>>> {
+    use tauri::Emitter;
+
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    {
+        let mut pending = PENDING_PASSPHRASE
+            .lock()
+            .expect("pending passphrase mutex poisoned");
+        if pending.is_some() {
+            return Ok(None);
+        }
+        *pending = Some(sender);
+    }
+
+    let _ = app.emit(
+        "auth:request-passphrase",
+        serde_json::json!({ "rejected": rejected }),
+    );
+    let received = tokio::time::timeout(PASSPHRASE_TIMEOUT, receiver).await;
+    PENDING_PASSPHRASE
+        .lock()
+        .expect("pending passphrase mutex poisoned")
+        .take();
+    let passphrase = match received {
+        Ok(Ok(passphrase)) => passphrase,
+        Ok(Err(_)) => {
+            return Err(BackendError::Keyring(
+                "passphrase request was dropped".to_string(),
+            ))
+        }
+        Err(_) => {
+            return Err(BackendError::Keyring(
+                "timed out waiting for passphrase".to_string(),
+            ))
+        }
+    };
+    Ok(Some(passphrase))
+}
+
+/// Checks the derived key against the verifier MAC next to the salt, writing
+/// the verifier on first use, and caches the key only when it matches.
+#[cfg(feature = "passphrase-key")]
+fn verify_or_store_passphrase(path: &std::path::Path, key: &[u8; 32]) -> Result<()> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key)
+        .map_err(|_| BackendError::Crypto("invalid passphrase key".to_string()))?;
+    mac.update(PASSPHRASE_CHECK);
+    match std::fs::read(path) {
+        Ok(expected) => mac.verify_slice(&expected).map_err(|_| {
+            BackendError::Validation("master key passphrase is incorrect".to_string())
+        })?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            std::fs::write(path, mac.finalize().into_bytes())?;
+        }
+        Err(err) => return Err(err.into()),
+    }
+    *PASSPHRASE_KEY
+        .lock()
+        .expect("passphrase key mutex poisoned") = Some(*key);
+    Ok(())
+}
+
+/// Hands the passphrase entered in the frontend to the pending request. The
+/// passphrase is zeroized once the key is derived and is never stored.
+#[cfg(feature = "passphrase-key")]
+pub fn submit_passphrase(passphrase: String) -> Result<()> {
+    let passphrase = Zeroizing::new(passphrase);
+    let sender = PENDING_PASSPHRASE
+        .lock()
+        .expect("pending passphrase mutex poisoned")
+        .take()
+        .ok_or_else(|| BackendError::Validation("no passphrase request is pending".to_string()))?;
+    sender
+        .send(passphrase)
+        .map_err(|_| BackendError::Validation("passphrase request expired".to_string()))
+}
+
+#[cfg(feature = "passphrase-key")]
+fn passphrase_data_dir<R: Runtime>(app: &AppHandle<R>) -> Result<std::path::PathBuf> {
+    use tauri::Manager;
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| BackendError::Path(err.to_string()))?;
+    std::fs::create_dir_all(&data_dir)?;
+    Ok(data_dir)
+}
+
+#[cfg(feature = "passphrase-key")]
+fn load_or_create_salt(data_dir: &std::path::Path) -> Result<Vec<u8>> {
+    let path = data_dir.join(SALT_FILE_NAME);
+    match std::fs::read(&path) {
+        Ok(salt) if salt.len() == 32 => return Ok(salt),
+        Ok(_) => {
+            return Err(BackendError::Crypto(
+                "master key salt file is invalid".to_string(),
+            ))
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    let mut salt = vec![0u8; 32];
+    OsRng.fill_bytes(&mut salt);
+    std::fs::write(&path, &salt)?;
+    Ok(salt)
+}
+
+fn derive_key(master_key: &[u8; 32], credential_id: &str) -> Result<Zeroizing<[u8; 32]>> {
     let hkdf = Hkdf::<Sha256>::new(Some(HKDF_SALT), master_key);
-    let mut derived = [0u8; 32];
-    hkdf.expand(credential_id.as_bytes(), &mut derived)
+    let mut derived = Zeroizing::new([0u8; 32]);
+    hkdf.expand(credential_id.as_bytes(), derived.as_mut())
         .map_err(|_| BackendError::Crypto("key derivation failed".to_string()))?;
     Ok(derived)
 }
@@ -94,16 +375,33 @@ fn encrypt_credentials<R: Runtime>(
     account: &AccountRecord,
     credentials: &serde_json::Value,
 ) -> Result<EncryptedCredentials> {
-    let master_key = get_or_create_master_key(app, KEY_VERSION)?;
-    let credential_id = credential_id(account);
-    let key = derive_key(&master_key, &credential_id)?;
-    let cipher = XChaCha20Poly1305::new_from_slice(&key)
+    let key_version = current_key_version();
+    match get_or_create_master_key(app, key_version) {
+        Ok(master_key) => encrypt_with_master_key(&master_key, key_version, account, credentials),
+        #[cfg(feature = "passphrase-key")]
+        Err(BackendError::Keyring(err)) => {
+            let master_key = passphrase_master_key(app, &err)?;
+            encrypt_with_master_key(&master_key, PASSPHRASE_KEY_VERSION, account, credentials)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+fn encrypt_with_master_key(
+    master_key: &[u8; 32],
+    key_version: u32,
+    account: &AccountRecord,
+    credentials: &serde_json::Value,
+) -> Result<EncryptedCredentials> {
+    let credential_id = credential_id(account)?;
+    let key = derive_key(master_key, &credential_id)?;
+    let cipher = XChaCha20Poly1305::new_from_slice(&key[..])
         .map_err(|_| BackendError::Crypto("invalid encryption key".to_string()))?;
 
     let mut nonce_bytes = [0u8; 24];
     OsRng.fill_bytes(&mut nonce_bytes);
     let nonce = XNonce::from_slice(&nonce_bytes);
-    let payload = serde_json::to_vec(credentials)?;
+    let payload = Zeroizing::new(serde_json::to_vec(credentials)?);
     let ciphertext = cipher
         .encrypt(
             nonce,
@@ -114,12 +412,62 @@ fn encrypt_credentials<R: Runtime>(
         )
         .map_err(|_| BackendError::Crypto("encryption failed".to_string()))?;
 
-    Ok(EncryptedCredentials {
+    let mut encrypted = EncryptedCredentials {
         alg: ALGORITHM.to_string(),
-        key_version: KEY_VERSION,
+        key_version,
         nonce: URL_SAFE_NO_PAD.encode(nonce_bytes),
         ciphertext: URL_SAFE_NO_PAD.encode(ciphertext),
-    })
+        hmac: String::new(),
+        legacy: false,
+    };
+    encrypted.hmac = URL_SAFE_NO_PAD.encode(
+        credentials_mac(master_key, &encrypted)?
+            .finalize()
+            .into_bytes(),
+    );
+    Ok(encrypted)
+}
+
+/// MAC over the blob metadata, keyed separately from the cipher key so that
+/// `alg` and `key_version` edits are caught before decryption.
+fn credentials_mac(
+    master_key: &[u8; 32],
+    encrypted: &EncryptedCredentials,
+) -> Result<Hmac<Sha256>> {
+    let hkdf = Hkdf::<Sha256>::new(Some(HKDF_SALT), master_key);
+    let mut mac_key = Zeroizing::new([0u8; 32]);
+    hkdf.expand(HMAC_INFO, mac_key.as_mut())
+        .map_err(|_| BackendError::Crypto("key derivation failed".to_string()))?;
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&mac_key[..])
+        .map_err(|_| BackendError::Crypto("invalid integrity key".to_string()))?;
+    mac.update(
+        format!(
+            "{}:{}:{}:{}",
+            encrypted.alg, encrypted.key_version, encrypted.nonce, encrypted.ciphertext
+        )
+        .as_bytes(),
+    );
+    Ok(mac)
+}
+
+fn verify_credentials_mac(master_key: &[u8; 32], encrypted: &EncryptedCredentials) -> Result<()> {
+    // Blobs flagged by the store migration predate the MAC and rotation, so
+    // they can only be on the base key version; they are re-encrypted on the
+    // next read. Any other blob without a MAC has been tampered with.
+    if encrypted.hmac.is_empty() {
+        if encrypted.legacy && encrypted.key_version == BASE_KEY_VERSION {
+            return Ok(());
+        }
+        return Err(BackendError::Crypto(
+            "credential integrity check failed".to_string(),
+        ));
+    }
+    let expected = URL_SAFE_NO_PAD
+        .decode(&encrypted.hmac)
+        .map_err(|_| BackendError::Crypto("credential integrity check failed".to_string()))?;
+    credentials_mac(master_key, encrypted)?
+        .verify_slice(&expected)
+        .map_err(|_| BackendError::Crypto("credential integrity check failed".to_string()))
 }
 
 #[allow(dead_code)]
@@ -128,13 +476,26 @@ fn decrypt_credentials<R: Runtime>(
     account: &AccountRecord,
     encrypted: &EncryptedCredentials,
 ) -> Result<serde_json::Value> {
-    if encrypted.key_version > KEY_VERSION {
+    if encrypted.key_version > current_key_version() {
         return Err(BackendError::Crypto(format!(
             "unsupported key version: {}",
             encrypted.key_version
         )));
     }
 
+    let master_key = read_master_key(app, encrypted.key_version)?.ok_or_else(|| {
+        BackendError::Crypto(format!("master key v{} missing", encrypted.key_version))
+    })?;
+    decrypt_with_master_key(&master_key, account, encrypted)
+}
+
+fn decrypt_with_master_key(
+    master_key: &[u8; 32],
+    account: &AccountRecord,
+    encrypted: &EncryptedCredentials,
+) -> Result<serde_json::Value> {
+    verify_credentials_mac(master_key, encrypted)?;
+
     let nonce_bytes = URL_SAFE_NO_PAD
         .decode(&encrypted.nonce)
         .map_err(|err| BackendError::Crypto(format!("invalid nonce: {err}")))?;
@@ -142,19 +503,15 @@ fn decrypt_credentials<R: Runtime>(
         .decode(&encrypted.ciphertext)
         .map_err(|err| BackendError::Crypto(format!("invalid ciphertext: {err}")))?;
 
-    let master_key = read_master_key(app, encrypted.key_version)?.ok_or_else(|| {
-        BackendError::Crypto(format!("master key v{} missing", encrypted.key_version))
-    })?;
+    let credential_id = credential_id(account)?;
+    let key = derive_key(master_key, &credential_id)?;
 
-    let credential_id = credential_id(account);
-    let key = derive_key(&master_key, &credential_id)?;
-
-    let plaintext = match encrypted.alg.as_str() {
+    let plaintext = Zeroizing::new(match encrypted.alg.as_str() {
         "xchacha20poly1305" => {
             if nonce_bytes.len() != 24 {
                 return Err(BackendError::Crypto("invalid nonce length".to_string()));
             }
-            let cipher = XChaCha20Poly1305::new_from_slice(&key)
+            let cipher = XChaCha20Poly1305::new_from_slice(&key[..])
                 .map_err(|_| BackendError::Crypto("invalid decryption key".to_string()))?;
             let nonce = XNonce::from_slice(&nonce_bytes);
             cipher
@@ -171,7 +528,7 @@ fn decrypt_credentials<R: Runtime>(
             if nonce_bytes.len() != 12 {
                 return Err(BackendError::Crypto("invalid nonce length".to_string()));
             }
-            let cipher = ChaCha20Poly1305::new_from_slice(&key)
+            let cipher = ChaCha20Poly1305::new_from_slice(&key[..])
                 .map_err(|_| BackendError::Crypto("invalid decryption key".to_string()))?;
             let nonce = Nonce::from_slice(&nonce_bytes);
             cipher
@@ -190,7 +547,7 @@ fn decrypt_credentials<R: Runtime>(
                 encrypted.alg
             )));
         }
-    };
+    });
 
     let value = serde_json::from_slice(&plaintext)?;
     Ok(value)
@@ -224,9 +581,19 @@ pub fn get_account_credentials<R: Runtime>(
     };
 
     let value = decrypt_credentials(app, &account, &encrypted)?;
-    if encrypted.key_version != KEY_VERSION || encrypted.alg != ALGORITHM {
+    if encrypted.key_version != current_key_version()
+        || encrypted.alg != ALGORITHM
+        || encrypted.hmac.is_empty()
+    {
         let updated = encrypt_credentials(app, &account, &value)?;
-        store.set_credentials_blob(account_id, updated)?;
+        // While the keyring is still down a passphrase blob would only be
+        // re-sealed under the same key, so skip the write.
+        if updated.key_version != encrypted.key_version
+            || encrypted.alg != ALGORITHM
+            || encrypted.hmac.is_empty()
+        {
+            store.set_credentials_blob(account_id, updated)?;
+        }
     }
 
     Ok(Some(value))
@@ -239,3 +606,206 @@ pub fn has_account_credentials(store: &AccountStore, account_id: &str) -> Result
 pub fn clear_account_credentials(store: &AccountStore, account_id: &str) -> Result<()> {
     store.delete_credentials_blob(account_id)
 }
+
+/// Re-encrypts every stored credential blob under a freshly generated master
+/// key and returns the new key version. Blobs are only written once all of them
+/// re-encrypt successfully, and older keys are kept in the keyring.
+pub async fn rotate_master_key<R: Runtime>(
+    app: &AppHandle<R>,
+    store: &AccountStore,
+) -> Result<u32> {
+    let old_version = current_key_version();
+    let new_version = old_version
+        .checked_add(1)
+        .ok_or_else(|| BackendError::Crypto("master key version overflow".to_string()))?;
+
+    let mut new_key = Zeroizing::new([0u8; 32]);
+    OsRng.fill_bytes(new_key.as_mut());
+    app.keyring()
+        .set_secret(SERVICE_NAME, &master_key_name(new_version), &new_key[..])
+        .map_err(|err| BackendError::Keyring(err.to_string()))?;
+
+    let mut updates = Vec::new();
+    for account in all_accounts(store)? {
+        let Some(encrypted) = account.credentials.as_ref() else {
+            continue;
+        };
+        let master_key = read_master_key(app, encrypted.key_version)?.ok_or_else(|| {
+            BackendError::Crypto(format!("master key v{} missing", encrypted.key_version))
+        })?;
+        let reencrypted =
+            reencrypt_credentials(&master_key, &new_key, new_version, &account, encrypted)?;
+        updates.push((account.id.clone(), encrypted.nonce.clone(), reencrypted));
+    }
+
+    let rotated = store.replace_credentials_blobs(updates)?;
+    KEY_VERSION.store(new_version, Ordering::SeqCst);
+    clear_master_key_cache();
+    log::info!(
+        "[secrets] rotated master key version={} credentials={}",
+        new_version,
+        rotated
+    );
+    Ok(new_version)
+}
+
+fn reencrypt_credentials(
+    old_master_key: &[u8; 32],
+    new_master_key: &[u8; 32],
+    new_version: u32,
+    account: &AccountRecord,
+    encrypted: &EncryptedCredentials,
+) -> Result<EncryptedCredentials> {
+    let value = decrypt_with_master_key(old_master_key, account, encrypted)?;
+    encrypt_with_master_key(new_master_key, new_version, account, &value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account() -> AccountRecord {
+        serde_json::from_value(serde_json::json!({
+            "id": "acc-1",
+            "providerId": "codex",
+            "label": "Work",
+            "settings": {},
+            "createdAt": "2026-01-01T00:00:00Z",
+            "updatedAt": "2026-01-01T00:00:00Z",
+        }))
+        .expect("account should deserialize")
+    }
+
+    #[test]
+    fn reencrypted_credentials_decrypt_to_the_same_value() {
+        clear_master_key_cache();
+        let account = account();
+        let credentials = serde_json::json!({ "access_token": "token", "expires_at": 42 });
+        let old_key = [7u8; 32];
+        let new_key = [9u8; 32];
+
+        let encrypted = encrypt_with_master_key(&old_key, 1, &account, &credentials)
+            .expect("encrypt should succeed");
+        let before = decrypt_with_master_key(&old_key, &account, &encrypted)
+            .expect("decrypt should succeed");
+
+        let rotated = reencrypt_credentials(&old_key, &new_key, 2, &account, &encrypted)
+            .expect("re-encrypt should succeed");
+        assert_eq!(rotated.key_version, 2);
+        let after =
+            decrypt_with_master_key(&new_key, &account, &rotated).expect("decrypt should succeed");
+
+        assert_eq!(before, credentials);
+        assert_eq!(after, before);
+        assert!(decrypt_with_master_key(&old_key, &account, &rotated).is_err());
+    }
+
+    #[test]
+    fn tampered_metadata_fails_integrity_check() {
+        let account = account();
+        let credentials = serde_json::json!({ "apiKey": "secret" });
+        let key = [3u8; 32];
+        let encrypted = encrypt_with_master_key(&key, 1, &account, &credentials)
+            .expect("encrypt should succeed");
+        assert!(!encrypted.hmac.is_empty());
+
+        let tampered = EncryptedCredentials {
+            alg: "chacha20poly1305".to_string(),
+            ..encrypted.clone()
+        };
+        match decrypt_with_master_key(&key, &account, &tampered) {
+            Err(BackendError::Crypto(message)) => {
+                assert_eq!(message, "credential integrity check failed")
+            }
+            other => panic!("expected integrity failure, got {other:?}"),
+        }
+
+        let legacy = EncryptedCredentials {
+            hmac: String::new(),
+            legacy: true,
+            ..encrypted.clone()
+        };
+        assert_eq!(
+            decrypt_with_master_key(&key, &account, &legacy).expect("legacy blob should decrypt"),
+            credentials
+        );
+    }
+
+    #[test]
+    fn blanked_hmac_is_rejected_unless_flagged_legacy() {
+        let account = account();
+        let credentials = serde_json::json!({ "apiKey": "secret" });
+        let key = [3u8; 32];
+        let encrypted = encrypt_with_master_key(&key, 1, &account, &credentials)
+            .expect("encrypt should succeed");
+
+        let blanked = EncryptedCredentials {
+            hmac: String::new(),
+            ..encrypted.clone()
+        };
+        let blanked_and_bumped = EncryptedCredentials {
+            key_version: 2,
+            ..blanked.clone()
+        };
+        let forged_legacy = EncryptedCredentials {
+            legacy: true,
+            ..blanked_and_bumped.clone()
+        };
+        for tampered in [blanked, blanked_and_bumped, forged_legacy] {
+            match decrypt_with_master_key(&key, &account, &tampered) {
+                Err(BackendError::Crypto(message)) => {
+                    assert_eq!(message, "credential integrity check failed")
+                }
+                other => panic!("expected integrity failure, got {other:?}"),
+            }
+        }
+    }
+
+    #[cfg(feature = "passphrase-key")]
+    #[test]
+    fn passphrase_verifier_rejects_a_different_key() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let path = dir.path().join(VERIFIER_FILE_NAME);
+        let salt = [1u8; 32];
+        let key = derive_master_key_from_passphrase("correct horse", &salt)
+            .expect("derive should succeed");
+        let wrong =
+            derive_master_key_from_passphrase("wrong horse", &salt).expect("derive should succeed");
+
+        verify_or_store_passphrase(&path, &key).expect("first passphrase should be stored");
+        assert!(path.exists());
+        verify_or_store_passphrase(&path, &key).expect("same passphrase should verify");
+        *PASSPHRASE_KEY
+            .lock()
+            .expect("passphrase key mutex poisoned") = None;
+
+        match verify_or_store_passphrase(&path, &wrong) {
+            Err(BackendError::Validation(message)) => {
+                assert_eq!(message, "master key passphrase is incorrect")
+            }
+            other => panic!("expected rejected passphrase, got {other:?}"),
+        }
+        assert!(PASSPHRASE_KEY
+            .lock()
+            .expect("passphrase key mutex poisoned")
+            .is_none());
+    }
+
+    #[cfg(feature = "passphrase-key")]
+    #[test]
+    fn passphrase_key_is_deterministic_per_salt() {
+        let salt = [1u8; 32];
+        let key = derive_master_key_from_passphrase("correct horse", &salt)
+            .expect("derive should succeed");
+        assert_eq!(
+            derive_master_key_from_passphrase("correct horse", &salt)
+                .expect("derive should succeed"),
+            key
+        );
+        assert_ne!(
+            derive_master_key_from_passphrase("correct horse", &[2u8; 32])
+                .expect("derive should succeed"),
+            key
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::error::{BackendError, Result};
+use crate::utils::write_file_atomic;
+
+// `settings.json` belongs to the frontend/tray plugin store.
+const STORE_FILE_NAME: &str = "app-settings.json";
+const STORE_SCHEMA_VERSION: u32 = 1;
+const PANEL_AUTO_HIDE_SETTING: &str = "panelAutoHide";
+const PANEL_SHORTCUT_SETTING: &str = "panelShortcut";
+pub const DEFAULT_PANEL_SHORTCUT: &str = "CommandOrControl+Shift+Space";
+const LOG_LEVEL_SETTING: &str = "logLevel";
+const DEFAULT_LOG_LEVEL: log::LevelFilter = log::LevelFilter::Info;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SettingsStoreFile {
+    schema_version: u32,
+    #[serde(default)]
+    settings: serde_json::Map<String, serde_json::Value>,
+}
+
+/// App-wide user preferences persisted next to `accounts.json`.
+#[derive(Debug)]
+pub struct SettingsStore {
+    path: PathBuf,
+    state: RwLock<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl SettingsStore {
+    pub fn load(app: &AppHandle) -> Result<Self> {
+        let data_dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|err| BackendError::Path(err.to_string()))?;
+        fs::create_dir_all(&data_dir)?;
+        let path = data_dir.join(STORE_FILE_NAME);
+        Self::load_from_path(path)
+    }
+
+    pub(crate) fn load_from_path(path: PathBuf) -> Result<Self> {
+        let settings = match fs::read_to_string(&path) {
+            Ok(contents) => {
+                if contents.trim().is_empty() {
+                    serde_json::Map::new()
+                } else {
+                    parse_store_contents(&contents)?
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => serde_json::Map::new(),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Self {
+            path,
+            state: RwLock::new(settings),
+        })
+    }
+
+    pub fn get_setting(&self, key: &str) -> Result<Option<serde_json::Value>> {
+        let key = normalize_key(key)?;
+        let state = self.read_state()?;
+        Ok(state.get(key).cloned())
+    }
+
+    /// Whether the panel hides when another app becomes active. Defaults to `false`.
+    pub fn panel_auto_hide(&self) -> bool {
+        self.get_setting(PANEL_AUTO_HIDE_SETTING)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false)
+    }
+
+    pub fn set_panel_auto_hide(&self, enabled: bool) -> Result<()> {
+        self.set_setting(PANEL_AUTO_HIDE_SETTING, serde_json::json!(enabled))
+    }
+
+    /// Accelerator that toggles the panel, e.g. `CommandOrControl+Shift+Space`.
+    pub fn panel_shortcut(&self) -> String {
+        self.get_setting(PANEL_SHORTCUT_SETTING)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_str().map(str::trim).map(str::to_string))
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| DEFAULT_PANEL_SHORTCUT.to_string())
+    }
+
+    pub fn set_panel_shortcut(&self, shortcut: &str) -> Result<()> {
+        self.set_setting(PANEL_SHORTCUT_SETTING, serde_json::json!(shortcut))
+    }
+
+    /// Global backend log level. Defaults to `info`.
+    pub fn log_level(&self) -> log::LevelFilter {
+        self.get_setting(LOG_LEVEL_SETTING)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_str().and_then(parse_log_level))
+            .unwrap_or(DEFAULT_LOG_LEVEL)
+    }
+
+    pub fn set_log_level(&self, level: log::LevelFilter) -> Result<()> {
+        self.set_setting(LOG_LEVEL_SETTING, serde_json::json!(log_level_name(level)))
+    }
+
+    pub fn set_setting(&self, key: &str, value: serde_json::Value) -> Result<()> {
+        let key = normalize_key(key)?;
+        let mut state = self.write_state()?;
+        state.insert(key.to_string(), value);
+        self.save_locked(&state)
+    }
+
+    fn read_state(
+        &self,
+    ) -> Result<RwLockReadGuard<'_, serde_json::Map<String, serde_json::Value>>> {
+        self.state
+            .read()
+            .map_err(|_| BackendError::Store("settings store lock poisoned".to_string()))
+    }
+
+    fn write_state(
+        &self,
+    ) -> Result<RwLockWriteGuard<'_, serde_json::Map<String, serde_json::Value>>> {
+        self.state
+            .write()
+            .map_err(|_| BackendError::Store("settings store lock poisoned".to_string()))
+    }
+
+    fn save_locked(&self, settings: &serde_json::Map<String, serde_json::Value>) -> Result<()> {
+        let payload = SettingsStoreFile {
+            schema_version: STORE_SCHEMA_VERSION,
+            settings: settings.clone(),
+        };
+        let serialized = serde_json::to_string_pretty(&payload)?;
+        write_file_atomic(&self.path, serialized.as_bytes())
+    }
+}
+
+fn normalize_key(key: &str) -> Result<&str> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(BackendError::Validation(
+            "setting key is required".to_string(),
+        ));
+    }
+    Ok(key)
+}
+
+/// Parses one of `error`, `warn`, `info`, `debug` or `trace`.
+pub fn parse_log_level(value: &str) -> Option<log::LevelFilter> {
+    match value.trim() {
+        "error" => Some(log::LevelFilter::Error),
+        "warn" => Some(log::LevelFilter::Warn),
+        "info" => Some(log::LevelFilter::Info),
+        "debug" => Some(log::LevelFilter::Debug),
+        "trace" => Some(log::LevelFilter::Trace),
+        _ => None,
+    }
+}
+
+pub fn log_level_name(level: log::LevelFilter) -> &'static str {
+    match level {
+        log::LevelFilter::Off => "off",
+        log::LevelFilter::Error => "error",
+        log::LevelFilter::Warn => "warn",
+        log::LevelFilter::Info => "info",
+        log::LevelFilter::Debug => "debug",
+        log::LevelFilter::Trace => "trace",
+    }
+}
+
+fn parse_store_contents(contents: &str) -> Result<serde_json::Map<String, serde_json::Value>> {
+    let store_file = serde_json::from_str::<SettingsStoreFile>(contents)?;
+    if store_file.schema_version != STORE_SCHEMA_VERSION {
+        return Err(BackendError::Store(format!(
+            "unsupported settings store schema version: {}",
+            store_file.schema_version
+        )));
+    }
+    Ok(store_file.settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn set_setting_persists_and_reloads() {
+        let dir =
+            std::env::temp_dir().join(format!("openburn-settings-store-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("temp dir should be created");
+        let path = dir.join(STORE_FILE_NAME);
+
+        let store = SettingsStore::load_from_path(path.clone()).expect("store should load");
+        assert_eq!(
+            store.get_setting("panelWidth").expect("get should succeed"),
+            None
+        );
+        store
+            .set_setting("panelWidth", serde_json::json!(420))
+            .expect("set should succeed");
+        assert!(store.set_setting("  ", serde_json::json!(true)).is_err());
+        drop(store);
+
+        let reloaded = SettingsStore::load_from_path(path).expect("store should reload");
+        assert_eq!(
+            reloaded
+                .get_setting("panelWidth")
+                .expect("get should succeed"),
+            Some(serde_json::json!(420))
+        );
+
+        fs::remove_dir_all(dir).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn panel_preferences_default_and_round_trip() {
+        let dir =
+            std::env::temp_dir().join(format!("openburn-settings-store-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("temp dir should be created");
+        let path = dir.join(STORE_FILE_NAME);
+
+        let store = SettingsStore::load_from_path(path.clone()).expect("store should load");
+        assert!(!store.panel_auto_hide());
+        store
+            .set_setting(PANEL_AUTO_HIDE_SETTING, serde_json::json!("yes"))
+            .expect("set should succeed");
+        assert!(!store.panel_auto_hide());
+        store.set_panel_auto_hide(true).expect("set should succeed");
+        drop(store);
+
+        let reloaded = SettingsStore::load_from_path(path).expect("store should reload");
+        assert!(reloaded.panel_auto_hide());
+        assert_eq!(reloaded.panel_shortcut(), DEFAULT_PANEL_SHORTCUT);
+        reloaded
+            .set_panel_shortcut("Alt+Shift+U")
+            .expect("set should succeed");
+        assert_eq!(reloaded.panel_shortcut(), "Alt+Shift+U");
+        assert_eq!(
+            reloaded
+                .get_setting("panelAutoHide")
+                .expect("get should succeed"),
+            Some(serde_json::json!(true))
+        );
+
+        fs::remove_dir_all(dir).expect("temp dir should be removed");
+    }
+
+    #[test]
+    fn log_level_defaults_to_info_and_round_trips() {
+        let dir =
+            std::env::temp_dir().join(format!("openburn-settings-store-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("temp dir should be created");
+        let store =
+            SettingsStore::load_from_path(dir.join(STORE_FILE_NAME)).expect("store should load");
+
+        assert_eq!(store.log_level(), log::LevelFilter::Info);
+        store
+            .set_setting(LOG_LEVEL_SETTING, serde_json::json!("verbose"))
+            .expect("set should succeed");
+        assert_eq!(store.log_level(), log::LevelFilter::Info);
+        store
+            .set_log_level(log::LevelFilter::Debug)
+            .expect("set should succeed");
+        assert_eq!(store.log_level(), log::LevelFilter::Debug);
+        assert_eq!(
+            store.get_setting("logLevel").expect("get should succeed"),
+            Some(serde_json::json!("debug"))
+        );
+        assert_eq!(parse_log_level("off"), None);
+
+        fs::remove_dir_all(dir).expect("temp dir should be removed");
+    }
+}
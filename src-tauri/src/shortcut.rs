@@ -0,0 +1,83 @@
+//! Global keyboard shortcut that toggles the panel.
+
+use tauri::plugin::TauriPlugin;
+use tauri::{AppHandle, Manager, Wry};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::panel;
+use crate::settings_store::{SettingsStore, DEFAULT_PANEL_SHORTCUT};
+
+/// The plugin with a handler that toggles the panel on key press. Only the
+/// panel shortcut is ever registered, so every press is a toggle.
+pub fn plugin() -> TauriPlugin<Wry> {
+    tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(|app_handle, _shortcut, event| {
+            if event.state() != ShortcutState::Pressed {
+                return;
+            }
+            if let Err(err) = panel::toggle(app_handle) {
+                log::warn!("[shortcut] panel toggle failed: {}", err);
+            }
+        })
+        .build()
+}
+
+/// Registers the stored shortcut, falling back to the default when the stored
+/// value cannot be registered.
+pub fn register_stored(app_handle: &AppHandle) {
+    let Some(settings) = app_handle.try_state::<SettingsStore>() else {
+        return;
+    };
+    let shortcut = settings.panel_shortcut();
+    let Err(err) = app_handle.global_shortcut().register(shortcut.as_str()) else {
+        return;
+    };
+    log::warn!(
+        "[shortcut] failed to register shortcut={} error={}",
+        shortcut,
+        err
+    );
+    if shortcut != DEFAULT_PANEL_SHORTCUT {
+        if let Err(err) = app_handle
+            .global_shortcut()
+            .register(DEFAULT_PANEL_SHORTCUT)
+        {
+            log::warn!("[shortcut] failed to register default shortcut: {}", err);
+        }
+    }
+}
+
+/// Swaps the registered shortcut for `shortcut` and stores it. The previous
+/// shortcut is restored when the new one cannot be registered.
+pub fn replace(
+    app_handle: &AppHandle,
+    settings: &SettingsStore,
+    shortcut: &str,
+) -> Result<(), String> {
+    let shortcut = shortcut.trim();
+    let parsed = shortcut
+        .parse::<Shortcut>()
+        .map_err(|err| format!("invalid shortcut: {err}"))?;
+    let global_shortcut = app_handle.global_shortcut();
+    let previous = settings.panel_shortcut();
+
+    global_shortcut
+        .unregister_all()
+        .map_err(|err| err.to_string())?;
+    if let Err(err) = global_shortcut.register(parsed) {
+        let _ = global_shortcut.register(previous.as_str());
+        return Err(err.to_string());
+    }
+
+    settings
+        .set_panel_shortcut(shortcut)
+        .map_err(|err| err.to_string())?;
+    log::info!("[shortcut] panel shortcut set to {}", shortcut);
+    Ok(())
+}
+
+pub fn unregister_all(app_handle: &AppHandle) {
+    if let Err(err) = app_handle.global_shortcut().unregister_all() {
+        log::warn!("[shortcut] failed to unregister shortcuts: {}", err);
+    }
+}
@@ -1,48 +1,15 @@
 use tauri::image::Image;
-use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::menu::{CheckMenuItem, IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
 use tauri::path::BaseDirectory;
 use tauri::tray::{MouseButtonState, TrayIconBuilder, TrayIconEvent};
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Emitter, Manager, Wry};
 use tauri_nspanel::ManagerExt;
-use tauri_plugin_store::StoreExt;
 
+use crate::account_store::AccountStore;
 use crate::panel::position_panel_at_tray_icon;
-
-const LOG_LEVEL_STORE_KEY: &str = "logLevel";
-
-fn get_stored_log_level(app_handle: &AppHandle) -> log::LevelFilter {
-    let store = match app_handle.store("settings.json") {
-        Ok(s) => s,
-        Err(_) => return log::LevelFilter::Error,
-    };
-    let value = store.get(LOG_LEVEL_STORE_KEY);
-    let level_str = value.and_then(|v| v.as_str().map(|s| s.to_string()));
-    match level_str.as_deref() {
-        Some("error") => log::LevelFilter::Error,
-        Some("warn") => log::LevelFilter::Warn,
-        Some("info") => log::LevelFilter::Info,
-        Some("debug") => log::LevelFilter::Debug,
-        Some("trace") => log::LevelFilter::Trace,
-        _ => log::LevelFilter::Error, // Default: least verbose
-    }
-}
-
-fn set_stored_log_level(app_handle: &AppHandle, level: log::LevelFilter) {
-    let level_str = match level {
-        log::LevelFilter::Error => "error",
-        log::LevelFilter::Warn => "warn",
-        log::LevelFilter::Info => "info",
-        log::LevelFilter::Debug => "debug",
-        log::LevelFilter::Trace => "trace",
-        log::LevelFilter::Off => "off",
-    };
-    log::info!("Log level changing to {:?}", level);
-    if let Ok(store) = app_handle.store("settings.json") {
-        store.set(LOG_LEVEL_STORE_KEY, serde_json::json!(level_str));
-        let _ = store.save();
-    }
-    log::set_max_level(level);
-}
+use crate::providers;
+use crate::settings_store::SettingsStore;
+use crate::utils::{now_unix_ms, rfc3339_to_unix_ms};
 
 macro_rules! get_or_init_panel {
     ($app_handle:expr) => {
@@ -72,15 +39,141 @@ fn show_panel(app_handle: &AppHandle) {
     }
 }
 
-pub fn create(app_handle: &AppHandle) -> tauri::Result<()> {
-    let tray_icon_path = app_handle
-        .path()
-        .resolve("icons/Square44x44Logo.png", BaseDirectory::Resource)?;
-    let icon = Image::from_path(tray_icon_path)?;
+/// Shows how many accounts are at high usage: the Dock badge on macOS, the
+/// tray tooltip elsewhere. A `count` of 0 clears it.
+pub fn update_badge(app: &AppHandle, count: u32) {
+    #[cfg(target_os = "macos")]
+    {
+        let label = (count > 0).then(|| count.to_string());
+        if let Err(err) = app.run_on_main_thread(move || set_dock_badge_label(label.as_deref())) {
+            log::warn!("[tray] failed to update badge: {}", err);
+        }
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let tooltip = match count {
+            0 => "OpenBurn".to_string(),
+            1 => "OpenBurn - 1 account above 80% usage".to_string(),
+            _ => format!("OpenBurn - {count} accounts above 80% usage"),
+        };
+        if let Some(tray) = app.tray_by_id("tray") {
+            if let Err(err) = tray.set_tooltip(Some(tooltip)) {
+                log::warn!("[tray] failed to update tooltip: {}", err);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn set_dock_badge_label(label: Option<&str>) {
+    use objc2::runtime::AnyObject;
+    use objc2::{class, msg_send};
+    use objc2_foundation::NSString;
 
-    // Load persisted log level
-    let current_level = get_stored_log_level(app_handle);
-    log::set_max_level(current_level);
+    let label = label.map(NSString::from_str);
+    unsafe {
+        let app: *mut AnyObject = msg_send![class!(NSApplication), sharedApplication];
+        let dock_tile: *mut AnyObject = msg_send![app, dockTile];
+        let _: () = msg_send![dock_tile, setBadgeLabel: label.as_deref()];
+    }
+}
+
+/// One provider row in the tray menu. With no fetch time and no error the row
+/// reads "Loading...".
+#[derive(Debug, Clone)]
+pub struct ProviderStatus {
+    pub provider_id: String,
+    pub last_fetched_at: Option<String>,
+    pub has_error: bool,
+}
+
+fn provider_status_label(status: &ProviderStatus, now_ms: i64) -> String {
+    let name = providers::find_provider_runtime(&status.provider_id)
+        .map(|runtime| runtime.name())
+        .unwrap_or(status.provider_id.as_str());
+    if status.has_error {
+        // Native menu items cannot be colored, so the marker carries the red.
+        return format!("{name} \u{2014} \u{1f534} Error");
+    }
+    let Some(fetched_ms) = status
+        .last_fetched_at
+        .as_deref()
+        .and_then(rfc3339_to_unix_ms)
+    else {
+        return format!("{name} \u{2014} Loading...");
+    };
+    let minutes = now_ms.saturating_sub(fetched_ms).max(0) / 60_000;
+    match minutes {
+        0 => format!("{name} \u{2014} fetched just now"),
+        1 => format!("{name} \u{2014} fetched 1 minute ago"),
+        _ => format!("{name} \u{2014} fetched {minutes} minutes ago"),
+    }
+}
+
+/// Providers with at least one enabled account, in registry order, with the
+/// latest fetch time across those accounts and whether any last failed.
+fn provider_statuses(app_handle: &AppHandle) -> Vec<ProviderStatus> {
+    let Some(store) = app_handle.try_state::<AccountStore>() else {
+        return Vec::new();
+    };
+    let accounts = match store.list_accounts() {
+        Ok(accounts) => accounts,
+        Err(err) => {
+            log::warn!("[tray] failed to list accounts: {}", err);
+            return Vec::new();
+        }
+    };
+    providers::all_provider_ids()
+        .into_iter()
+        .filter_map(|provider_id| {
+            let provider_accounts = accounts
+                .iter()
+                .filter(|account| {
+                    account.provider_id == provider_id && account.disabled != Some(true)
+                })
+                .collect::<Vec<_>>();
+            if provider_accounts.is_empty() {
+                return None;
+            }
+            let last_fetched_at = provider_accounts
+                .iter()
+                .filter_map(|account| account.last_fetch_at.as_deref())
+                .max_by_key(|value| rfc3339_to_unix_ms(value))
+                .map(str::to_string);
+            let has_error = provider_accounts
+                .iter()
+                .any(|account| account.last_error.is_some());
+            Some(ProviderStatus {
+                provider_id,
+                last_fetched_at,
+                has_error,
+            })
+        })
+        .collect()
+}
+
+pub fn create_menu_with_provider_status(
+    app_handle: &AppHandle,
+    statuses: &[ProviderStatus],
+) -> tauri::Result<Menu<Wry>> {
+    let current_level = log::max_level();
+    let now_ms = now_unix_ms();
+
+    let refresh_all =
+        MenuItem::with_id(app_handle, "refresh_all", "Refresh All", true, None::<&str>)?;
+    let status_items = statuses
+        .iter()
+        .map(|status| {
+            MenuItem::with_id(
+                app_handle,
+                format!("provider_status:{}", status.provider_id),
+                provider_status_label(status, now_ms),
+                false,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
 
     let show_stats = MenuItem::with_id(app_handle, "show_stats", "Show Stats", true, None::<&str>)?;
     let go_to_settings = MenuItem::with_id(
@@ -91,78 +184,87 @@ pub fn create(app_handle: &AppHandle) -> tauri::Result<()> {
         None::<&str>,
     )?;
 
-    // Log level submenu - clone items for use in event handler
-    let log_error = CheckMenuItem::with_id(
-        app_handle,
-        "log_error",
-        "Error",
-        true,
-        current_level == log::LevelFilter::Error,
-        None::<&str>,
-    )?;
-    let log_warn = CheckMenuItem::with_id(
-        app_handle,
-        "log_warn",
-        "Warn",
-        true,
-        current_level == log::LevelFilter::Warn,
-        None::<&str>,
-    )?;
-    let log_info = CheckMenuItem::with_id(
-        app_handle,
-        "log_info",
-        "Info",
-        true,
-        current_level == log::LevelFilter::Info,
-        None::<&str>,
-    )?;
-    let log_debug = CheckMenuItem::with_id(
-        app_handle,
-        "log_debug",
-        "Debug",
-        true,
-        current_level == log::LevelFilter::Debug,
-        None::<&str>,
-    )?;
-    let log_trace = CheckMenuItem::with_id(
-        app_handle,
-        "log_trace",
-        "Trace",
-        true,
-        current_level == log::LevelFilter::Trace,
-        None::<&str>,
-    )?;
-    let log_level_submenu = Submenu::with_items(
-        app_handle,
-        "Debug Level",
-        true,
-        &[&log_error, &log_warn, &log_info, &log_debug, &log_trace],
-    )?;
-
-    // Clone for capture in event handler
     let log_items = [
-        (log_error.clone(), log::LevelFilter::Error),
-        (log_warn.clone(), log::LevelFilter::Warn),
-        (log_info.clone(), log::LevelFilter::Info),
-        (log_debug.clone(), log::LevelFilter::Debug),
-        (log_trace.clone(), log::LevelFilter::Trace),
-    ];
-
-    let separator = PredefinedMenuItem::separator(app_handle)?;
+        ("log_error", "Error", log::LevelFilter::Error),
+        ("log_warn", "Warn", log::LevelFilter::Warn),
+        ("log_info", "Info", log::LevelFilter::Info),
+        ("log_debug", "Debug", log::LevelFilter::Debug),
+        ("log_trace", "Trace", log::LevelFilter::Trace),
+    ]
+    .into_iter()
+    .map(|(id, label, level)| {
+        CheckMenuItem::with_id(
+            app_handle,
+            id,
+            label,
+            true,
+            current_level == level,
+            None::<&str>,
+        )
+    })
+    .collect::<tauri::Result<Vec<_>>>()?;
+    let log_item_refs = log_items
+        .iter()
+        .map(|item| item as &dyn IsMenuItem<Wry>)
+        .collect::<Vec<_>>();
+    let log_level_submenu = Submenu::with_items(app_handle, "Debug Level", true, &log_item_refs)?;
+
     let about = MenuItem::with_id(app_handle, "about", "About OpenBurn", true, None::<&str>)?;
     let quit = MenuItem::with_id(app_handle, "quit", "Quit", true, None::<&str>)?;
 
-    let menu = Menu::with_items(
-        app_handle,
-        &[
-            &show_stats,
-            &go_to_settings,
-            &log_level_submenu,
-            &separator,
-            &about,
-            &quit,
-        ],
-    )?;
+    let menu = Menu::new(app_handle)?;
+    menu.append(&refresh_all)?;
+    if !status_items.is_empty() {
+        menu.append(&PredefinedMenuItem::separator(app_handle)?)?;
+        for item in &status_items {
+            menu.append(item)?;
+        }
+    }
+    menu.append(&PredefinedMenuItem::separator(app_handle)?)?;
+    menu.append_items(&[&show_stats, &go_to_settings, &log_level_submenu, &about])?;
+    menu.append(&PredefinedMenuItem::separator(app_handle)?)?;
+    menu.append(&quit)?;
+    Ok(menu)
+}
+
+/// Rebuilds the tray menu from the account store on the main thread.
+pub fn refresh_provider_status(app_handle: &AppHandle) {
+    let handle = app_handle.clone();
+    let result = app_handle.run_on_main_thread(move || {
+        let Some(tray) = handle.tray_by_id("tray") else {
+            return;
+        };
+        let statuses = provider_statuses(&handle);
+        match create_menu_with_provider_status(&handle, &statuses) {
+            Ok(menu) => {
+                if let Err(err) = tray.set_menu(Some(menu)) {
+                    log::warn!("[tray] failed to set menu: {}", err);
+                }
+            }
+            Err(err) => log::warn!("[tray] failed to build menu: {}", err),
+        }
+    });
+    if let Err(err) = result {
+        log::warn!("[tray] failed to schedule menu refresh: {}", err);
+    }
+}
+
+pub fn create(app_handle: &AppHandle) -> tauri::Result<()> {
+    let tray_icon_path = app_handle
+        .path()
+        .resolve("icons/Square44x44Logo.png", BaseDirectory::Resource)?;
+    let icon = Image::from_path(tray_icon_path)?;
+
+    // No probe has finished yet, so every provider starts out as "Loading...".
+    let loading = provider_statuses(app_handle)
+        .into_iter()
+        .map(|status| ProviderStatus {
+            last_fetched_at: None,
+            has_error: false,
+            ..status
+        })
+        .collect::<Vec<_>>();
+    let menu = create_menu_with_provider_status(app_handle, &loading)?;
 
     TrayIconBuilder::with_id("tray")
         .icon(icon)
@@ -173,6 +275,10 @@ pub fn create(app_handle: &AppHandle) -> tauri::Result<()> {
         .on_menu_event(move |app_handle, event| {
             log::debug!("tray menu: {}", event.id.as_ref());
             match event.id.as_ref() {
+                "refresh_all" => {
+                    show_panel(app_handle);
+                    let _ = app_handle.emit("tray:refresh-all", ());
+                }
                 "show_stats" => {
                     show_panel(app_handle);
                     let _ = app_handle.emit("tray:navigate", "home");
@@ -198,11 +304,15 @@ pub fn create(app_handle: &AppHandle) -> tauri::Result<()> {
                         "log_trace" => log::LevelFilter::Trace,
                         _ => unreachable!(),
                     };
-                    set_stored_log_level(app_handle, selected_level);
-                    // Update all checkmarks - only the selected level should be checked
-                    for (item, level) in &log_items {
-                        let _ = item.set_checked(*level == selected_level);
+                    log::info!("[logs] log level changing to {:?}", selected_level);
+                    log::set_max_level(selected_level);
+                    if let Some(settings) = app_handle.try_state::<SettingsStore>() {
+                        if let Err(err) = settings.set_log_level(selected_level) {
+                            log::warn!("[logs] failed to save log level: {}", err);
+                        }
                     }
+                    // Rebuild so only the selected level is checked
+                    refresh_provider_status(app_handle);
                 }
                 _ => {}
             }
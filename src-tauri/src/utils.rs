@@ -1,5 +1,62 @@
+use std::io::Write;
+use std::path::Path;
+#[cfg(test)]
+use std::sync::atomic::{AtomicI64, Ordering};
+#[cfg(test)]
+use std::sync::Arc;
+use std::time::Instant;
+
+use tempfile::NamedTempFile;
 use time::format_description::well_known::Rfc3339;
 
+use crate::error::{BackendError, Result};
+
+/// Source of wall-clock time for stores and OAuth flows, so tests can swap in
+/// `MockClock` instead of reading the system time.
+pub trait ClockProvider: Send + Sync {
+    fn now_unix_ms(&self) -> i64;
+
+    fn now_rfc3339(&self) -> String {
+        unix_ms_to_rfc3339(self.now_unix_ms()).unwrap_or_else(|| "1970-01-01T00:00:00Z".to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl ClockProvider for SystemClock {
+    fn now_unix_ms(&self) -> i64 {
+        now_unix_ms()
+    }
+
+    fn now_rfc3339(&self) -> String {
+        now_rfc3339()
+    }
+}
+
+/// Manually advanced clock; clones share the same time.
+#[cfg(test)]
+#[derive(Debug, Clone, Default)]
+pub struct MockClock(Arc<AtomicI64>);
+
+#[cfg(test)]
+impl MockClock {
+    pub fn new(now_ms: i64) -> Self {
+        Self(Arc::new(AtomicI64::new(now_ms)))
+    }
+
+    pub fn advance_ms(&self, ms: i64) {
+        self.0.fetch_add(ms, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+impl ClockProvider for MockClock {
+    fn now_unix_ms(&self) -> i64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
 pub fn now_rfc3339() -> String {
     time::OffsetDateTime::now_utc()
         .format(&Rfc3339)
@@ -9,3 +66,101 @@ pub fn now_rfc3339() -> String {
 pub fn now_unix_ms() -> i64 {
     time::OffsetDateTime::now_utc().unix_timestamp_nanos() as i64 / 1_000_000
 }
+
+pub fn rfc3339_to_unix_ms(value: &str) -> Option<i64> {
+    let timestamp = time::OffsetDateTime::parse(value.trim(), &Rfc3339).ok()?;
+    Some((timestamp.unix_timestamp_nanos() / 1_000_000) as i64)
+}
+
+pub fn unix_ms_to_rfc3339(value: i64) -> Option<String> {
+    let timestamp =
+        time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(value) * 1_000_000).ok()?;
+    timestamp.format(&Rfc3339).ok()
+}
+
+/// Keeps only the last six characters of a workspace id for logs.
+pub fn mask_workspace_id(workspace_id: &str) -> String {
+    let visible_tail_len = 6;
+    if workspace_id.len() <= visible_tail_len {
+        return workspace_id.to_string();
+    }
+
+    let tail = &workspace_id[workspace_id.len() - visible_tail_len..];
+    format!("***{tail}")
+}
+
+pub fn now_instant() -> Instant {
+    Instant::now()
+}
+
+pub fn elapsed_ms_since(instant: Instant) -> u64 {
+    u64::try_from(instant.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Writes to a sibling temp file and renames it over `path`, so a crash
+/// mid-write never leaves a truncated file behind. The temp file is removed if
+/// any step fails.
+pub fn write_file_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| BackendError::Path(format!("{} has no parent", path.display())))?;
+    let mut temp_file = NamedTempFile::new_in(parent)?;
+    temp_file.write_all(contents)?;
+    temp_file.as_file().sync_all()?;
+    temp_file
+        .persist(path)
+        .map_err(|err| BackendError::Io(err.error))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dir_entries(dir: &Path) -> Vec<String> {
+        let mut entries = std::fs::read_dir(dir)
+            .expect("dir should be readable")
+            .map(|entry| {
+                entry
+                    .expect("entry should be readable")
+                    .file_name()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>();
+        entries.sort();
+        entries
+    }
+
+    #[test]
+    fn write_file_atomic_replaces_contents_without_leftovers() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let path = dir.path().join("store.json");
+        std::fs::write(&path, "old contents that are longer").expect("seed should be written");
+
+        write_file_atomic(&path, b"new").expect("atomic write should succeed");
+
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("file should be readable"),
+            "new"
+        );
+        assert_eq!(dir_entries(dir.path()), vec!["store.json"]);
+    }
+
+    #[test]
+    fn failed_persist_keeps_target_and_removes_temp_file() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        // A non-empty directory at the target path makes the final rename fail.
+        let path = dir.path().join("store.json");
+        std::fs::create_dir(&path).expect("target dir should be created");
+        std::fs::write(path.join("prior"), "prior").expect("prior file should be written");
+
+        assert!(write_file_atomic(&path, b"new").is_err());
+
+        assert_eq!(
+            std::fs::read_to_string(path.join("prior")).expect("prior file should survive"),
+            "prior"
+        );
+        assert_eq!(dir_entries(dir.path()), vec!["store.json"]);
+    }
+}
@@ -8,6 +8,7 @@ import { openUrl } from "@tauri-apps/plugin-opener"
 import { TrayIcon } from "@tauri-apps/api/tray"
 import { SideNav, type ActiveView } from "@/components/side-nav"
 import { PanelFooter } from "@/components/panel-footer"
+import { PassphraseDialog } from "@/components/passphrase-dialog"
 import { OverviewPage } from "@/pages/overview"
 import { ProviderDetailPage } from "@/pages/provider-detail"
 import { SettingsPage } from "@/pages/settings"
@@ -31,8 +32,8 @@ import {
   finishCodexOAuth,
   finishCopilotOAuth,
   finishOpencodeOAuth,
-  hasAccountCredentials,
-  listAccounts,
+  inferZaiRegion,
+  listAccountsWithCredentialStatus,
   listProviders,
   setAccountCredentials,
   startAntigravityOAuth,
@@ -220,6 +221,7 @@ function App() {
 
   const { updateStatus, triggerInstall } = useAppUpdate()
   const [showAbout, setShowAbout] = useState(false)
+  const [passphrasePrompt, setPassphrasePrompt] = useState<{ rejected: boolean } | null>(null)
 
   const trayRef = useRef<TrayIcon | null>(null)
   const trayFallbackIconPathRef = useRef<string | null>(null)
@@ -439,10 +441,10 @@ function App() {
     invoke("init_panel").catch(console.error);
   }, []);
 
-  // Hide panel on Escape key (unless about dialog is open - it handles its own Escape)
+  // Hide panel on Escape key (unless a dialog is open - it handles its own Escape)
   useEffect(() => {
     if (!isTauri()) return
-    if (showAbout) return // Let dialog handle its own Escape
+    if (showAbout || passphrasePrompt) return // Let dialog handle its own Escape
 
     const handleKeyDown = (e: KeyboardEvent) => {
       if (e.key === "Escape") {
@@ -451,7 +453,7 @@ function App() {
     }
     document.addEventListener("keydown", handleKeyDown)
     return () => document.removeEventListener("keydown", handleKeyDown)
-  }, [showAbout])
+  }, [showAbout, passphrasePrompt])
 
   // Listen for tray menu events
   useEffect(() => {
@@ -471,6 +473,14 @@ function App() {
       })
       if (cancelled) { u2(); return }
       unlisteners.push(u2)
+
+      // Keyring fallback (passphrase-key builds): the backend waits for
+      // submit_master_key_passphrase before it can read or save credentials
+      const u3 = await listen<{ rejected: boolean }>("auth:request-passphrase", (event) => {
+        setPassphrasePrompt({ rejected: event.payload?.rejected ?? false })
+      })
+      if (cancelled) { u3(); return }
+      unlisteners.push(u3)
     }
     void setup()
 
@@ -655,16 +665,10 @@ function App() {
       setAccountsLoading(true)
     }
     try {
-      const nextAccounts = await listAccounts()
-      const credentialsEntries = await Promise.all(
-        nextAccounts.map(async (account) => {
-          try {
-            const hasCredentials = await hasAccountCredentials(account.id)
-            return [account.id, hasCredentials] as const
-          } catch {
-            return [account.id, false] as const
-          }
-        }),
+      const rows = await listAccountsWithCredentialStatus()
+      const nextAccounts = rows.map(({ account }) => account)
+      const credentialsEntries = rows.map(
+        ({ account, hasCredentials }) => [account.id, hasCredentials] as const,
       )
 
       const { snapshots: prunedSnapshots, pruned } = pruneSnapshotsForAccounts(
@@ -1309,6 +1313,25 @@ function App() {
     })
   }, [providerSettings, setLoadingForProviders, setErrorForProviders, startBatch])
 
+  // "Refresh All" in the tray menu
+  useEffect(() => {
+    if (!isTauri()) return
+    let cancelled = false
+    let unlisten: (() => void) | undefined
+
+    void listen("tray:refresh-all", () => {
+      handleManualRefreshAll()
+    }).then((fn) => {
+      if (cancelled) fn()
+      else unlisten = fn
+    })
+
+    return () => {
+      cancelled = true
+      unlisten?.()
+    }
+  }, [handleManualRefreshAll])
+
   const hasEnabledProviders = useMemo(() => {
     if (!providerSettings) return false
     return getEnabledProviderIds(providerSettings).length > 0
@@ -1527,6 +1550,7 @@ function App() {
           onDeleteAccount={handleDeleteProviderAccount}
           onSaveAccountCredentials={handleSaveProviderAccountCredentials}
           onClearAccountCredentials={handleClearProviderAccountCredentials}
+          onInferZaiRegion={inferZaiRegion}
           accountOAuthSessionById={accountOAuthSessionById}
           onStartAccountOAuth={handleStartAccountOAuth}
           onCancelAccountOAuth={handleCancelAccountOAuth}
@@ -1590,6 +1614,12 @@ function App() {
             />
           </div>
         </div>
+        {passphrasePrompt && (
+          <PassphraseDialog
+            rejected={passphrasePrompt.rejected}
+            onClose={() => setPassphrasePrompt(null)}
+          />
+        )}
       </div>
     </div>
   );
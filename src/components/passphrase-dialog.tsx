@@ -0,0 +1,79 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { Button } from "@/components/ui/button";
+
+interface PassphraseDialogProps {
+  rejected: boolean;
+  onClose: () => void;
+}
+
+export function PassphraseDialog({ rejected, onClose }: PassphraseDialogProps) {
+  const [passphrase, setPassphrase] = useState("");
+  const [error, setError] = useState<string | null>(null);
+  const [submitting, setSubmitting] = useState(false);
+
+  // Close on ESC key; the backend request times out on its own
+  useEffect(() => {
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === "Escape") {
+        e.preventDefault();
+        onClose();
+      }
+    };
+    document.addEventListener("keydown", handleKeyDown);
+    return () => document.removeEventListener("keydown", handleKeyDown);
+  }, [onClose]);
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault();
+    if (!passphrase || submitting) return;
+    setSubmitting(true);
+    try {
+      await invoke("submit_master_key_passphrase", { passphrase });
+      setPassphrase("");
+      onClose();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setSubmitting(false);
+    }
+  };
+
+  return (
+    <div className="absolute inset-0 z-50 flex items-center justify-center bg-black/50 backdrop-blur-sm rounded-xl">
+      <form
+        className="bg-card rounded-lg border shadow-xl p-6 max-w-sm w-full mx-4 animate-in fade-in zoom-in-95 duration-200"
+        onSubmit={handleSubmit}
+      >
+        <h2 className="text-base font-semibold leading-none">Unlock credentials</h2>
+        <p className="mt-2 text-sm text-muted-foreground">
+          The system keychain is unavailable. Enter your master key passphrase to read and save
+          account credentials.
+        </p>
+
+        <input
+          type="password"
+          autoFocus
+          value={passphrase}
+          onChange={(event) => setPassphrase(event.target.value)}
+          placeholder="Passphrase"
+          className="mt-4 h-8 w-full rounded-md border border-input bg-background px-2 text-xs"
+        />
+        {(error || rejected) && (
+          <p className="mt-2 text-xs text-destructive">
+            {error ?? "Incorrect passphrase. Try again."}
+          </p>
+        )}
+
+        <div className="mt-4 grid grid-cols-2 gap-2">
+          <Button type="button" variant="outline" size="sm" onClick={onClose}>
+            Cancel
+          </Button>
+          <Button type="submit" size="sm" disabled={!passphrase || submitting}>
+            Unlock
+          </Button>
+        </div>
+      </form>
+    </div>
+  );
+}
@@ -89,6 +89,7 @@ interface ProviderAccountsSectionProps {
     credentials: Record<string, unknown>,
   ) => Promise<void>
   onClearAccountCredentials: (providerId: string, accountId: string) => Promise<void>
+  onInferZaiRegion?: (apiKey: string) => Promise<string | null>
   oauthSessionByAccount: Record<string, AccountOAuthSession | undefined>
   onStartAccountOAuth: (providerId: string, accountId: string) => Promise<void>
   onCancelAccountOAuth: (providerId: string, accountId: string) => Promise<void>
@@ -109,6 +110,7 @@ function authLabel(value?: string | null): string {
   if (value === "oauth") return "OAuth"
   if (value === "apiKey") return "API Key"
   if (value === "cookie") return "Cookie"
+  if (value === "pat") return "Personal Access Token"
   return value
 }
 
@@ -163,7 +165,14 @@ function resolveAccountFetchStatus(account: ProviderAccountSummary): ProviderFet
   }
 }
 
-function credentialTemplate(providerId: string): string {
+function credentialTemplate(providerId: string, authStrategyId?: string | null): string {
+  if (providerId === "copilot" && authStrategyId === "pat") {
+    return `{
+  "type": "pat",
+  "token": ""
+}`
+  }
+
   if (providerId === "codex") {
     return `{
   "type": "oauth",
@@ -270,6 +279,7 @@ export function ProviderAccountsSection({
   onDeleteAccount,
   onSaveAccountCredentials,
   onClearAccountCredentials,
+  onInferZaiRegion,
   oauthSessionByAccount,
   onStartAccountOAuth,
   onCancelAccountOAuth,
@@ -492,7 +502,8 @@ export function ProviderAccountsSection({
                         const copyOauthUrlActionId = `oauth:copy:${account.id}`
                         const deleteActionId = `delete:${account.id}`
                         const oauthSession = oauthSessionByAccount[account.id]
-                        const canOAuth = supportsNativeOAuth(provider.id)
+                        const canOAuth =
+                          supportsNativeOAuth(provider.id) && account.authStrategyId !== "pat"
                         const supportsManualCredentials = !canOAuth
                         const supportsZaiCredentialForm = provider.id === "zai"
                         const supportsJsonCredentials =
@@ -675,6 +686,18 @@ export function ProviderAccountsSection({
                                                     ...previous,
                                                     [account.id]: value,
                                                   }))
+                                                  if (!onInferZaiRegion) return
+                                                  // Only pre-fill; a region the user picked wins.
+                                                  void onInferZaiRegion(value)
+                                                    .then((region) => {
+                                                      if (!region) return
+                                                      setZaiRegionDraftByAccount((previous) =>
+                                                        previous[account.id]
+                                                          ? previous
+                                                          : { ...previous, [account.id]: region },
+                                                      )
+                                                    })
+                                                    .catch(() => {})
                                                 }}
                                                 className="h-8 w-full rounded-md border border-input bg-background px-2 text-xs"
                                                 placeholder="Enter Z.ai API key"
@@ -731,7 +754,7 @@ export function ProviderAccountsSection({
                                             }))
                                           }}
                                           className="min-h-28 w-full rounded-md border border-input bg-background px-2 py-2 text-xs font-mono"
-                                          placeholder={credentialTemplate(provider.id)}
+                                          placeholder={credentialTemplate(provider.id, account.authStrategyId)}
                                         />
                                       )}
 
@@ -9,7 +9,7 @@ import { SkeletonLines } from "@/components/skeleton-lines"
 import { ProviderError } from "@/components/provider-error"
 import { useNowTicker } from "@/hooks/use-now-ticker"
 import { type DisplayMode } from "@/lib/settings"
-import type { ManifestLine, MetricLine } from "@/lib/provider-types"
+import type { ManifestLine, MetricLine, ProgressFormat } from "@/lib/provider-types"
 import { clamp01 } from "@/lib/utils"
 import { calculatePaceStatus, type PaceStatus } from "@/lib/pace-status"
 import { buildPaceDetailText, formatCompactDuration, getPaceStatusText } from "@/lib/pace-tooltip"
@@ -44,6 +44,27 @@ function formatCount(value: number) {
   return new Intl.NumberFormat("en-US", { maximumFractionDigits }).format(value)
 }
 
+function formatMoney(value: number, format: ProgressFormat): string {
+  if (format.kind === "currency") {
+    if (!Number.isFinite(value)) return `${format.symbol}0`
+    return `${format.symbol}${new Intl.NumberFormat("en-US", {
+      minimumFractionDigits: format.decimals,
+      maximumFractionDigits: format.decimals,
+    }).format(value)}`
+  }
+  return `$${formatNumber(value)}`
+}
+
+function isMoneyFormat(format: ProgressFormat): boolean {
+  return format.kind === "currency" || format.kind === "dollars"
+}
+
+function formatUnitSuffix(format: ProgressFormat): string {
+  if (format.kind === "count") return format.suffix
+  if (format.kind === "time") return format.unit
+  return ""
+}
+
 function formatPercentValue(value: number, displayMode: DisplayMode): string {
   if (!Number.isFinite(value)) return "0"
   const clamped = Math.max(0, Math.min(100, value))
@@ -189,7 +210,11 @@ export function ProviderCard({
     : skeletonLines.filter(line => line.scope === "overview")
   const filteredLines = scopeFilter === "all"
     ? lines
-    : lines.filter((line) => line.type !== "progress" || overviewLabels.has(getBaseMetricLabel(line.label)))
+    : lines.filter(
+        (line) =>
+          (line.type !== "progress" && line.type !== "table") ||
+          overviewLabels.has(getBaseMetricLabel(line.label))
+      )
   const hasVisibleData = filteredLines.length > 0
   const showSkeleton = loading && !error && !hasVisibleData
 
@@ -437,6 +462,60 @@ function MetricLineRenderer({
     )
   }
 
+  if (line.type === "table") {
+    return (
+      <div>
+        <div className="text-sm font-medium mb-1.5">{line.label}</div>
+        <table className="w-full text-xs tabular-nums">
+          <thead>
+            <tr className="text-muted-foreground">
+              {line.columns.map((column) => (
+                <th key={column} className="text-left font-normal pb-1">
+                  {column}
+                </th>
+              ))}
+            </tr>
+          </thead>
+          <tbody>
+            {line.rows.map((row, rowIndex) => (
+              <tr key={`${row[0] ?? ""}-${rowIndex}`}>
+                {row.map((cell, cellIndex) => (
+                  <td key={cellIndex} className="py-0.5 truncate">
+                    {cell}
+                  </td>
+                ))}
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      </div>
+    )
+  }
+
+  if (line.type === "trend") {
+    const delta = line.current - line.previous
+    const sign = delta > 0 ? "+" : delta < 0 ? "-" : ""
+    const magnitude = Math.abs(delta)
+    const deltaText =
+      line.format.kind === "percent"
+        ? `${sign}${magnitude.toFixed(1)} pts`
+        : isMoneyFormat(line.format)
+          ? `${sign}${formatMoney(magnitude, line.format)}`
+          : `${sign}${formatCount(magnitude)} ${formatUnitSuffix(line.format)}`
+
+    return (
+      <div className="flex justify-between items-center h-[22px]">
+        <span className="text-sm text-muted-foreground flex-shrink-0">{line.label}</span>
+        <span
+          className="text-sm text-muted-foreground tabular-nums"
+          style={line.color ? { color: line.color } : undefined}
+        >
+          {deltaText}
+        </span>
+      </div>
+    )
+  }
+
   if (line.type === "progress") {
     const resetsAtMs = line.resetsAt ? Date.parse(line.resetsAt) : Number.NaN
     const hasPaceContext = Number.isFinite(resetsAtMs) && Number.isFinite(line.periodDurationMs)
@@ -450,18 +529,18 @@ function MetricLineRenderer({
     const primaryText =
       line.format.kind === "percent"
         ? `${formatPercentValue(shownAmount, displayMode)}%${leftSuffix}`
-        : line.format.kind === "dollars"
-          ? `$${formatNumber(shownAmount)}${leftSuffix}`
-          : `${formatCount(shownAmount)} ${line.format.suffix}${leftSuffix}`
+        : isMoneyFormat(line.format)
+          ? `${formatMoney(shownAmount, line.format)}${leftSuffix}`
+          : `${formatCount(shownAmount)} ${formatUnitSuffix(line.format)}${leftSuffix}`
 
     const secondaryText =
       line.resetsAt
         ? formatResetIn(now, line.resetsAt)
         : line.format.kind === "percent"
           ? `${line.limit}% cap`
-          : line.format.kind === "dollars"
-            ? `$${formatNumber(line.limit)} limit`
-            : `${formatCount(line.limit)} ${line.format.suffix}`
+          : isMoneyFormat(line.format)
+            ? `${formatMoney(line.limit, line.format)} limit`
+            : `${formatCount(line.limit)} ${formatUnitSuffix(line.format)}`
 
     // Calculate pace status if we have reset time and period duration
     const paceResult = hasPaceContext
@@ -1,7 +1,7 @@
 import { useCallback, useEffect, useRef } from "react"
 import { listen, type UnlistenFn } from "@tauri-apps/api/event"
 import { invoke } from "@tauri-apps/api/core"
-import type { ProviderOutput } from "@/lib/provider-types"
+import type { ProbeOutcome, ProviderOutput } from "@/lib/provider-types"
 
 type ProbeResult = {
   batchId: string
@@ -10,6 +10,9 @@ type ProbeResult = {
 
 type ProbeBatchComplete = {
   batchId: string
+  totalAccounts: number
+  successfulAccounts: number
+  summary?: Record<string, ProbeOutcome>
 }
 
 type ProbeBatchStarted = {
@@ -104,5 +107,18 @@ export function useProbeEvents({ onResult, onBatchComplete }: UseProbeEventsOpti
     }
   }, [])
 
-  return { startBatch }
+  const probeAccount = useCallback(
+    async (accountId: string) => {
+      const result = await invoke<ProbeResult>("probe_single_account", { accountId })
+      onResult(result.output)
+      return result.output
+    },
+    [onResult]
+  )
+
+  const clearCache = useCallback(async (providerId?: string) => {
+    await invoke("clear_probe_cache", { providerId: providerId ?? null })
+  }, [])
+
+  return { startBatch, probeAccount, clearCache }
 }
@@ -10,6 +10,8 @@ export type ProviderDescriptor = {
   name: string
   defaultAuthStrategyId: string
   authStrategies: ProviderAuthStrategy[]
+  documentationUrl?: string | null
+  dashboardUrl?: string | null
 }
 
 export type AccountRecord = {
@@ -22,6 +24,11 @@ export type AccountRecord = {
   updatedAt: string
   lastFetchAt?: string | null
   lastError?: string | null
+  lastErrorAt?: string | null
+  tags?: string[] | null
+  notes?: string | null
+  sortOrder?: number | null
+  iconOverride?: string | null
 }
 
 export type CreateAccountInput = {
@@ -36,6 +43,8 @@ export type UpdateAccountInput = {
   label?: string
   settings?: unknown
   clearLastError?: boolean
+  tags?: string[]
+  notes?: string
 }
 
 type AccountRecordWire = Partial<AccountRecord> & {
@@ -73,14 +82,41 @@ function normalizeAccountRecord(record: AccountRecordWire): AccountRecord {
         : record.last_error !== undefined
           ? record.last_error
           : null,
+    lastErrorAt: record.lastErrorAt ?? null,
+    tags: Array.isArray(record.tags) ? record.tags : null,
+    notes: record.notes ?? null,
+    sortOrder: typeof record.sortOrder === "number" ? record.sortOrder : null,
+    iconOverride: record.iconOverride ?? null,
   }
 }
 
+export type AccountErrorInfo = {
+  message: string
+  occurredAt: string
+}
+
+export type AccountWithCredentialStatus = {
+  account: AccountRecord
+  hasCredentials: boolean
+}
+
+type AccountWithCredentialStatusWire = {
+  account: AccountRecordWire
+  hasCredentials: boolean
+}
+
 export type OAuthStartResponse = {
   requestId: string
   url: string
   redirectUri: string
   userCode?: string | null
+  state?: string | null
+}
+
+export type OAuthFlowSummary = {
+  requestId: string
+  accountId: string
+  createdAtMs: number
 }
 
 export type OAuthResult = {
@@ -88,10 +124,39 @@ export type OAuthResult = {
   expiresAt: number
 }
 
+export type CodexOrganization = {
+  id: string
+  name: string | null
+  role: string | null
+}
+
 export async function listProviders(): Promise<ProviderDescriptor[]> {
   return invoke<ProviderDescriptor[]>("list_providers")
 }
 
+export async function listProviderAliases(): Promise<[string, string][]> {
+  return invoke<[string, string][]>("list_provider_aliases")
+}
+
+export async function validateProviderId(providerId: string): Promise<boolean> {
+  return invoke<boolean>("validate_provider_id", { providerId })
+}
+
+export async function validateStrategyId(strategyId: string): Promise<boolean> {
+  return invoke<boolean>("validate_strategy_id", { strategyId })
+}
+
+export async function validateAccountLabel(label: string): Promise<void> {
+  await invoke("validate_account_label", { label })
+}
+
+export async function validateProviderSettings(
+  providerId: string,
+  settings: unknown,
+): Promise<void> {
+  await invoke("validate_provider_settings", { providerId, settings })
+}
+
 export async function listAccounts(): Promise<AccountRecord[]> {
   const rows = await invoke<AccountRecordWire[]>("list_accounts")
   return rows
@@ -117,6 +182,24 @@ export async function deleteAccount(accountId: string): Promise<AccountRecord |
   return record ? normalizeAccountRecord(record) : null
 }
 
+export async function listAccountsWithCredentialStatus(): Promise<
+  AccountWithCredentialStatus[]
+> {
+  const rows = await invoke<AccountWithCredentialStatusWire[]>(
+    "list_accounts_with_credential_status",
+  )
+  return rows
+    .map((row) => ({
+      account: normalizeAccountRecord(row.account),
+      hasCredentials: row.hasCredentials === true,
+    }))
+    .filter(({ account }) => account.id.length > 0 && account.providerId.length > 0)
+}
+
+export async function setCopilotPat(accountId: string, token: string): Promise<void> {
+  await invoke("set_copilot_pat", { accountId, token })
+}
+
 export async function hasAccountCredentials(accountId: string): Promise<boolean> {
   return invoke<boolean>("has_account_credentials", { accountId })
 }
@@ -147,6 +230,14 @@ export async function cancelCodexOAuth(requestId: string): Promise<boolean> {
   return invoke<boolean>("cancel_codex_oauth", { requestId })
 }
 
+export async function listCodexOrganizations(accountId: string): Promise<CodexOrganization[]> {
+  return invoke<CodexOrganization[]>("list_codex_organizations", { accountId })
+}
+
+export async function setCodexOrganization(accountId: string, orgId: string): Promise<void> {
+  await invoke("set_codex_organization", { accountId, orgId })
+}
+
 export async function startAntigravityOAuth(accountId: string): Promise<OAuthStartResponse> {
   return invoke<OAuthStartResponse>("start_antigravity_oauth", { accountId })
 }
@@ -162,6 +253,29 @@ export async function cancelAntigravityOAuth(requestId: string): Promise<boolean
   return invoke<boolean>("cancel_antigravity_oauth", { requestId })
 }
 
+export async function listAntigravityProjects(accountId: string): Promise<string[]> {
+  return invoke<string[]>("list_antigravity_projects", { accountId })
+}
+
+export async function setAntigravityPreferredProject(
+  accountId: string,
+  projectId: string,
+): Promise<void> {
+  await invoke("set_antigravity_preferred_project", { accountId, projectId })
+}
+
+export async function updateAntigravityModelFilter(
+  accountId: string,
+  whitelist: string[],
+  blacklist: string[],
+): Promise<void> {
+  await invoke("update_antigravity_model_filter", { accountId, whitelist, blacklist })
+}
+
+export async function inferZaiRegion(apiKey: string): Promise<string | null> {
+  return invoke<string | null>("infer_zai_region", { apiKey })
+}
+
 export async function startClaudeOAuth(accountId: string): Promise<OAuthStartResponse> {
   return invoke<OAuthStartResponse>("start_claude_oauth", { accountId })
 }
@@ -206,3 +320,70 @@ export async function finishOpencodeOAuth(
 export async function cancelOpencodeOAuth(requestId: string): Promise<boolean> {
   return invoke<boolean>("cancel_opencode_oauth", { requestId })
 }
+
+export async function refreshOpencodeSession(
+  accountId: string,
+  timeoutMs?: number,
+): Promise<OAuthResult> {
+  return invoke<OAuthResult>("refresh_opencode_session", { accountId, timeoutMs })
+}
+
+export async function cancelOpencodeSessionRefresh(accountId: string): Promise<boolean> {
+  return invoke<boolean>("cancel_opencode_session_refresh", { accountId })
+}
+
+export async function listActiveOAuthFlows(): Promise<OAuthFlowSummary[]> {
+  return invoke<OAuthFlowSummary[]>("list_active_oauth_flows")
+}
+
+export async function cancelAllOAuthFlows(): Promise<number> {
+  return invoke<number>("cancel_all_oauth_flows")
+}
+
+export async function updateAccountTags(
+  accountId: string,
+  tags: string[],
+): Promise<AccountRecord> {
+  const record = await invoke<AccountRecordWire>("update_account_tags", { accountId, tags })
+  return normalizeAccountRecord(record)
+}
+
+export async function updateAccountNotes(
+  accountId: string,
+  notes: string,
+): Promise<AccountRecord> {
+  const record = await invoke<AccountRecordWire>("update_account_notes", { accountId, notes })
+  return normalizeAccountRecord(record)
+}
+
+export async function getAccountLastError(accountId: string): Promise<AccountErrorInfo | null> {
+  return invoke<AccountErrorInfo | null>("get_account_last_error", { accountId })
+}
+
+export async function setAccountIcon(accountId: string, icon: string | null): Promise<void> {
+  await invoke("set_account_icon", { accountId, icon })
+}
+
+export async function reorderAccounts(accountIds: string[]): Promise<void> {
+  await invoke("reorder_accounts", { accountIds })
+}
+
+export async function moveAccountBefore(accountId: string, targetId: string): Promise<void> {
+  await invoke("move_account_before", { accountId, targetId })
+}
+
+export async function listAccountsByTag(tag: string): Promise<AccountRecord[]> {
+  const rows = await invoke<AccountRecordWire[]>("list_accounts_by_tag", { tag })
+  return rows.map(normalizeAccountRecord)
+}
+
+export async function listOpencodeWorkspaces(accountId: string): Promise<string[]> {
+  return invoke<string[]>("list_opencode_workspaces", { accountId })
+}
+
+export async function setOpencodeActiveWorkspace(
+  accountId: string,
+  workspaceId: string,
+): Promise<void> {
+  await invoke("set_opencode_active_workspace", { accountId, workspaceId })
+}
@@ -1,7 +1,10 @@
 export type ProgressFormat =
   | { kind: "percent" }
+  | { kind: "currency"; symbol: string; decimals: number }
+  /** @deprecated Snapshots cached before `currency`; rendered as USD. */
   | { kind: "dollars" }
   | { kind: "count"; suffix: string }
+  | { kind: "time"; unit: "minutes" | "hours" | "days" }
 
 export type MetricLine =
   | { type: "text"; label: string; value: string; color?: string; subtitle?: string }
@@ -16,6 +19,15 @@ export type MetricLine =
       color?: string
     }
   | { type: "badge"; label: string; text: string; color?: string; subtitle?: string }
+  | { type: "table"; label: string; columns: string[]; rows: string[][] }
+  | {
+      type: "trend"
+      label: string
+      current: number
+      previous: number
+      format: ProgressFormat
+      color?: string
+    }
 
 export type ManifestLine = {
   type: "text" | "progress" | "badge"
@@ -29,6 +41,14 @@ export type ProviderOutput = {
   plan?: string
   lines: MetricLine[]
   iconUrl: string
+  fromCache?: boolean
+}
+
+export type ProbeOutcome = {
+  timestampMs: number
+  success: boolean
+  error?: string
+  durationMs: number
 }
 
 export type ProviderMeta = {
@@ -38,6 +58,8 @@ export type ProviderMeta = {
   brandColor?: string
   lines: ManifestLine[]
   primaryCandidates: string[]
+  documentationUrl?: string | null
+  dashboardUrl?: string | null
 }
 
 export type ProviderDisplayState = {
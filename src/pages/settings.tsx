@@ -206,6 +206,7 @@ interface SettingsPageProps {
     credentials: Record<string, unknown>
   ) => Promise<void>;
   onClearAccountCredentials: (providerId: string, accountId: string) => Promise<void>;
+  onInferZaiRegion?: (apiKey: string) => Promise<string | null>;
   accountOAuthSessionById: Record<string, AccountOAuthSession | undefined>;
   onStartAccountOAuth: (providerId: string, accountId: string) => Promise<void>;
   onCancelAccountOAuth: (providerId: string, accountId: string) => Promise<void>;
@@ -234,6 +235,7 @@ export function SettingsPage({
   onDeleteAccount,
   onSaveAccountCredentials,
   onClearAccountCredentials,
+  onInferZaiRegion,
   accountOAuthSessionById,
   onStartAccountOAuth,
   onCancelAccountOAuth,
@@ -400,6 +402,7 @@ export function SettingsPage({
         onDeleteAccount={onDeleteAccount}
         onSaveAccountCredentials={onSaveAccountCredentials}
         onClearAccountCredentials={onClearAccountCredentials}
+        onInferZaiRegion={onInferZaiRegion}
         oauthSessionByAccount={accountOAuthSessionById}
         onStartAccountOAuth={onStartAccountOAuth}
         onCancelAccountOAuth={onCancelAccountOAuth}